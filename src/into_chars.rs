@@ -10,7 +10,7 @@ pub trait IntoChars {
 /// 对静态字串实现`into_chars`方法
 impl IntoChars for &str {
     fn into_chars(self) -> impl Iterator<Item = char> {
-        self.to_owned().into_chars()
+        IntoChars::into_chars(self.to_owned())
     }
 }
 
@@ -16,11 +16,11 @@ use std::collections::VecDeque;
 type Expanded<T> = Vec<T>;
 // type Expanded<T> = Box<dyn Iterator<Item = T>>;
 
-/// 将一个元素的不可变引用进行扩展，得到其它元素的不可变引用
-/// * 🚩只读获取「被扩展元素」（不可变引用），返回「扩展到的元素」（迭代器）
-///
-/// ! 📝【2024-03-02 11:48:07】此处不使用迭代器`impl Iterator<Item = &T>`，因为其内存大小不确定
-/// ! 🚩【2024-03-02 11:48:07】此处现通过「装箱」返回更通用的迭代器（结合`into_iter`使用）
+// /// 将一个元素的不可变引用进行扩展，得到其它元素的不可变引用
+// /// * 🚩只读获取「被扩展元素」（不可变引用），返回「扩展到的元素」（迭代器）
+// ///
+// /// ! 📝【2024-03-02 11:48:07】此处不使用迭代器`impl Iterator<Item = &T>`，因为其内存大小不确定
+// /// ! 🚩【2024-03-02 11:48:07】此处现通过「装箱」返回更通用的迭代器（结合`into_iter`使用）
 // type ExpandF<T> = dyn ;
 
 /// BFT迭代器
@@ -1,5 +1,12 @@
-use crate::{catch_flow, if_return};
-use std::collections::VecDeque;
+#[cfg(feature = "str_processing")]
+use crate::PrefixMatch;
+use crate::{catch_flow, if_return, AhoCorasick, KmpPattern};
+use std::{
+    collections::VecDeque,
+    error::Error,
+    fmt::{self, Display, Formatter},
+    hash::Hash,
+};
 
 // ! ❌【2024-03-04 20:58:35】实践：因为「打包后需要从中借用值」的借用问题，再次弃用「独立使用『头迭代器』管理迭代过程」的想法
 // /// ! ❌【2024-03-04 20:28:24】无法经由「新struct代理」为[`BufferIterator`]生成「头迭代器」（同时不获取所有权）
@@ -88,6 +95,91 @@ where
     /// 是否迭代到了末尾
     /// * 🎯为了在获取「是否迭代完」时不修改迭代器
     is_ended: bool,
+    /// 回溯历史：检查点存续期间「被缓冲区消耗」的元素（副本）
+    /// * 🎯支持「建立检查点⇒尝试解析⇒失败回滚」的回溯式解析
+    /// * 📌首个元素总是位于「最早的检查点」处
+    /// * 📌所有检查点都结束后即清空
+    history: VecDeque<T>,
+    /// 存续中的检查点（栈）
+    /// * 📌格式：`(检查点序号, 检查点位置)`
+    ///   * 位置为「已消耗元素数」，参见[`Self::n_consumed`]
+    /// * 📌后建立的检查点总在后边：嵌套的检查点总比外层的先结束
+    checkpoints: Vec<(usize, usize)>,
+    /// 下一个检查点的序号
+    /// * 🎯识别「已经失效的检查点」
+    next_checkpoint_id: usize,
+    /// 元素的复制函数
+    /// * 🎯使「通用实现」中的消耗操作亦能记录回溯历史，而无需对所有方法限定[`Clone`]
+    /// * 🚩仅在有检查点存续时为[`Some`]，由[`Self::checkpoint`]设置
+    clone_item: Option<fn(&T) -> T>,
+    /// 缓冲区容量上限与溢出策略
+    /// * 🎯避免「预读过远」时缓冲区无限增长
+    ///   * 📄从永不结束的进程输出中查找标记
+    /// * 📌为[`None`]时不限容量
+    capacity: Option<(usize, BufferOverflowPolicy)>,
+    /// 缓冲区长度的历史峰值
+    peak_len_buffer: usize,
+    /// 因容量上限而丢弃的元素个数
+    n_dropped: usize,
+}
+
+/// 缓冲区达到容量上限时的处理策略
+/// * 📄参见[`BufferIterator::with_capacity`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BufferOverflowPolicy {
+    /// 拒绝继续预读
+    /// * 📌[`BufferIterator::try_head_next`]返回[`LookaheadExceeded`]错误
+    /// * 📌[`BufferIterator::head_next`]返回[`None`]，但不会标记「迭代完」
+    Error,
+    /// 丢弃缓冲区中最早的元素
+    /// * 📌被丢弃的元素视作「已消耗」
+    /// * 📌有检查点存续时，被丢弃的元素仍会记入回溯历史
+    DropOldest,
+}
+
+/// 「预读超限」错误
+/// * 🚩在[`BufferOverflowPolicy::Error`]策略下，缓冲区已满时继续预读产生
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LookaheadExceeded {
+    /// 缓冲区容量上限
+    pub capacity: usize,
+}
+
+impl Display for LookaheadExceeded {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "预读超出缓冲区容量上限{}", self.capacity)
+    }
+}
+
+impl Error for LookaheadExceeded {}
+
+/// 缓冲迭代器的「检查点」
+/// * 🎯用于在解析失败时回滚到先前的位置
+/// * 🚩由[`BufferIterator::checkpoint`]创建，交由[`BufferIterator::rollback`]或[`BufferIterator::commit`]结束
+/// * 📌不可复制：一个检查点只能被结束一次
+#[derive(Debug, PartialEq, Eq)]
+#[must_use = "checkpoints should be either rolled back or committed"]
+pub struct BufferCheckpoint {
+    /// 检查点序号
+    id: usize,
+    /// 在检查点栈中的深度
+    depth: usize,
+    /// 检查点位置（已消耗元素数）
+    position: usize,
+}
+
+impl BufferCheckpoint {
+    /// 获取检查点所在的位置
+    /// * 📌即建立检查点时的「已消耗元素数」，参见[`BufferIterator::n_consumed`]
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// 获取检查点的嵌套深度
+    /// * 📌最外层的检查点深度为`0`
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
 }
 
 /// 通用实现
@@ -108,9 +200,123 @@ where
             // 未开始迭代，未结束迭代
             is_began: false,
             is_ended: false,
+            // 没有检查点，也就没有回溯历史
+            history: VecDeque::new(),
+            checkpoints: Vec::new(),
+            next_checkpoint_id: 0,
+            clone_item: None,
+            // 默认不限容量
+            capacity: None,
+            peak_len_buffer: 0,
+            n_dropped: 0,
+        }
+    }
+
+    /// 构造函数（限制缓冲区容量）
+    /// * 📄容量与策略的含义参见[`Self::set_capacity`]
+    ///
+    /// # Panics
+    ///
+    /// ⚠️容量为`0`时panic
+    pub fn with_capacity(iterator: I, capacity: usize, policy: BufferOverflowPolicy) -> Self {
+        let mut iter = Self::new(iterator);
+        iter.set_capacity(capacity, policy);
+        iter
+    }
+
+    /// 设置缓冲区容量上限与溢出策略
+    /// * 📌只限制「预读」：通过[`Self::head_next`]（及基于它的方法）扩充缓冲区时生效
+    ///   * ⚠️缓冲区已经超出容量的，不会被截断；回滚放回的元素也不受限制
+    /// * 📌「相对缓冲区头部」超出容量的位置视作「取不到」
+    ///   * 📄[`Self::buffer_has`]返回`false`，[`Self::buffer_get`]返回[`None`]
+    ///   * 🎯确保[`BufferOverflowPolicy::DropOldest`]策略下，相对位置不会因「丢弃元素」而错位
+    ///   * 📌需要区分「取不到」与「预读超限」的，请使用`try_`系列方法，如[`Self::try_buffer_has`]
+    /// * 📌「查找」类方法在[`BufferOverflowPolicy::DropOldest`]策略下会滑动缓冲区，参见[`Self::find_next_pattern`]
+    ///
+    /// # Panics
+    ///
+    /// ⚠️容量为`0`时panic
+    pub fn set_capacity(&mut self, capacity: usize, policy: BufferOverflowPolicy) {
+        assert!(capacity > 0, "缓冲区容量不能为0");
+        self.capacity = Some((capacity, policy));
+    }
+
+    /// 取消缓冲区容量上限
+    pub fn unset_capacity(&mut self) {
+        self.capacity = None;
+    }
+
+    /// 获取缓冲区容量上限
+    /// * 📌不限容量⇒[`None`]
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity.map(|(capacity, _)| capacity)
+    }
+
+    /// 获取缓冲区溢出策略
+    /// * 📌不限容量⇒[`None`]
+    pub fn overflow_policy(&self) -> Option<BufferOverflowPolicy> {
+        self.capacity.map(|(_, policy)| policy)
+    }
+
+    /// 获取「缓冲区长度」的历史峰值
+    /// * 🎯评估预读的开销，以便设置合适的容量上限
+    pub fn peak_len_buffer(&self) -> usize {
+        self.peak_len_buffer
+    }
+
+    /// 重置「缓冲区长度」的历史峰值
+    /// * 🚩重置为当前的缓冲区长度
+    pub fn reset_peak_len_buffer(&mut self) {
+        self.peak_len_buffer = self.buffer.len();
+    }
+
+    /// 获取「因容量上限而丢弃的元素」个数
+    /// * 📌仅在[`BufferOverflowPolicy::DropOldest`]策略下增加
+    pub fn n_dropped(&self) -> usize {
+        self.n_dropped
+    }
+
+    /// 【内部】检查「相对缓冲区头部的位置」是否超出容量
+    /// * 🚩超出⇒[`LookaheadExceeded`]错误
+    fn check_capacity(&self, index: usize) -> Result<(), LookaheadExceeded> {
+        match self.capacity {
+            Some((capacity, _)) if index >= capacity => Err(LookaheadExceeded { capacity }),
+            _ => Ok(()),
+        }
+    }
+
+    /// 【内部】扫描时步进到「相对缓冲区头部的位置」`offset`处
+    /// * 🎯供「查找」类方法逐个扫描元素
+    /// * 🚩返回该元素「实际所在的位置」，内部迭代器耗尽⇒[`None`]
+    ///   * 📌超出容量上限时按策略处理
+    ///     * [`BufferOverflowPolicy::Error`]⇒返回错误
+    ///     * [`BufferOverflowPolicy::DropOldest`]⇒丢弃最早的元素，让缓冲区「滑动」：实际位置会比`offset`靠前
+    /// * ⚠️要求`offset`不超过缓冲区长度：只能逐个步进
+    fn try_scan_to(&mut self, offset: usize) -> Result<Option<usize>, LookaheadExceeded> {
+        if_return! { offset < self.len_buffer() => Ok(Some(offset)) }
+        match self.capacity {
+            Some((_, BufferOverflowPolicy::DropOldest)) if self.check_capacity(offset).is_err() => {
+                Ok(self
+                    .head_next_dropping()
+                    .is_some()
+                    .then(|| self.len_buffer() - 1))
+            }
+            _ => {
+                self.check_capacity(offset)?;
+                Ok(self.head_next().is_some().then_some(offset))
+            }
         }
     }
 
+    /// 【内部】计算「在`end`处结束、长`len`的匹配」的开头位置
+    /// * 📌开头已因容量上限被丢弃⇒[`LookaheadExceeded`]错误
+    fn match_start(&self, end: usize, len: usize) -> Result<usize, LookaheadExceeded> {
+        (end + 1).checked_sub(len).ok_or(LookaheadExceeded {
+            // * 🚩只有丢弃过元素才会出现，此时必定有容量上限
+            capacity: self.capacity().unwrap_or_default(),
+        })
+    }
+
     /// 获取「头索引」
     /// * 📌当【缓冲区非空】时，不会随[`Self::buffer_next`]的调用而改变
     /// * ⚠️不是「缓冲区开头」所在的索引
@@ -130,6 +336,17 @@ where
         (self.head + 1) - self.buffer.len()
     }
 
+    /// 获取「已消耗元素数」
+    /// * 📌即「已经被缓冲区迭代取走的元素」个数
+    /// * 📌未开始迭代时为`0`，其余情况与「缓冲区头索引」[`Self::buffer_head`]一致
+    /// * 🎯作为「检查点」的绝对位置
+    pub fn n_consumed(&self) -> usize {
+        match self.is_began {
+            true => self.buffer_head(),
+            false => 0,
+        }
+    }
+
     /// 获取「头元素」（不可变引用）
     /// * 📌实际上是「缓冲区末尾元素」
     /// * 🚩缓冲区非空⇒`Some(引用)`，缓冲区为空⇒`None`
@@ -182,7 +399,46 @@ where
     ///   * ❌无法返回可变引用："cannot mutate immutable variable `item`"
     ///
     /// * ℹ️要使用「头迭代器」，请使用`while let Some(item) = iter.head_next()`
+    /// * ⚠️缓冲区已满且策略为[`BufferOverflowPolicy::Error`]时，同样返回[`None`]
+    ///   * 📌需要区分「迭代完」与「预读超限」的，请使用[`Self::try_head_next`]
     pub fn head_next(&mut self) -> Option<&T> {
+        self.try_head_next().unwrap_or(None)
+    }
+
+    /// 头迭代（区分「预读超限」）
+    /// * 🚩同[`Self::head_next`]，但缓冲区达到容量上限时按策略处理
+    ///   * [`BufferOverflowPolicy::Error`]⇒返回错误，不从「内部迭代器」中取出元素
+    ///   * [`BufferOverflowPolicy::DropOldest`]⇒丢弃缓冲区最早的元素，再继续迭代
+    pub fn try_head_next(&mut self) -> Result<Option<&T>, LookaheadExceeded> {
+        // 缓冲区已满⇒按策略处理
+        if let Some((capacity, policy)) = self.capacity {
+            if self.buffer.len() >= capacity {
+                match policy {
+                    BufferOverflowPolicy::Error => return Err(LookaheadExceeded { capacity }),
+                    // * 📌只在确实取到新元素时丢弃，因此先行查看「内部迭代器」
+                    BufferOverflowPolicy::DropOldest => return Ok(self.head_next_dropping()),
+                }
+            }
+        }
+        Ok(self.head_next_unbounded())
+    }
+
+    /// 【内部】头迭代，并在取到元素时丢弃缓冲区最早的元素
+    fn head_next_dropping(&mut self) -> Option<&T> {
+        let item = self.iterator.next();
+        let Some(item) = item else {
+            self.is_ended = true;
+            return None;
+        };
+        // 丢弃最早的元素 | 经由「消耗」途径，以便记录回溯历史
+        self.buffer_pop_front();
+        self.n_dropped += 1;
+        self.push_head(item);
+        self.buffer.back()
+    }
+
+    /// 【内部】不限容量的头迭代
+    fn head_next_unbounded(&mut self) -> Option<&T> {
         // 从封装的迭代器中迭代出一个元素
         let item = self.iterator.next();
         // 判断是否结束
@@ -193,16 +449,14 @@ where
                 self.is_began = true;
                 // 存入缓冲区
                 self.buffer.push_back(item);
+                self.update_peak_len_buffer();
                 // 头索引不变
                 // 取出刚刚置入元素的引用
                 Some(self.buffer.back().unwrap()) // * 存入了值
             }
             // 已开始，正在中途
             (true, Some(item)) => {
-                // 头索引递增
-                self.head += 1;
-                // 存入缓冲区
-                self.buffer.push_back(item);
+                self.push_head(item);
                 // 取出刚刚置入元素的引用
                 Some(self.buffer.back().unwrap()) // * 存入了值
             }
@@ -216,6 +470,20 @@ where
         // ! 作为一般的「缓存迭代」，不返回「内置迭代器」迭代出的元素
     }
 
+    /// 【内部】（已开始迭代时）将新元素存入缓冲区末尾
+    fn push_head(&mut self, item: T) {
+        // 头索引递增
+        self.head += 1;
+        // 存入缓冲区
+        self.buffer.push_back(item);
+        self.update_peak_len_buffer();
+    }
+
+    /// 【内部】更新「缓冲区长度」的历史峰值
+    fn update_peak_len_buffer(&mut self) {
+        self.peak_len_buffer = self.peak_len_buffer.max(self.buffer.len());
+    }
+
     /// 缓冲区迭代：从**缓冲区**/**内置迭代器**中拿取元素
     /// * ⚠️总是会拿出元素（故可能涉及缓冲区的索引）
     /// * 🚩分「缓冲区是否为空」执行
@@ -228,10 +496,22 @@ where
             self.head_next();
         }
         // 尝试从缓冲区头部取出元素
-        self.buffer.pop_front()
+        self.buffer_pop_front()
         // ! 此处无需处理「缓冲区头索引」：会自动计算
     }
 
+    /// 【内部】从缓冲区头部取出元素
+    /// * 🚩所有「消耗缓冲区」的操作都要经过此处
+    ///   * 🎯在有检查点存续时，记录回溯历史
+    fn buffer_pop_front(&mut self) -> Option<T> {
+        let item = self.buffer.pop_front()?;
+        // 有检查点⇒复制一份存入历史
+        if let Some(clone_item) = self.clone_item {
+            self.history.push_back(clone_item(&item));
+        }
+        Some(item)
+    }
+
     /// 头迭代（多次）
     /// * 🚩执行多次头迭代（后续可优化）
     ///   * 返回「是否完全迭代」，即是否`n`次都迭代出了元素
@@ -300,11 +580,21 @@ where
     /// * 🚩直接判断并返回真/假
     /// * ⚠️越界⇒尝试从「内部迭代器」中取出元素
     ///   * 实在取不到⇒false
+    /// * ⚠️超出容量上限⇒false，参见[`Self::set_capacity`]
+    ///   * 📌需要区分「取不到」与「预读超限」的，请使用[`Self::try_buffer_has`]
     pub fn buffer_has(&mut self, index: usize) -> bool {
+        self.try_buffer_has(index).unwrap_or(false)
+    }
+
+    /// 缓冲区判别（区分「预读超限」）
+    /// * 🚩同[`Self::buffer_has`]，但超出容量上限时返回[`LookaheadExceeded`]错误
+    pub fn try_buffer_has(&mut self, index: usize) -> Result<bool, LookaheadExceeded> {
         // 先判断「index是否在缓冲区内」
-        index < self.len_buffer()
+        if_return! { index < self.len_buffer() => Ok(true) }
+        // 再判断「是否超出容量」
+        self.check_capacity(index)?;
         // 然后判断「缓冲区头能不能延伸到index处」
-        || self.head_next_n(index - self.len_buffer() + 1)
+        Ok(self.head_next_n(index - self.len_buffer() + 1))
     }
 
     /// 缓冲区获取
@@ -313,15 +603,19 @@ where
     /// * 🚩直接获取缓冲区相应位置的元素
     /// * ⚠️越界⇒尝试从「内部迭代器」中取出元素
     ///   * 实在取不到⇒[`None`]
+    /// * ⚠️超出容量上限⇒[`None`]，参见[`Self::set_capacity`]
+    ///   * 📌需要区分「取不到」与「预读超限」的，请使用[`Self::try_buffer_get`]
     pub fn buffer_get(&mut self, index: usize) -> Option<&T> {
-        match index < self.len_buffer() {
-            // * 已经判断了「是否越界」，所以直接进行数组索引
-            true => Some(&self.buffer[index]),
-            // * 越界⇒尝试扩展缓冲区，并获取头部（缓冲区末尾）元素
-            false => match self.head_next_n(index - self.len_buffer() + 1) {
-                true => self.head_item(),
-                false => None,
-            },
+        self.try_buffer_get(index).unwrap_or(None)
+    }
+
+    /// 缓冲区获取（区分「预读超限」）
+    /// * 🚩同[`Self::buffer_get`]，但超出容量上限时返回[`LookaheadExceeded`]错误
+    pub fn try_buffer_get(&mut self, index: usize) -> Result<Option<&T>, LookaheadExceeded> {
+        match self.try_buffer_has(index)? {
+            // * 已经扩展了缓冲区，所以直接进行数组索引
+            true => Ok(Some(&self.buffer[index])),
+            false => Ok(None),
         }
     }
 
@@ -337,8 +631,14 @@ where
 
     /// 缓冲区清空
     /// * 📌「缓冲区头索引」会自动更新
+    /// * 📌有检查点存续时，被清空的元素仍会记入回溯历史
     pub fn buffer_clear(&mut self) {
-        self.buffer.clear();
+        match self.clone_item {
+            // 无需记录历史⇒直接清空
+            None => self.buffer.clear(),
+            // 需要记录历史⇒逐个消耗
+            Some(..) => while self.buffer_pop_front().is_some() {},
+        }
     }
 
     /// 缓冲区转移（从前往后）
@@ -348,7 +648,7 @@ where
     pub fn buffer_transfer(&mut self, f: impl Fn(T)) {
         // 清除「缓冲区长度」个元素，即清除所有元素
         for _ in 0..self.len_buffer() {
-            f(self.buffer.pop_front().unwrap());
+            f(self.buffer_pop_front().unwrap());
         }
     }
 
@@ -358,7 +658,107 @@ where
     pub fn buffer_transfer_mut(&mut self, mut f: impl FnMut(T)) {
         // 清除「缓冲区长度」个元素，即清除所有元素
         for _ in 0..self.len_buffer() {
-            f(self.buffer.pop_front().unwrap());
+            f(self.buffer_pop_front().unwrap());
+        }
+    }
+}
+
+/// 对可复制的元素实现「检查点」与「回滚」
+/// * 🎯回溯式解析：尝试一种解析方式，失败了就回到原先的位置再尝试另一种
+/// * 📌检查点存续期间，被消耗的元素会被复制一份保留，直到所有检查点都结束
+/// * ✨支持嵌套：内层检查点可先于外层结束，外层结束时也会一并结束所有内层检查点
+impl<T, I> BufferIterator<T, I>
+where
+    T: Clone,
+    I: Iterator<Item = T>,
+{
+    /// 建立检查点
+    /// * 🚩记录当前的「已消耗元素数」，并开始保留被消耗的元素
+    /// * 📌返回的检查点须交由[`Self::rollback`]或[`Self::commit`]结束
+    pub fn checkpoint(&mut self) -> BufferCheckpoint {
+        // 开始记录回溯历史
+        self.clone_item = Some(T::clone);
+        let checkpoint = BufferCheckpoint {
+            id: self.next_checkpoint_id,
+            depth: self.checkpoints.len(),
+            position: self.n_consumed(),
+        };
+        self.next_checkpoint_id += 1;
+        self.checkpoints.push((checkpoint.id, checkpoint.position));
+        checkpoint
+    }
+
+    /// 获取存续中的检查点个数
+    pub fn n_checkpoints(&self) -> usize {
+        self.checkpoints.len()
+    }
+
+    /// 回滚到检查点
+    /// * 🚩将检查点之后被消耗的元素放回缓冲区头部
+    /// * 📌检查点与在其之后建立的检查点都会结束
+    /// * 📌「头索引」不变：放回的元素原本就已从内部迭代器中取出
+    ///
+    /// # Panics
+    ///
+    /// ⚠️检查点已失效（已随外层检查点结束）时panic
+    pub fn rollback(&mut self, checkpoint: BufferCheckpoint) {
+        self.validate_checkpoint(&checkpoint);
+        // 从历史末尾取回元素，逐个放回缓冲区头部
+        let n_rollback = self.n_consumed() - checkpoint.position;
+        for _ in 0..n_rollback {
+            // * 📌历史必定覆盖「最早检查点」之后的所有已消耗元素
+            let item = self.history.pop_back().unwrap();
+            self.buffer.push_front(item);
+        }
+        self.update_peak_len_buffer();
+        self.release_checkpoints_from(checkpoint.depth);
+    }
+
+    /// 提交检查点
+    /// * 🚩不回滚，仅结束检查点
+    /// * 📌检查点与在其之后建立的检查点都会结束
+    /// * 📌所有检查点都结束后，不再保留被消耗的元素
+    ///
+    /// # Panics
+    ///
+    /// ⚠️检查点已失效（已随外层检查点结束）时panic
+    pub fn commit(&mut self, checkpoint: BufferCheckpoint) {
+        self.validate_checkpoint(&checkpoint);
+        self.release_checkpoints_from(checkpoint.depth);
+    }
+
+    /// 尝试执行一段解析逻辑
+    /// * 🚩先建立检查点，然后根据结果决定提交或回滚
+    ///   * [`Ok`]⇒提交
+    ///   * [`Err`]⇒回滚
+    /// * 🎯简化「尝试某种解析方式，不行就换一种」的代码
+    pub fn attempt<R, E>(&mut self, f: impl FnOnce(&mut Self) -> Result<R, E>) -> Result<R, E> {
+        let checkpoint = self.checkpoint();
+        let result = f(self);
+        match result.is_ok() {
+            true => self.commit(checkpoint),
+            false => self.rollback(checkpoint),
+        }
+        result
+    }
+
+    /// 【内部】检查「检查点是否仍然存续」
+    fn validate_checkpoint(&self, checkpoint: &BufferCheckpoint) {
+        assert_eq!(
+            self.checkpoints.get(checkpoint.depth),
+            Some(&(checkpoint.id, checkpoint.position)),
+            "检查点已失效：{checkpoint:?}"
+        );
+    }
+
+    /// 【内部】结束指定深度及以内的检查点
+    /// * 🚩所有检查点都结束⇒清空历史、停止记录
+    fn release_checkpoints_from(&mut self, depth: usize) {
+        self.checkpoints.truncate(depth);
+        // * 📌只有最外层检查点结束时，历史的起点才会变化
+        if self.checkpoints.is_empty() {
+            self.history.clear();
+            self.clone_item = None;
         }
     }
 }
@@ -391,36 +791,51 @@ where
     /// * 🎯用于在语法解析中实现「前缀匹配」
     /// * ⚠️会改变缓冲区，且不区分「因不匹配而『非前缀』」与「因迭代完而『非前缀』」
     /// * 📌是`starts_with_at`的特殊情况，但做好了特化
-    pub fn starts_with(&mut self, mut pattern: impl Iterator<Item = T>) -> bool {
+    /// * ⚠️比对范围超出容量上限⇒false，参见[`Self::set_capacity`]
+    ///   * 📌避免[`BufferOverflowPolicy::DropOldest`]策略下丢弃已比对的元素
+    ///   * 📌需要区分「非前缀」与「预读超限」的，请使用[`Self::try_starts_with`]
+    pub fn starts_with(&mut self, pattern: impl Iterator<Item = T>) -> bool {
+        self.try_starts_with(pattern).unwrap_or(false)
+    }
+
+    /// 判断是否以`pattern`的元素开头（区分「预读超限」）
+    /// * 🚩同[`Self::starts_with`]，但比对范围超出容量上限时返回[`LookaheadExceeded`]错误
+    ///   * 📌不论何种策略：已比对的元素不会被丢弃
+    pub fn try_starts_with(
+        &mut self,
+        mut pattern: impl Iterator<Item = T>,
+    ) -> Result<bool, LookaheadExceeded> {
         // 先比对缓冲区中的元素（不会改变自身） | 此时「比对者」相对未知
         for item_self in &self.buffer {
             // ! ↑此处`item_self`不能加`&`，只需在需要比对时解引用
             // 从「比对者」中取出元素以对比
             match pattern.next() {
                 // 在`false`之前就没有⇒返回`true`
-                None => return true,
+                None => return Ok(true),
                 // 比对失败⇒返回`false`
-                Some(item_other) if *item_self != item_other => return false,
+                Some(item_other) if *item_self != item_other => return Ok(false),
                 // 比对成功⇒继续
                 _ => {}
             }
         }
         // 再从自身拿出来比对 | 此时「自身」相对未知
         for item_other in pattern {
+            // 超出容量上限⇒不再预读，返回错误
+            self.check_capacity(self.len_buffer())?;
             // 从「内部迭代器」中取出元素，置入缓冲区
             match self.head_next() {
                 // 然后对比
                 // 内部迭代器用尽⇒自身长度不够⇒返回`false`
-                None => return false,
+                None => return Ok(false),
                 // 迭代出元素⇒从缓冲区中取出元素，对齐，比对
                 Some(item_self) => {
                     // 比对失败⇒返回`false`
-                    if_return! { *item_self != item_other => false }
+                    if_return! { *item_self != item_other => Ok(false) }
                 }
             }
         }
         // 比对都没失败⇒成功⇒`true`
-        true
+        Ok(true)
     }
 
     /// 判断从「『缓冲区头』后`buffer_offset`个索引处」开始是否以`pattern`的元素开头
@@ -463,36 +878,53 @@ where
 
     /// 从另一个字符迭代器中返回「缓冲区之后下一个匹配的子串」的**开头位置**
     /// * 🎯使用「前缀匹配字符串」在识别到「左括弧」后寻找「右括弧」
-    /// * 🚩实际上可以直接上暴力算法：不断进行前缀匹配，失败了就挪位，直到匹配成功
-    ///   * 💭需要对子串进行缓冲，可能需要构造另一个缓冲区迭代器
+    /// * 🚩先预编译模式，再使用线性时间的KMP算法查找
+    ///   * 📌原先的暴力算法在每个偏移处都要重新比对整个模式：O(n·m)
+    ///   * 💡需要多次查找同一模式时，推荐预编译后使用[`Self::find_next_pattern`]
     /// * ⚠️【2024-03-16 17:20:11】目前要求子串必须有限
     ///   * 会进行collect
     /// * 📌不会让「缓冲区头」位移
     pub fn find_next_prefix(&mut self, pattern: impl Iterator<Item = T>) -> Option<usize> {
-        // 先构造子串的缓冲区迭代器
-        let items = pattern.collect::<Vec<_>>();
+        self.find_next_pattern(&KmpPattern::new(pattern))
+    }
+
+    /// 使用预编译的模式，返回「缓冲区之后下一个匹配的子串」的**开头位置**
+    /// * 🚩KMP算法：每个元素只比对（均摊）常数次，不回退
+    ///   * 📌复杂度：O(扫描长度 + 模式长度)
+    /// * 🎯在长输出中反复查找同一标记（如`ANSWER:`）
+    /// * 📌空模式⇒`Some(0)`
+    /// * 📌不会让「缓冲区头」位移：缓冲区会扩展到模式末尾（找到时）或内部迭代器耗尽（找不到时）
+    /// * 📌达到容量上限时按策略处理，参见[`Self::set_capacity`]
+    ///   * [`BufferOverflowPolicy::Error`]⇒停止扫描，返回[`None`]
+    ///   * [`BufferOverflowPolicy::DropOldest`]⇒丢弃最早的元素继续扫描，位置相对丢弃后的「缓冲区头」
+    ///     * 🎯从永不结束的输出中查找标记
+    ///   * 📌需要区分「找不到」与「预读超限」的，请使用[`Self::try_find_next_pattern`]
+    pub fn find_next_pattern(&mut self, pattern: &KmpPattern<T>) -> Option<usize> {
+        self.try_find_next_pattern(pattern).unwrap_or(None)
+    }
+
+    /// 使用预编译的模式查找（区分「预读超限」）
+    /// * 🚩同[`Self::find_next_pattern`]，但以下情况返回[`LookaheadExceeded`]错误
+    ///   * [`BufferOverflowPolicy::Error`]策略下，扫描到容量上限仍未找到
+    ///   * [`BufferOverflowPolicy::DropOldest`]策略下，找到的匹配比容量更长（开头已被丢弃）
+    pub fn try_find_next_pattern(
+        &mut self,
+        pattern: &KmpPattern<T>,
+    ) -> Result<Option<usize>, LookaheadExceeded> {
         // 空字串⇒直接返回`Some(0)`
-        if_return! { items.is_empty() => Some(0) }
-        let len_items = items.len();
-        // ! 📝【2024-03-16 16:41:47】迭代器`find`/`position`的方法行不通：闭包内重复借用
-        // 然后开始匹配
-        let mut head_offset = 0;
-        loop {
-            // 确保自身（偏移之后）长度足够，没有⇒返回`None`
-            let expected_pattern_end_i = head_offset + len_items - 1;
-            if !self.buffer_has(expected_pattern_end_i) {
-                break None;
-            }
-            // 尝试逐个匹配
-            let found_relative_prefix = self._starts_with_at_unchecked(head_offset, items.iter());
-            // 检查是否找到
-            match found_relative_prefix {
-                // 找到⇒返回`Some(head_offset)`
-                true => break Some(head_offset),
-                // 未找到⇒头索引偏移量递增
-                false => head_offset += 1,
+        if_return! { pattern.is_empty() => Ok(Some(0)) }
+        let mut matched = 0;
+        let mut offset = 0;
+        // 逐个扫描缓冲区（不够就从内部迭代器中取）
+        // * 📌KMP的状态不依赖被丢弃的元素
+        while let Some(i) = self.try_scan_to(offset)? {
+            matched = pattern.step(matched, &self.buffer[i]);
+            if matched == pattern.len() {
+                return self.match_start(i, pattern.len()).map(Some);
             }
+            offset = i + 1;
         }
+        Ok(None)
     }
 
     /// 若以`pattern`的元素开头⇒跳过元素
@@ -520,6 +952,53 @@ where
     }
 }
 
+/// 对可哈希的元素实现「多模式查找」
+impl<T, I> BufferIterator<T, I>
+where
+    T: Eq + Hash + Clone,
+    I: Iterator<Item = T>,
+{
+    /// 使用预编译的多模式自动机，查找「缓冲区之后下一个匹配的模式」
+    /// * 🚩Aho-Corasick算法：一次扫描同时查找所有模式
+    /// * 📌返回`(匹配开头的位置, 模式的关联内容)`
+    ///   * 📌位置为相对「缓冲区头」的偏移
+    /// * 📌最早结束的匹配优先，同时结束的最长者优先（参见[`AhoCorasick`]）
+    ///   * 🎯只从内部迭代器中取出「必要数量」的元素
+    /// * 📌不会让「缓冲区头」位移
+    /// * 💡可从「词缀匹配字典」构造：`AhoCorasick::from(&dict)`
+    /// * 📌达到容量上限时的处理同[`Self::find_next_pattern`]
+    ///   * 📌需要区分「找不到」与「预读超限」的，请使用[`Self::try_find_any_of`]
+    pub fn find_any_of<'p, P>(
+        &mut self,
+        patterns: &'p AhoCorasick<T, P>,
+    ) -> Option<(usize, &'p P)> {
+        self.try_find_any_of(patterns).unwrap_or(None)
+    }
+
+    /// 使用预编译的多模式自动机查找（区分「预读超限」）
+    /// * 🚩同[`Self::find_any_of`]，返回错误的情况同[`Self::try_find_next_pattern`]
+    pub fn try_find_any_of<'p, P>(
+        &mut self,
+        patterns: &'p AhoCorasick<T, P>,
+    ) -> Result<Option<(usize, &'p P)>, LookaheadExceeded> {
+        let mut state = patterns.start();
+        // 空模式⇒在开头匹配
+        if let Some((_, associated)) = patterns.output(state) {
+            return Ok(Some((0, associated)));
+        }
+        let mut offset = 0;
+        // * 📌自动机的状态不依赖被丢弃的元素
+        while let Some(i) = self.try_scan_to(offset)? {
+            state = patterns.step(state, &self.buffer[i]);
+            if let Some((len, associated)) = patterns.output(state) {
+                return Ok(Some((self.match_start(i, len)?, associated)));
+            }
+            offset = i + 1;
+        }
+        Ok(None)
+    }
+}
+
 /// 对「字符迭代器」实现的专用方法
 impl<I> BufferIterator<char, I>
 where
@@ -532,7 +1011,7 @@ where
         // 遍历一定长度
         for _ in 0..len {
             // 取出字符 | 肯定有
-            let ch = self.buffer.pop_front().unwrap();
+            let ch = self.buffer_pop_front().unwrap();
             // 将字符加入字符串
             target.push(ch);
         }
@@ -554,6 +1033,100 @@ where
     }
 }
 
+/// 对「字符迭代器」实现「基于词缀匹配字典的前缀匹配」
+/// * 🎯词法解析时，在当前位置直接匹配最长的系词、括弧等
+///   * ✨无需先把字符预取到临时的[`String`]中，再调用[`PrefixMatch::match_prefix`]
+/// * 📌兼容所有「前缀匹配」的实现：[`crate::XFixMatchDict`]、[`crate::PrefixMatchDictPair`]、[`crate::BiFixMatchDictPair`]……
+#[cfg(feature = "str_processing")]
+impl<I> BufferIterator<char, I>
+where
+    I: Iterator<Item = char>,
+{
+    /// 【内部】逐个拉取字符，直到「更多的字符」不会改变前缀匹配的结果
+    /// * 🚩每拉取一个字符，交由[`PrefixMatch::prefix_needs_more_chars`]判断
+    ///   * 📌只取出必要的字符：不会因字典中有更长的词缀而多读
+    ///   * 📌字典树等实现的优化同样有效
+    /// * 📌内部迭代器耗尽时提前停止
+    /// * ⚠️所需字符超出容量上限⇒[`LookaheadExceeded`]错误
+    ///   * 📌不会退而返回更短的匹配：更长的词缀可能在容量之外
+    fn try_buffer_fill_for_dict<PrefixTerm, Dict>(
+        &mut self,
+        dict: &Dict,
+    ) -> Result<(), LookaheadExceeded>
+    where
+        Dict: PrefixMatch<PrefixTerm>,
+    {
+        while dict.prefix_needs_more_chars(&self.buffer)
+            && self.try_buffer_has(self.len_buffer())?
+        {}
+        Ok(())
+    }
+
+    /// 使用「前缀匹配字典」在缓冲区头部进行前缀匹配
+    /// * 🚩逐个拉取字符，直到匹配结果确定，再直接在缓冲区上匹配
+    ///   * 📌交由[`PrefixMatch::match_prefix_chars`]：字典树等实现的优化同样有效
+    /// * 📌不会消耗缓冲区，但可能扩展缓冲区
+    /// * ⚠️所需字符超出容量上限⇒[`None`]
+    ///   * 📌需要区分「匹配失败」与「预读超限」的，请使用[`Self::try_match_prefix_dict`]
+    pub fn match_prefix_dict<'d, PrefixTerm, Dict>(
+        &mut self,
+        dict: &'d Dict,
+    ) -> Option<&'d PrefixTerm>
+    where
+        Dict: PrefixMatch<PrefixTerm>,
+    {
+        self.try_match_prefix_dict(dict).unwrap_or(None)
+    }
+
+    /// 使用「前缀匹配字典」在缓冲区头部进行前缀匹配（区分「预读超限」）
+    /// * 🚩同[`Self::match_prefix_dict`]，但所需字符超出容量上限时返回[`LookaheadExceeded`]错误
+    pub fn try_match_prefix_dict<'d, PrefixTerm, Dict>(
+        &mut self,
+        dict: &'d Dict,
+    ) -> Result<Option<&'d PrefixTerm>, LookaheadExceeded>
+    where
+        Dict: PrefixMatch<PrefixTerm>,
+    {
+        self.try_buffer_fill_for_dict(dict)?;
+        Ok(dict.match_prefix_chars(&self.buffer))
+    }
+
+    /// 使用「前缀匹配字典」在缓冲区头部进行前缀匹配，并跳过匹配到的前缀
+    /// * 🚩匹配成功⇒消耗被匹配的字符，并返回「前缀条目」
+    ///   * 📌消耗的字符数以[`PrefixMatch::match_prefix_chars_longest`]的结果为准
+    /// * 📌匹配失败时不会消耗缓冲区
+    /// * ⚠️所需字符超出容量上限⇒[`None`]，且不会消耗缓冲区
+    ///   * 📌需要区分「匹配失败」与「预读超限」的，请使用[`Self::try_skip_when_match_prefix_dict`]
+    /// * 📄参见[`Self::match_prefix_dict`]、[`Self::skip_when_starts_with`]
+    pub fn skip_when_match_prefix_dict<'d, PrefixTerm, Dict>(
+        &mut self,
+        dict: &'d Dict,
+    ) -> Option<&'d PrefixTerm>
+    where
+        Dict: PrefixMatch<PrefixTerm>,
+    {
+        self.try_skip_when_match_prefix_dict(dict).unwrap_or(None)
+    }
+
+    /// 使用「前缀匹配字典」进行前缀匹配，并跳过匹配到的前缀（区分「预读超限」）
+    /// * 🚩同[`Self::skip_when_match_prefix_dict`]，但所需字符超出容量上限时返回[`LookaheadExceeded`]错误
+    pub fn try_skip_when_match_prefix_dict<'d, PrefixTerm, Dict>(
+        &mut self,
+        dict: &'d Dict,
+    ) -> Result<Option<&'d PrefixTerm>, LookaheadExceeded>
+    where
+        Dict: PrefixMatch<PrefixTerm>,
+    {
+        self.try_buffer_fill_for_dict(dict)?;
+        let Some(matched) = dict.match_prefix_chars_longest(&self.buffer) else {
+            return Ok(None);
+        };
+        // 跳过被匹配的字符 | 已在缓冲区内
+        self.buffer_consume_n(matched.n_chars);
+        Ok(Some(matched.term))
+    }
+}
+
 /// 单元测试
 #[cfg(test)]
 mod tests {
@@ -838,6 +1411,353 @@ mod tests {
             iter.buffer_head() => 6 // 此时「缓冲区头索引」改变：从（以原迭代器为开头）第七个字符开始
         }
     }
+
+    /// 测试/检查点与回滚
+    #[test]
+    fn test_checkpoint() {
+        let mut iter = BufferIterator::new("(&&, A, B)".chars());
+
+        // 外层检查点：尚未开始迭代
+        let outer = iter.checkpoint();
+        asserts! {
+            outer.position() => 0,
+            outer.depth() => 0,
+            iter.n_checkpoints() => 1,
+        }
+        // 消耗"(&&"
+        iter.buffer_consume_n(3);
+        assert_eq!(iter.n_consumed(), 3);
+
+        // 内层检查点：尝试以", B"开头失败后回滚
+        let inner = iter.checkpoint();
+        asserts! {
+            inner.position() => 3,
+            inner.depth() => 1,
+        }
+        iter.buffer_consume_n(2); // ", "
+        let c = iter.buffer_next();
+        asserts! {
+            c => Some('A'),
+            iter.n_consumed() => 6,
+        }
+        iter.rollback(inner);
+        asserts! {
+            iter.n_consumed() => 3,
+            iter.n_checkpoints() => 1,
+            iter.buffer_next() => Some(','),
+        }
+
+        // 内层检查点：提交后不影响外层
+        let inner = iter.checkpoint();
+        iter.buffer_consume_n(2); // " A"
+        iter.commit(inner);
+        asserts! {
+            iter.n_consumed() => 6,
+            iter.n_checkpoints() => 1,
+        }
+
+        // 外层回滚：回到最开始
+        iter.rollback(outer);
+        asserts! {
+            iter.n_consumed() => 0,
+            iter.n_checkpoints() => 0,
+            iter.head() => 5, // 头索引不受回滚影响
+            iter.collect::<String>() => "(&&, A, B)",
+        }
+    }
+
+    /// 测试/检查点与缓冲区清空、尝试解析
+    #[test]
+    fn test_checkpoint_attempt() {
+        let mut iter = BufferIterator::new("<A --> B>".chars());
+        // 失败⇒回滚（清空缓冲区也能回滚）
+        let result = iter.attempt(|iter| {
+            iter.head_next_n(3);
+            iter.buffer_clear();
+            match iter.skip_when_starts_with("==>".chars()) {
+                true => Ok(()),
+                false => Err(iter.n_consumed()),
+            }
+        });
+        asserts! {
+            result => Err(3),
+            iter.n_consumed() => 0,
+            iter.len_buffer() => 4, // 回滚的"<A "，加上比对时新取出的'-'
+        }
+        // 成功⇒提交
+        let result = iter.attempt(|iter| match iter.skip_when_starts_with("<".chars()) {
+            true => Ok(iter.n_consumed()),
+            false => Err(()),
+        });
+        asserts! {
+            result => Ok(1),
+            iter.n_checkpoints() => 0,
+            iter.collect::<String>() => "A --> B>",
+        }
+    }
+
+    /// 测试/基于字典的前缀匹配
+    #[test]
+    #[cfg(feature = "str_processing")]
+    fn test_match_prefix_dict() {
+        use crate::{
+            bi_fix_match_dict_pair, prefix_match_dict, prefix_match_dict_pair, x_fix_match_dict,
+            BiFixMatchDictPair, PrefixMatchDict, PrefixMatchDictPair,
+        };
+        // 系词 | 词缀匹配字典
+        let copulas = prefix_match_dict!("-->" "<->" "==>" "<=>" "--" "-");
+        let mut iter = BufferIterator::new("--> B>".chars());
+        asserts! {
+            // 仅匹配，不消耗
+            iter.match_prefix_dict(&copulas).map(String::as_str) => Some("-->"),
+            iter.n_consumed() => 0,
+            // 匹配并消耗
+            iter.skip_when_match_prefix_dict(&copulas).map(String::as_str) => Some("-->"),
+            iter.n_consumed() => 3,
+            // 匹配失败⇒不消耗
+            iter.skip_when_match_prefix_dict(&copulas) => None,
+            iter.n_consumed() => 3,
+        }
+        // 只取出必要的字符：最长的词缀只有三个字符
+        let mut iter = BufferIterator::new("==> 后面很长很长".chars());
+        asserts! {
+            iter.match_prefix_dict(&copulas).map(String::as_str) => Some("==>"),
+            iter.len_buffer() => 3,
+        }
+        // 逐个拉取字符：匹配结果确定即停止，可用于永不结束的输入
+        let mut iter = BufferIterator::new(std::iter::repeat('-'));
+        asserts! {
+            iter.skip_when_match_prefix_dict(&copulas).map(String::as_str) => Some("--"),
+            iter.len_buffer() => 1, // 只多读了一个用于排除`-->`的字符
+        }
+        let brackets = prefix_match_dict!("(" "<<很长很长的括弧>>");
+        let mut iter = BufferIterator::new("(A)".chars());
+        asserts! {
+            iter.match_prefix_dict(&brackets).map(String::as_str) => Some("("),
+            iter.len_buffer() => 1,
+        }
+        // 超出容量上限⇒预读超限，而非更短的匹配
+        for policy in [
+            BufferOverflowPolicy::Error,
+            BufferOverflowPolicy::DropOldest,
+        ] {
+            let mut iter = BufferIterator::with_capacity("-->".chars(), 2, policy);
+            asserts! {
+                iter.try_match_prefix_dict(&copulas) => Err(LookaheadExceeded { capacity: 2 }),
+                iter.try_skip_when_match_prefix_dict(&copulas) => Err(LookaheadExceeded { capacity: 2 }),
+                iter.match_prefix_dict(&copulas) => None,
+                iter.n_consumed() => 0,
+                iter.n_dropped() => 0,
+            }
+        }
+        // 字典树 | 直接在缓冲区上匹配
+        let trie = crate::XFixTrieDict::from(&copulas);
+        let mut iter = BufferIterator::new("<=>B".chars());
+        asserts! {
+            iter.skip_when_match_prefix_dict(&trie).map(String::as_str) => Some("<=>"),
+            iter.len_buffer() => 0, // 不会多读
+            iter.collect::<String>() => "B",
+        }
+
+        // 括弧 | 前缀配对字典
+        let brackets: PrefixMatchDictPair<String> = prefix_match_dict_pair!(
+            "(" => ")"
+            "<" => ">"
+            "{" => "}"
+        );
+        let mut iter = BufferIterator::new("{A}".chars());
+        asserts! {
+            iter.skip_when_match_prefix_dict(&brackets) => Some(&("{".into(), "}".into())),
+            iter.collect::<String>() => "A}",
+        }
+
+        // 多字符括弧 | 双向配对字典
+        let brackets: BiFixMatchDictPair = bi_fix_match_dict_pair!(
+            "(" => ")"
+            r"\left(" => r"\right)"
+        );
+        let mut iter = BufferIterator::new(r"\left(A\right)".chars());
+        asserts! {
+            iter.skip_when_match_prefix_dict(&brackets).map(|(_, r, _)| r.as_str()) => Some(r"\right)"),
+            iter.collect::<String>() => r"A\right)",
+        }
+    }
+
+    /// 测试/预编译模式的查找
+    #[test]
+    fn test_find_pattern() {
+        let text = "IN: <A --> B>.\nOUT: <A --> B>.\nANSWER: <A --> B>. %1.0;0.9%";
+        let answer = KmpPattern::new("ANSWER:".chars());
+        let out = KmpPattern::new("OUT:".chars());
+        let mut iter = BufferIterator::new(text.chars());
+        // 同一模式，多次查找
+        let f = iter.find_next_pattern(&out);
+        asserts! {
+            f => Some(15),
+            iter.len_buffer() => 19, // 缓冲区只扩展到模式末尾
+        }
+        iter.buffer_consume_n(19);
+        asserts! {
+            iter.find_next_pattern(&out) => None,
+            iter.find_next_pattern(&answer) => Some(12),
+            iter.n_consumed() => 19,
+        }
+        // 部分匹配后失配的情形
+        let mut iter = BufferIterator::new("aabaabaaab".chars());
+        asserts! {
+            iter.find_next_pattern(&KmpPattern::new("aaab".chars())) => Some(6),
+            iter.find_next_prefix("abaa".chars()) => Some(1),
+        }
+    }
+
+    /// 测试/多模式查找
+    #[test]
+    fn test_find_any_of() {
+        let markers = AhoCorasick::new([("ANSWER:".chars(), "answer"), ("ERR:".chars(), "error")]);
+        let mut iter = BufferIterator::new("IN: <A --> B>.\nANSWER: <A --> B>.\nERR: x".chars());
+        let found = iter.find_any_of(&markers);
+        asserts! {
+            found => Some((15, &"answer")),
+            iter.len_buffer() => 22,
+        }
+        iter.buffer_consume_n(22);
+        asserts! {
+            iter.find_any_of(&markers) => Some((12, &"error")),
+        }
+        iter.buffer_clear();
+        asserts! {
+            iter.find_any_of(&markers) => None,
+        }
+    }
+
+    /// 测试/限制缓冲区容量
+    #[test]
+    fn test_capacity() {
+        // 拒绝预读
+        let mut iter =
+            BufferIterator::with_capacity("abcdef".chars(), 3, BufferOverflowPolicy::Error);
+        asserts! {
+            iter.buffer_has(2),
+            !iter.buffer_has(3), // 超出容量
+            iter.try_head_next() => Err(LookaheadExceeded { capacity: 3 }),
+            iter.head_next() => None,
+            !iter.is_ended(), // 不会标记「迭代完」
+            iter.find_next_prefix("ef".chars()) => None, // 超出预读范围
+            iter.find_next_prefix("bc".chars()) => Some(1),
+        }
+        // 消耗之后可继续预读
+        iter.buffer_consume_n(2);
+        asserts! {
+            iter.head_next() => Some(&'d'),
+            iter.find_next_prefix("de".chars()) => Some(1),
+            iter.peak_len_buffer() => 3,
+            iter.n_dropped() => 0,
+            iter.collect::<String>() => "cdef",
+        }
+        // 丢弃最早的元素
+        let mut iter =
+            BufferIterator::with_capacity("abcdef".chars(), 3, BufferOverflowPolicy::DropOldest);
+        while iter.head_next().is_some() {}
+        asserts! {
+            iter.n_dropped() => 3,
+            iter.n_consumed() => 3, // 被丢弃的元素视作「已消耗」
+            iter.peak_len_buffer() => 3,
+            iter.buffer_get(3) => None, // 相对位置不会错位
+            iter.buffer_get(2) => Some(&'f'),
+        }
+        // 被丢弃的元素仍可回滚
+        let mut iter =
+            BufferIterator::with_capacity("abcdef".chars(), 2, BufferOverflowPolicy::DropOldest);
+        let checkpoint = iter.checkpoint();
+        iter.head_consume_n(4);
+        asserts! {
+            iter.buffer_iter().collect::<String>() => "cd",
+        }
+        iter.rollback(checkpoint);
+        asserts! {
+            iter.peak_len_buffer() => 4, // 回滚放回的元素不受限制
+            iter.collect::<String>() => "abcdef",
+        }
+        // 前缀匹配不会越过容量上限：已比对的元素不会被丢弃
+        let mut iter =
+            BufferIterator::with_capacity("abcx".chars(), 2, BufferOverflowPolicy::DropOldest);
+        asserts! {
+            !iter.starts_with("abc".chars()),
+            iter.starts_with("ab".chars()),
+            !iter.skip_when_starts_with("abc".chars()),
+            iter.n_dropped() => 0,
+            iter.skip_when_starts_with("ab".chars()),
+            iter.collect::<String>() => "cx", // 不会多消耗`x`
+        }
+        // 区分「预读超限」与「取不到」
+        let mut iter = BufferIterator::with_capacity("abc".chars(), 2, BufferOverflowPolicy::Error);
+        asserts! {
+            iter.try_buffer_has(1) => Ok(true),
+            iter.try_buffer_has(2) => Err(LookaheadExceeded { capacity: 2 }),
+            iter.try_buffer_get(1) => Ok(Some(&'b')),
+            iter.try_buffer_get(2) => Err(LookaheadExceeded { capacity: 2 }),
+            iter.try_starts_with("ax".chars()) => Ok(false),
+            iter.try_starts_with("abc".chars()) => Err(LookaheadExceeded { capacity: 2 }),
+            iter.try_find_next_pattern(&KmpPattern::new("c".chars())) => Err(LookaheadExceeded { capacity: 2 }),
+            iter.try_find_any_of(&AhoCorasick::new([("c".chars(), ())])) => Err(LookaheadExceeded { capacity: 2 }),
+        }
+        iter.buffer_consume_n(2);
+        asserts! {
+            iter.try_buffer_has(1) => Ok(false), // 内部迭代器耗尽
+            iter.try_find_next_pattern(&KmpPattern::new("x".chars())) => Ok(None),
+        }
+        // 丢弃最早的元素⇒滑动窗口：在永不结束的输出中查找标记
+        let endless = || {
+            std::iter::repeat_n('.', 100)
+                .chain("ANSWER: 42".chars())
+                .chain(std::iter::repeat('.'))
+        };
+        let mut iter =
+            BufferIterator::with_capacity(endless(), 8, BufferOverflowPolicy::DropOldest);
+        let marker = KmpPattern::new("ANSWER:".chars());
+        asserts! {
+            iter.find_next_pattern(&marker) => Some(1),
+            iter.n_dropped() => 99,
+            iter.peak_len_buffer() => 8,
+        }
+        iter.buffer_consume_n(1 + marker.len());
+        asserts! {
+            iter.by_ref().take(3).collect::<String>() => " 42",
+        }
+        let mut iter =
+            BufferIterator::with_capacity(endless(), 8, BufferOverflowPolicy::DropOldest);
+        let markers = AhoCorasick::new([("ANSWER:".chars(), "answer"), ("ERR:".chars(), "error")]);
+        asserts! {
+            iter.find_any_of(&markers) => Some((1, &"answer")),
+        }
+        // 匹配比容量更长⇒开头已被丢弃⇒预读超限
+        let mut iter =
+            BufferIterator::with_capacity(endless(), 4, BufferOverflowPolicy::DropOldest);
+        asserts! {
+            iter.try_find_next_pattern(&marker) => Err(LookaheadExceeded { capacity: 4 }),
+        }
+        // 取消限制
+        let mut iter =
+            BufferIterator::with_capacity("abcdef".chars(), 1, BufferOverflowPolicy::Error);
+        iter.unset_capacity();
+        asserts! {
+            iter.capacity() => None,
+            iter.buffer_has(5),
+            iter.peak_len_buffer() => 6,
+        }
+    }
+
+    /// 测试/失效的检查点
+    #[test]
+    #[should_panic]
+    fn test_checkpoint_invalidated() {
+        let mut iter = BufferIterator::new("abc".chars());
+        let outer = iter.checkpoint();
+        let inner = iter.checkpoint();
+        iter.commit(outer);
+        // 内层检查点已随外层结束
+        iter.rollback(inner);
+    }
 }
 
 // ! ❌【2024-03-17 15:52:37】无法实现迭代器新方法「批量解引用」
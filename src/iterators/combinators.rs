@@ -0,0 +1,513 @@
+//! 基于[`BufferIterator`]的「解析器组合子」
+//! * 🎯为各种Narsese方言快速编写小型词法/语法解析器
+//!   * 📌原先的「前缀匹配」「跳过前缀」「检查点回滚」等功能已在[`BufferIterator`]中具备
+//!   * ✨此处将其组合为可复用的「解析器」
+//! * 📌「解析器」即`FnMut(&mut BufferIterator<T, I>) -> ParseResult<输出, T>`的闭包
+//!   * 🚩所有组合子都返回这样的闭包，亦可直接传入自定义的闭包
+//! * 📌所有的错误都携带「出错时的位置」，即[`BufferIterator::n_consumed`]
+//! * 📌只有「需要回溯」的组合子（如[`alt`]、[`many`]、[`separated`]、[`opt`]）会在失败时回滚
+//!   * ⚠️其它组合子失败时，可能已经消耗了部分元素
+//! * ⚠️为避免与其它名称冲突，此模块中的函数不在包的根路径下导出
+//!   * 🚩使用`use nar_dev_utils::combinators::*`导入
+
+use crate::BufferIterator;
+use std::{
+    error::Error,
+    fmt::{self, Debug, Display, Formatter},
+};
+
+/// 解析错误的种类
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind<T> {
+    /// 期望某个元素序列
+    Tag(Vec<T>),
+    /// 期望至少一个满足条件的元素
+    TakeWhile,
+    /// 期望其中某个元素
+    OneOf(Vec<T>),
+    /// 期望左括弧
+    OpeningBracket,
+    /// 期望右括弧
+    ClosingBracket(String),
+    /// 自定义错误
+    /// * 🎯用于自定义解析器
+    Custom(String),
+}
+
+/// 解析错误
+/// * 📌总是携带「出错时的位置」
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError<T> {
+    /// 出错时的位置
+    /// * 📌即此时的「已消耗元素数」，参见[`BufferIterator::n_consumed`]
+    pub position: usize,
+    /// 错误的种类
+    pub kind: ParseErrorKind<T>,
+}
+
+impl<T> ParseError<T> {
+    /// 构造函数
+    pub fn new(position: usize, kind: ParseErrorKind<T>) -> Self {
+        Self { position, kind }
+    }
+
+    /// 在「缓冲迭代器」的当前位置构造错误
+    pub fn at<I>(iter: &BufferIterator<T, I>, kind: ParseErrorKind<T>) -> Self
+    where
+        I: Iterator<Item = T>,
+    {
+        Self::new(iter.n_consumed(), kind)
+    }
+}
+
+impl<T: Debug> Display for ParseError<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let position = self.position;
+        match &self.kind {
+            ParseErrorKind::Tag(items) => write!(f, "在位置{position}处期望{items:?}"),
+            ParseErrorKind::TakeWhile => write!(f, "在位置{position}处期望满足条件的元素"),
+            ParseErrorKind::OneOf(items) => write!(f, "在位置{position}处期望{items:?}之一"),
+            ParseErrorKind::OpeningBracket => write!(f, "在位置{position}处期望左括弧"),
+            ParseErrorKind::ClosingBracket(right) => {
+                write!(f, "在位置{position}处期望右括弧{right:?}")
+            }
+            ParseErrorKind::Custom(message) => write!(f, "在位置{position}处：{message}"),
+        }
+    }
+}
+
+impl<T: Debug> Error for ParseError<T> {}
+
+/// 解析结果
+pub type ParseResult<O, T> = Result<O, ParseError<T>>;
+
+/// 匹配一个元素序列
+/// * 🚩基于[`BufferIterator::skip_when_starts_with`]：匹配成功则跳过
+/// * 📌失败时不消耗元素
+pub fn tag<T, I>(
+    pattern: impl IntoIterator<Item = T>,
+) -> impl FnMut(&mut BufferIterator<T, I>) -> ParseResult<(), T>
+where
+    T: Clone + PartialEq,
+    I: Iterator<Item = T>,
+{
+    let pattern = pattern.into_iter().collect::<Vec<_>>();
+    move |iter| match iter.skip_when_starts_with(pattern.iter().cloned()) {
+        true => Ok(()),
+        false => Err(ParseError::at(iter, ParseErrorKind::Tag(pattern.clone()))),
+    }
+}
+
+/// 取出所有连续满足条件的元素
+/// * 📌可以一个都不取（返回空数组）
+pub fn take_while<T, I>(
+    predicate: impl Fn(&T) -> bool,
+) -> impl FnMut(&mut BufferIterator<T, I>) -> ParseResult<Vec<T>, T>
+where
+    I: Iterator<Item = T>,
+{
+    move |iter| {
+        let mut items = vec![];
+        while iter.buffer_get(0).is_some_and(&predicate) {
+            items.extend(iter.buffer_next());
+        }
+        Ok(items)
+    }
+}
+
+/// 取出所有连续满足条件的元素，但至少要有一个
+/// * 📄参见[`take_while`]
+pub fn take_while1<T, I>(
+    predicate: impl Fn(&T) -> bool,
+) -> impl FnMut(&mut BufferIterator<T, I>) -> ParseResult<Vec<T>, T>
+where
+    I: Iterator<Item = T>,
+{
+    let mut take = take_while(predicate);
+    move |iter| match take(iter)? {
+        items if items.is_empty() => Err(ParseError::at(iter, ParseErrorKind::TakeWhile)),
+        items => Ok(items),
+    }
+}
+
+/// 取出一个元素，要求其为给定元素之一
+/// * 📌失败时不消耗元素
+pub fn one_of<T, I>(
+    items: impl IntoIterator<Item = T>,
+) -> impl FnMut(&mut BufferIterator<T, I>) -> ParseResult<T, T>
+where
+    T: Clone + PartialEq,
+    I: Iterator<Item = T>,
+{
+    let items = items.into_iter().collect::<Vec<_>>();
+    move |iter| match iter.buffer_get(0) {
+        Some(item) if items.contains(item) => Ok(iter.buffer_next().unwrap()),
+        _ => Err(ParseError::at(iter, ParseErrorKind::OneOf(items.clone()))),
+    }
+}
+
+/// 转换解析器的输出
+pub fn map<T, I, O1, O2>(
+    mut parser: impl FnMut(&mut BufferIterator<T, I>) -> ParseResult<O1, T>,
+    mut f: impl FnMut(O1) -> O2,
+) -> impl FnMut(&mut BufferIterator<T, I>) -> ParseResult<O2, T>
+where
+    I: Iterator<Item = T>,
+{
+    move |iter| parser(iter).map(&mut f)
+}
+
+/// 可选的解析器
+/// * 🚩失败⇒回滚，并返回[`None`]
+pub fn opt<T, I, O>(
+    mut parser: impl FnMut(&mut BufferIterator<T, I>) -> ParseResult<O, T>,
+) -> impl FnMut(&mut BufferIterator<T, I>) -> ParseResult<Option<O>, T>
+where
+    T: Clone,
+    I: Iterator<Item = T>,
+{
+    move |iter| Ok(iter.attempt(&mut parser).ok())
+}
+
+/// 「依次解析」的解析器组
+/// * 🎯由元组实现，作为[`sequence`]的参数
+pub trait Sequence<T, I: Iterator<Item = T>> {
+    /// 各个解析器输出组成的元组
+    type Output;
+    /// 依次解析，返回所有输出
+    /// * 📌遇到失败即返回
+    fn parse_sequence(&mut self, iter: &mut BufferIterator<T, I>) -> ParseResult<Self::Output, T>;
+}
+
+/// 「多选一」的解析器组
+/// * 🎯由元组实现，作为[`alt`]的参数
+pub trait Alternatives<T, I: Iterator<Item = T>> {
+    /// 各个解析器共同的输出
+    type Output;
+    /// 依次尝试，返回首个成功的输出
+    /// * 📌每个失败的尝试都会回滚
+    /// * 📌都失败⇒返回「走得最远」的错误
+    fn parse_alt(&mut self, iter: &mut BufferIterator<T, I>) -> ParseResult<Self::Output, T>;
+}
+
+/// 对元组批量实现[`Sequence`]与[`Alternatives`]
+macro_rules! impl_tuple_parsers {
+    ( $( ( $( $parser:ident $output:ident $index:tt ),+ ) )* ) => {
+        $(
+            impl<T, I, $($parser, $output),+> Sequence<T, I> for ($($parser,)+)
+            where
+                I: Iterator<Item = T>,
+                $( $parser: FnMut(&mut BufferIterator<T, I>) -> ParseResult<$output, T>, )+
+            {
+                type Output = ($($output,)+);
+                fn parse_sequence(
+                    &mut self,
+                    iter: &mut BufferIterator<T, I>,
+                ) -> ParseResult<Self::Output, T> {
+                    Ok(( $( (self.$index)(iter)?, )+ ))
+                }
+            }
+
+            impl<T, I, O, $($parser),+> Alternatives<T, I> for ($($parser,)+)
+            where
+                T: Clone,
+                I: Iterator<Item = T>,
+                $( $parser: FnMut(&mut BufferIterator<T, I>) -> ParseResult<O, T>, )+
+            {
+                type Output = O;
+                fn parse_alt(&mut self, iter: &mut BufferIterator<T, I>) -> ParseResult<O, T> {
+                    let mut furthest: Option<ParseError<T>> = None;
+                    $(
+                        match iter.attempt(&mut self.$index) {
+                            Ok(output) => return Ok(output),
+                            // 保留「走得最远」的错误 | 一样远的，保留先出现的
+                            Err(e) => match &furthest {
+                                Some(f) if f.position >= e.position => {}
+                                _ => furthest = Some(e),
+                            },
+                        }
+                    )+
+                    // * 📌至少有一个解析器，因此必定有错误
+                    Err(furthest.unwrap())
+                }
+            }
+        )*
+    };
+}
+
+impl_tuple_parsers! {
+    (P0 O0 0)
+    (P0 O0 0, P1 O1 1)
+    (P0 O0 0, P1 O1 1, P2 O2 2)
+    (P0 O0 0, P1 O1 1, P2 O2 2, P3 O3 3)
+    (P0 O0 0, P1 O1 1, P2 O2 2, P3 O3 3, P4 O4 4)
+    (P0 O0 0, P1 O1 1, P2 O2 2, P3 O3 3, P4 O4 4, P5 O5 5)
+    (P0 O0 0, P1 O1 1, P2 O2 2, P3 O3 3, P4 O4 4, P5 O5 5, P6 O6 6)
+    (P0 O0 0, P1 O1 1, P2 O2 2, P3 O3 3, P4 O4 4, P5 O5 5, P6 O6 6, P7 O7 7)
+}
+
+/// 依次解析，返回所有输出组成的元组
+/// * 📌参数为解析器元组：`sequence((p1, p2, p3))`
+/// * ⚠️失败时不回滚：若需回滚，可再包上[`opt`]或[`alt`]
+pub fn sequence<T, I, S>(
+    mut parsers: S,
+) -> impl FnMut(&mut BufferIterator<T, I>) -> ParseResult<S::Output, T>
+where
+    I: Iterator<Item = T>,
+    S: Sequence<T, I>,
+{
+    move |iter| parsers.parse_sequence(iter)
+}
+
+/// 依次尝试，返回首个成功的输出
+/// * 📌参数为解析器元组：`alt((p1, p2, p3))`
+/// * 📌每个失败的尝试都会回滚
+/// * 📌都失败⇒返回「走得最远」的错误
+pub fn alt<T, I, A>(
+    mut alternatives: A,
+) -> impl FnMut(&mut BufferIterator<T, I>) -> ParseResult<A::Output, T>
+where
+    I: Iterator<Item = T>,
+    A: Alternatives<T, I>,
+{
+    move |iter| alternatives.parse_alt(iter)
+}
+
+/// 重复解析，直到失败
+/// * 📌可以一个都没有（返回空数组）
+/// * 📌最后一次失败的尝试会回滚
+/// * 📌解析器成功但未消耗元素⇒停止，避免死循环
+pub fn many<T, I, O>(
+    mut parser: impl FnMut(&mut BufferIterator<T, I>) -> ParseResult<O, T>,
+) -> impl FnMut(&mut BufferIterator<T, I>) -> ParseResult<Vec<O>, T>
+where
+    T: Clone,
+    I: Iterator<Item = T>,
+{
+    move |iter| {
+        let mut outputs = vec![];
+        loop {
+            let position = iter.n_consumed();
+            match iter.attempt(&mut parser) {
+                Ok(output) => outputs.push(output),
+                Err(..) => break,
+            }
+            // 没有进展⇒停止
+            if iter.n_consumed() == position {
+                break;
+            }
+        }
+        Ok(outputs)
+    }
+}
+
+/// 解析「以分隔符分隔的元素」
+/// * 📌格式：`元素 (分隔符 元素)*`，可以一个元素都没有
+/// * 📌分隔符之后的元素解析失败⇒连同分隔符一并回滚
+/// * 📌「分隔符+元素」成功但未消耗元素⇒停止，避免死循环
+/// * 📄`A, B, C`
+pub fn separated<T, I, O, S>(
+    mut item: impl FnMut(&mut BufferIterator<T, I>) -> ParseResult<O, T>,
+    mut separator: impl FnMut(&mut BufferIterator<T, I>) -> ParseResult<S, T>,
+) -> impl FnMut(&mut BufferIterator<T, I>) -> ParseResult<Vec<O>, T>
+where
+    T: Clone,
+    I: Iterator<Item = T>,
+{
+    move |iter| {
+        let mut outputs = vec![];
+        // 第一个元素
+        match iter.attempt(&mut item) {
+            Ok(output) => outputs.push(output),
+            Err(..) => return Ok(outputs),
+        }
+        // 其后的「分隔符+元素」
+        loop {
+            let position = iter.n_consumed();
+            match iter.attempt(|iter| {
+                separator(iter)?;
+                item(iter)
+            }) {
+                Ok(output) => outputs.push(output),
+                Err(..) => break,
+            }
+            // 没有进展⇒停止
+            if iter.n_consumed() == position {
+                break;
+            }
+        }
+        Ok(outputs)
+    }
+}
+
+/// [`delimited`]的输出
+/// * 📌格式：`((左括弧, 右括弧, 关联内容), 内容)`
+#[cfg(feature = "str_processing")]
+pub type Delimited<'d, O, T = ()> = (&'d (String, String, T), O);
+
+/// 解析「由括弧包围的内容」
+/// * 🚩先用「双向配对字典」匹配左括弧，再解析内容，最后匹配对应的右括弧
+///   * 📌括弧可以是多个字符的，如`\left(`与`\right)`
+/// * 📌返回整个括弧条目`(左括弧, 右括弧, 关联内容)`与内容的输出
+///   * 📄可借「关联内容」得知括弧所开启的结构
+/// * ⚠️字典中的「空括弧」（`("", "")`）也会被匹配
+#[cfg(feature = "str_processing")]
+pub fn delimited<'d, I, O, T>(
+    brackets: &'d crate::BiFixMatchDictPair<T>,
+    mut inner: impl FnMut(&mut BufferIterator<char, I>) -> ParseResult<O, char>,
+) -> impl FnMut(&mut BufferIterator<char, I>) -> ParseResult<Delimited<'d, O, T>, char>
+where
+    I: Iterator<Item = char>,
+{
+    move |iter| {
+        let pair = iter
+            .skip_when_match_prefix_dict(brackets)
+            .ok_or_else(|| ParseError::at(iter, ParseErrorKind::OpeningBracket))?;
+        let output = inner(iter)?;
+        match iter.skip_when_starts_with(pair.1.chars()) {
+            true => Ok((pair, output)),
+            false => Err(ParseError::at(
+                iter,
+                ParseErrorKind::ClosingBracket(pair.1.clone()),
+            )),
+        }
+    }
+}
+
+/// 将字符数组转换为字符串
+/// * 🎯便于处理[`take_while`]等组合子的输出
+pub fn chars_to_string(chars: Vec<char>) -> String {
+    String::from_iter(chars)
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asserts;
+
+    /// 便捷构造「字符缓冲迭代器」
+    fn chars(s: &str) -> BufferIterator<char, std::str::Chars<'_>> {
+        BufferIterator::new(s.chars())
+    }
+
+    #[test]
+    fn test_basic() {
+        let mut iter = chars("<A --> B>");
+        let mut word = map(take_while1(|c: &char| c.is_alphanumeric()), chars_to_string);
+        asserts! {
+            tag("<".chars())(&mut iter) => Ok(()),
+            word(&mut iter) => Ok("A".to_string()),
+            take_while(|c: &char| c.is_whitespace())(&mut iter) => Ok(vec![' ']),
+            // 失败时携带位置
+            tag("==>".chars())(&mut iter) => Err(ParseError::new(3, ParseErrorKind::Tag(vec!['=', '=', '>']))),
+            word(&mut iter) => Err(ParseError::new(3, ParseErrorKind::TakeWhile)),
+            one_of(['-', '='])(&mut iter) => Ok('-'),
+            opt(tag("=".chars()))(&mut iter) => Ok(None),
+            opt(tag("->".chars()))(&mut iter) => Ok(Some(())),
+        }
+    }
+
+    #[test]
+    fn test_sequence_and_alt() {
+        let copula = || {
+            alt((
+                map(tag("-->".chars()), |_| "inheritance"),
+                map(tag("<->".chars()), |_| "similarity"),
+                map(tag("==>".chars()), |_| "implication"),
+            ))
+        };
+        let word = || map(take_while1(|c: &char| c.is_alphanumeric()), chars_to_string);
+        let space = || take_while(|c: &char| c.is_whitespace());
+        let mut statement = sequence((
+            tag("<".chars()),
+            word(),
+            space(),
+            copula(),
+            space(),
+            word(),
+            tag(">".chars()),
+        ));
+        let (_, subject, _, copula_name, _, predicate, _) =
+            statement(&mut chars("<A <-> B>")).unwrap();
+        asserts! {
+            subject => "A",
+            copula_name => "similarity",
+            predicate => "B",
+        }
+        // 返回走得最远的错误
+        let mut iter = chars("<-x");
+        let mut p = alt((
+            sequence((tag("<".chars()), tag("-x-".chars()))),
+            sequence((tag("<-".chars()), tag("y".chars()))),
+        ));
+        asserts! {
+            p(&mut iter) => Err(ParseError::new(2, ParseErrorKind::Tag(vec!['y']))),
+            iter.n_consumed() => 0, // 已回滚
+        }
+    }
+
+    #[test]
+    fn test_many_and_separated() {
+        let word = || map(take_while1(|c: &char| c.is_alphanumeric()), chars_to_string);
+        let mut iter = chars("A, B, C, ");
+        let items = separated(word(), tag(", ".chars()))(&mut iter);
+        asserts! {
+            items => Ok(vec!["A".to_string(), "B".into(), "C".into()]),
+            // 末尾多余的分隔符已回滚
+            iter.collect::<String>() => ", ",
+        }
+        let mut iter = chars("ababx");
+        asserts! {
+            many(tag("ab".chars()))(&mut iter) => Ok(vec![(), ()]),
+            // 不消耗元素的解析器不会导致死循环
+            many(take_while(|c: &char| c.is_numeric()))(&mut iter) => Ok(vec![vec![]]),
+            iter.collect::<String>() => "x",
+        }
+        asserts! {
+            separated(word(), tag(",".chars()))(&mut chars("")) => Ok(vec![]),
+        }
+        // 分隔符与元素都不消耗元素时，不会导致死循环
+        let mut iter = chars("x");
+        asserts! {
+            separated(many(tag("a".chars())), opt(tag(",".chars())))(&mut iter) => Ok(vec![vec![], vec![]]),
+            iter.collect::<String>() => "x",
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "str_processing")]
+    fn test_delimited() {
+        use crate::{bi_fix_match_dict_pair, BiFixMatchDictPair};
+        // 括弧 ⇒ 所开启的结构
+        let brackets: BiFixMatchDictPair<&str> = bi_fix_match_dict_pair!(
+            "(" => ")" => "compound"
+            "{" => "}" => "set"
+            r"\left(" => r"\right)" => "compound"
+        );
+        let word = || map(take_while1(|c: &char| c.is_alphanumeric()), chars_to_string);
+        let mut set = delimited(&brackets, separated(word(), tag(",".chars())));
+        let ((left, right, structure), items) = set(&mut chars("{A,B}")).unwrap();
+        asserts! {
+            left => "{",
+            right => "}",
+            *structure => "set",
+            items => vec!["A".to_string(), "B".into()],
+        }
+        let ((left, _, structure), _) = set(&mut chars(r"\left(A\right)")).unwrap();
+        asserts! {
+            left => r"\left(",
+            *structure => "compound",
+            set(&mut chars("[A]")) => Err(ParseError::new(0, ParseErrorKind::OpeningBracket)),
+            set(&mut chars("{A,B)")) => Err(ParseError::new(4, ParseErrorKind::ClosingBracket("}".into()))),
+        }
+    }
+
+    #[test]
+    fn test_display() {
+        asserts! {
+            ParseError::new(3, ParseErrorKind::Tag(vec!['>'])).to_string() => "在位置3处期望['>']",
+            ParseError::<char>::new(0, ParseErrorKind::Custom("未知的系词".into())).to_string() => "在位置0处：未知的系词",
+        }
+    }
+}
@@ -4,6 +4,8 @@
 //!   * 广度优先遍历
 //!   * 可以缓存迭代结果（缓冲区）的迭代器遍历
 //!   * 迭代结果基于一个函数/闭包
+//!   * 从I/O流中增量读取字符
+//!   * 基于缓冲区迭代器的解析器组合子
 
 // 导入并公开导出所有 //
 crate::pub_mod_and_pub_use! {
@@ -11,6 +13,15 @@ crate::pub_mod_and_pub_use! {
     bfs
     // 函数式迭代器
     functional
+    // 模式查找
+    pattern_search
     // 缓冲区迭代器
     buffer
+    // 从I/O流读取字符
+    read_chars
 }
+
+// 解析器组合子
+// * ⚠️只导出模块本身，以免`tag`、`many`等通用名称污染根路径
+pub mod combinators;
+pub use combinators::{Alternatives, ParseError, ParseErrorKind, ParseResult, Sequence};
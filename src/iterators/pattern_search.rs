@@ -0,0 +1,364 @@
+//! 预编译的「模式查找」算法
+//! * 🎯在长序列中线性时间查找子序列
+//!   * 📄在大段的NARS输出中查找`ANSWER:`等标记
+//! * 📌可预先构造，并在多次查找中复用
+//!   * 📌[`KmpPattern`]：单个模式（Knuth-Morris-Pratt算法）
+//!   * 📌[`AhoCorasick`]：多个模式（Aho-Corasick自动机）
+//! * 🚩查找逻辑均以「单步转移」的形式提供，以便用于「缓冲区迭代器」等流式场景
+//!   * 📄参见[`crate::BufferIterator::find_next_pattern`]、[`crate::BufferIterator::find_any_of`]
+
+use std::{collections::HashMap, hash::Hash};
+
+/// 预编译的单模式
+/// * 🚩使用KMP算法：预先计算「失配函数」，查找时不回退被查找序列
+/// * 📌查找复杂度：O(序列长度 + 模式长度)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KmpPattern<T> {
+    /// 模式本身
+    pattern: Vec<T>,
+    /// 失配函数
+    /// * 📌`fail[i]` = `pattern[..=i]`的「最长真前后缀」长度
+    fail: Vec<usize>,
+}
+
+impl<T: PartialEq> KmpPattern<T> {
+    /// 构造函数
+    /// * 🚩收集模式，并预先计算失配函数
+    pub fn new(pattern: impl IntoIterator<Item = T>) -> Self {
+        let pattern = pattern.into_iter().collect::<Vec<_>>();
+        let mut fail = vec![0; pattern.len()];
+        let mut matched = 0;
+        for i in 1..pattern.len() {
+            while matched > 0 && pattern[i] != pattern[matched] {
+                matched = fail[matched - 1];
+            }
+            if pattern[i] == pattern[matched] {
+                matched += 1;
+            }
+            fail[i] = matched;
+        }
+        Self { pattern, fail }
+    }
+
+    /// 获取模式长度
+    pub fn len(&self) -> usize {
+        self.pattern.len()
+    }
+
+    /// 判断模式是否为空
+    pub fn is_empty(&self) -> bool {
+        self.pattern.is_empty()
+    }
+
+    /// 获取模式本身
+    pub fn as_slice(&self) -> &[T] {
+        &self.pattern
+    }
+
+    /// 单步转移
+    /// * 🚩根据「已匹配长度」与「新元素」计算新的「已匹配长度」
+    /// * 📌新的「已匹配长度」等于模式长度⇒找到模式
+    /// * ⚠️需要保证`matched`小于模式长度
+    #[inline]
+    pub fn step(&self, mut matched: usize, item: &T) -> usize {
+        while matched > 0 && *item != self.pattern[matched] {
+            matched = self.fail[matched - 1];
+        }
+        match *item == self.pattern[matched] {
+            true => matched + 1,
+            false => 0,
+        }
+    }
+
+    /// 在切片中查找模式首次出现的位置
+    /// * 📌空模式⇒`Some(0)`
+    pub fn find_in(&self, haystack: &[T]) -> Option<usize> {
+        if self.is_empty() {
+            return Some(0);
+        }
+        let mut matched = 0;
+        for (i, item) in haystack.iter().enumerate() {
+            matched = self.step(matched, item);
+            if matched == self.len() {
+                return Some(i + 1 - self.len());
+            }
+        }
+        None
+    }
+}
+
+/// Aho-Corasick自动机的节点
+#[derive(Debug, Clone)]
+struct AcNode<T> {
+    /// 转移
+    goto: HashMap<T, usize>,
+    /// 失配指针
+    fail: usize,
+    /// 节点深度（从根节点到此的路径长度）
+    depth: usize,
+    /// 「在此结束的最长模式」的索引
+    /// * 📌包含经由失配指针可达的模式
+    output: Option<usize>,
+}
+
+impl<T> AcNode<T> {
+    fn new(depth: usize) -> Self {
+        Self {
+            goto: HashMap::new(),
+            fail: 0,
+            depth,
+            output: None,
+        }
+    }
+}
+
+/// 预编译的多模式
+/// * 🚩使用Aho-Corasick自动机：一次扫描同时查找多个模式
+/// * 📌查找复杂度：O(序列长度)（不计哈希开销）
+/// * 📌每个模式附带一个「关联内容」，匹配时一并返回
+///   * 📄从「词缀匹配字典」构造时，关联内容即为词缀本身
+/// * 📌查找策略：**最早结束**的匹配优先；同时结束的，**最长**的优先
+///   * 🎯流式查找时，只需从序列中取出「必要数量」的元素
+///   * 📄模式`["abcd", "bc"]`在`"abcd"`中找到的是`"bc"`
+/// * 📌重复的模式只保留第一个
+#[derive(Debug, Clone)]
+pub struct AhoCorasick<T, P = ()> {
+    /// 所有节点 | 第一个为根节点
+    nodes: Vec<AcNode<T>>,
+    /// 各个模式的「关联内容」与长度
+    patterns: Vec<(P, usize)>,
+}
+
+/// 根节点的索引
+const AC_ROOT: usize = 0;
+
+impl<T, P> AhoCorasick<T, P>
+where
+    T: Eq + Hash + Clone,
+{
+    /// 构造函数
+    /// * 📌格式：`(模式, 关联内容)`
+    /// * 🚩先构造字典树，再广度优先计算失配指针
+    pub fn new(patterns: impl IntoIterator<Item = (impl IntoIterator<Item = T>, P)>) -> Self {
+        let mut nodes = vec![AcNode::new(0)];
+        let mut outputs = vec![];
+        // 构造字典树
+        for (pattern, associated) in patterns {
+            let mut node = AC_ROOT;
+            for item in pattern {
+                node = match nodes[node].goto.get(&item) {
+                    Some(&next) => next,
+                    None => {
+                        let next = nodes.len();
+                        nodes.push(AcNode::new(nodes[node].depth + 1));
+                        nodes[node].goto.insert(item, next);
+                        next
+                    }
+                };
+            }
+            // 重复的模式只保留第一个
+            if nodes[node].output.is_none() {
+                nodes[node].output = Some(outputs.len());
+                outputs.push((associated, nodes[node].depth));
+            }
+        }
+        // 广度优先计算失配指针
+        let mut queue = std::collections::VecDeque::from([AC_ROOT]);
+        while let Some(node) = queue.pop_front() {
+            let children = nodes[node]
+                .goto
+                .iter()
+                .map(|(item, &child)| (item.clone(), child))
+                .collect::<Vec<_>>();
+            for (item, child) in children {
+                // 根节点的子节点⇒失配指针指向根节点
+                let fail = match node == AC_ROOT {
+                    true => AC_ROOT,
+                    false => Self::goto_from(&nodes, nodes[node].fail, &item),
+                };
+                nodes[child].fail = fail;
+                // 自身不是模式结尾⇒继承「失配指针处的最长模式」
+                if nodes[child].output.is_none() {
+                    nodes[child].output = nodes[fail].output;
+                }
+                queue.push_back(child);
+            }
+        }
+        Self {
+            nodes,
+            patterns: outputs,
+        }
+    }
+
+    /// 【内部】沿失配指针查找转移
+    fn goto_from(nodes: &[AcNode<T>], mut node: usize, item: &T) -> usize {
+        loop {
+            if let Some(&next) = nodes[node].goto.get(item) {
+                return next;
+            }
+            if node == AC_ROOT {
+                return AC_ROOT;
+            }
+            node = nodes[node].fail;
+        }
+    }
+
+    /// 获取模式个数
+    pub fn len(&self) -> usize {
+        self.patterns.len()
+    }
+
+    /// 判断是否没有模式
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// 初始状态
+    /// * 📌用于流式查找
+    pub fn start(&self) -> usize {
+        AC_ROOT
+    }
+
+    /// 单步转移
+    /// * 🚩根据「当前状态」与「新元素」计算新的状态
+    #[inline]
+    pub fn step(&self, state: usize, item: &T) -> usize {
+        Self::goto_from(&self.nodes, state, item)
+    }
+
+    /// 获取「在某状态结束的最长模式」
+    /// * 📌格式：`(模式长度, 关联内容)`
+    /// * 📌没有模式在此结束⇒[`None`]
+    pub fn output(&self, state: usize) -> Option<(usize, &P)> {
+        self.nodes[state]
+            .output
+            .map(|i| (self.patterns[i].1, &self.patterns[i].0))
+    }
+
+    /// 在切片中查找首个匹配
+    /// * 📌格式：`(匹配开头的位置, 关联内容)`
+    /// * 📌查找策略参见[`AhoCorasick`]
+    pub fn find_in(&self, haystack: &[T]) -> Option<(usize, &P)> {
+        // 空模式⇒在开头匹配
+        if let Some((_, associated)) = self.output(self.start()) {
+            return Some((0, associated));
+        }
+        let mut state = self.start();
+        for (i, item) in haystack.iter().enumerate() {
+            state = self.step(state, item);
+            if let Some((len, associated)) = self.output(state) {
+                return Some((i + 1 - len, associated));
+            }
+        }
+        None
+    }
+}
+
+/// 从「词缀匹配字典」构造
+/// * 📌关联内容即为词缀本身
+#[cfg(feature = "str_processing")]
+impl From<&crate::XFixMatchDict> for AhoCorasick<char, String> {
+    fn from(dict: &crate::XFixMatchDict) -> Self {
+        Self::new(
+            dict.iter_x_fixes()
+                .map(|x_fix| (x_fix.chars(), x_fix.clone())),
+        )
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asserts;
+
+    /// 朴素查找，用于比对
+    fn naive_find(haystack: &[char], pattern: &[char]) -> Option<usize> {
+        (0..=haystack.len())
+            .find(|&i| haystack.len() - i >= pattern.len() && haystack[i..].starts_with(pattern))
+    }
+
+    #[test]
+    fn test_kmp() {
+        let haystacks = [
+            "",
+            "a",
+            "aaaaab",
+            "abababcabababd",
+            "<A --> B>. ANSWER: <A --> C>.",
+            "中文中文中文英文",
+        ];
+        let patterns = [
+            "",
+            "a",
+            "aab",
+            "ababd",
+            "abababd",
+            "ANSWER:",
+            "中文英",
+            "不存在",
+            "ab",
+        ];
+        for haystack in haystacks {
+            let haystack = haystack.chars().collect::<Vec<_>>();
+            for pattern in patterns {
+                let kmp = KmpPattern::new(pattern.chars());
+                let pattern = pattern.chars().collect::<Vec<_>>();
+                assert_eq!(
+                    kmp.find_in(&haystack),
+                    naive_find(&haystack, &pattern),
+                    "{haystack:?} / {pattern:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_aho_corasick() {
+        let ac = AhoCorasick::new([
+            ("he".chars(), "he"),
+            ("she".chars(), "she"),
+            ("his".chars(), "his"),
+            ("hers".chars(), "hers"),
+        ]);
+        let find = |s: &str| {
+            ac.find_in(&s.chars().collect::<Vec<_>>())
+                .map(|(i, p)| (i, *p))
+        };
+        asserts! {
+            ac.len() => 4,
+            // 同时结束⇒最长的优先
+            find("ushers") => Some((1, "she")),
+            find("ahishers") => Some((1, "his")),
+            find("hehe") => Some((0, "he")),
+            find("xyz") => None,
+        }
+        // 最早结束的优先
+        let ac = AhoCorasick::new([("abcd".chars(), 0), ("bc".chars(), 1)]);
+        asserts! {
+            ac.find_in(&['a', 'b', 'c', 'd']) => Some((1, &1)),
+            ac.find_in(&['a', 'b', 'd']) => None,
+        }
+        // 重复的模式只保留第一个；空模式总是在开头匹配
+        let ac = AhoCorasick::new([("a".chars(), 0), ("a".chars(), 1), ("".chars(), 2)]);
+        asserts! {
+            ac.len() => 2,
+            ac.find_in(&['b', 'a']) => Some((0, &2)),
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "str_processing")]
+    fn test_aho_corasick_from_dict() {
+        use crate::{x_fix_match_dict, PrefixMatchDict, XFixMatchDict};
+        let dict: XFixMatchDict = x_fix_match_dict!("ANSWER:" "IN:" "OUT:" "EXE:");
+        let ac = AhoCorasick::from(&dict);
+        let haystack = "IN: <A --> B>.\nANSWER: <A --> B>."
+            .chars()
+            .collect::<Vec<_>>();
+        asserts! {
+            ac.find_in(&haystack) => Some((0, &"IN:".to_string())),
+            ac.find_in(&haystack[1..]) => Some((14, &"ANSWER:".to_string())),
+        }
+    }
+}
@@ -0,0 +1,419 @@
+//! 从[`std::io::Read`]中增量解码UTF-8字符的迭代器
+//! * 🎯从子进程管道、大型`.nal`文件中**惰性**读取Narsese
+//!   * 📌`IntoChars`与[`crate::BufferIterator`]原先只能处理「已在内存中的字符串」
+//! * 📌跨越多次读取的码点也能正确拼接
+//! * 📌非法字节序列与I/O错误均作为错误值返回，而非panic
+
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    io::{self, BufRead, BufReader, Read},
+};
+
+/// 读取字符时的错误
+#[derive(Debug)]
+pub enum ReadCharsError {
+    /// 底层的I/O错误
+    /// * 📌[`io::ErrorKind::Interrupted`]会被自动重试，不会出现在此
+    Io(io::Error),
+    /// 非法的UTF-8字节序列
+    /// * 📌`bytes`为「非法序列的最大有效前缀」，参见Unicode「maximal subpart」替换策略
+    ///   * 🚩导致失配的字节不会被消耗，而是留给下一个字符
+    InvalidUtf8 {
+        /// 非法序列开头的字节位置
+        position: usize,
+        /// 非法序列本身
+        bytes: Vec<u8>,
+    },
+    /// 读取到末尾时，码点尚不完整
+    UnexpectedEof {
+        /// 不完整序列开头的字节位置
+        position: usize,
+        /// 不完整的序列
+        bytes: Vec<u8>,
+    },
+}
+
+impl Display for ReadCharsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadCharsError::Io(e) => write!(f, "读取字符时发生I/O错误：{e}"),
+            ReadCharsError::InvalidUtf8 { position, bytes } => {
+                write!(f, "位于字节{position}处的非法UTF-8序列：{bytes:02X?}")
+            }
+            ReadCharsError::UnexpectedEof { position, bytes } => {
+                write!(f, "位于字节{position}处的UTF-8序列不完整：{bytes:02X?}")
+            }
+        }
+    }
+}
+
+impl Error for ReadCharsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ReadCharsError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ReadCharsError {
+    fn from(e: io::Error) -> Self {
+        ReadCharsError::Io(e)
+    }
+}
+
+/// 从[`BufRead`]中增量解码UTF-8字符的迭代器
+/// * 🚩每次只从读取器中取出「当前字符所需的字节」
+///   * 📌码点跨越了两次读取⇒自动拼接
+/// * 📌迭代出`Result<char, ReadCharsError>`
+///   * 出错后仍可继续迭代：跳过非法序列，继续读取之后的字符
+///   * 若要直接作为「字符迭代器」使用，参见[`Self::stop_on_error`]
+///
+/// ## 用例
+///
+/// ```rust
+/// use nar_dev_utils::{BufferIterator, ReadChars};
+/// let input: &[u8] = "<A --> B>. %1.0;0.9%".as_bytes();
+/// let mut chars = ReadChars::new(input).stop_on_error();
+/// let mut iter = BufferIterator::new(&mut chars);
+/// assert!(iter.skip_when_starts_with("<A".chars()));
+/// assert_eq!(iter.collect::<String>(), " --> B>. %1.0;0.9%");
+/// assert!(chars.error().is_none());
+/// ```
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ReadChars<R: BufRead> {
+    /// 被读取的读取器
+    reader: R,
+    /// 已读取的字节数
+    /// * 🎯报告错误位置
+    position: usize,
+}
+
+impl<R: BufRead> ReadChars<R> {
+    /// 构造函数
+    /// * 📌直接使用[`BufRead`]自带的缓冲区，无需额外缓冲
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            position: 0,
+        }
+    }
+
+    /// 获取「已读取的字节数」
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// 获取内部读取器的引用
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// 解包，拿回内部的读取器
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// 转换为「遇错即停」的字符迭代器
+    /// * 🎯作为[`crate::BufferIterator`]等「字符迭代器」的来源
+    /// * 📄参见[`ReadCharsStopOnError`]
+    pub fn stop_on_error(self) -> ReadCharsStopOnError<R> {
+        ReadCharsStopOnError {
+            chars: self,
+            error: None,
+        }
+    }
+
+    /// 【内部】预览下一个字节，但不消耗
+    /// * 🚩读取到末尾⇒[`None`]
+    /// * 🚩自动重试[`io::ErrorKind::Interrupted`]
+    fn peek_byte(&mut self) -> io::Result<Option<u8>> {
+        loop {
+            match self.reader.fill_buf() {
+                Ok(buf) => return Ok(buf.first().copied()),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// 【内部】消耗一个（已预览的）字节
+    fn consume_byte(&mut self) {
+        self.reader.consume(1);
+        self.position += 1;
+    }
+
+    /// 【内部】读取下一个字符
+    /// * 🚩先读首字节，确定码点长度，再逐个读取并校验后续字节
+    fn read_char(&mut self) -> Result<Option<char>, ReadCharsError> {
+        let position = self.position;
+        // 首字节：到末尾⇒迭代结束
+        let Some(lead) = self.peek_byte()? else {
+            return Ok(None);
+        };
+        self.consume_byte();
+        // 快速路径：ASCII
+        if lead < 0x80 {
+            return Ok(Some(lead as char));
+        }
+        let len = utf8_char_len(lead);
+        let mut bytes = [lead, 0, 0, 0];
+        // 非法首字节
+        if len == 0 {
+            return Err(ReadCharsError::InvalidUtf8 {
+                position,
+                bytes: vec![lead],
+            });
+        }
+        // 后续字节
+        for i in 1..len {
+            let Some(byte) = self.peek_byte()? else {
+                return Err(ReadCharsError::UnexpectedEof {
+                    position,
+                    bytes: bytes[..i].to_vec(),
+                });
+            };
+            // 失配⇒不消耗此字节，报告之前的部分
+            if !is_valid_continuation(lead, i, byte) {
+                return Err(ReadCharsError::InvalidUtf8 {
+                    position,
+                    bytes: bytes[..i].to_vec(),
+                });
+            }
+            self.consume_byte();
+            bytes[i] = byte;
+        }
+        // * 📌此时已经确保是合法的UTF-8序列
+        let c = std::str::from_utf8(&bytes[..len])
+            .ok()
+            .and_then(|s| s.chars().next())
+            .expect("已校验的UTF-8序列");
+        Ok(Some(c))
+    }
+}
+
+/// 从任意[`Read`]构造
+/// * 🚩使用[`BufReader`]包装
+impl<R: Read> ReadChars<BufReader<R>> {
+    /// 从任意[`Read`]构造
+    /// * 🚩使用[`BufReader`]包装，以便增量读取
+    pub fn from_read(reader: R) -> Self {
+        Self::new(BufReader::new(reader))
+    }
+}
+
+impl<R: BufRead> Iterator for ReadChars<R> {
+    type Item = Result<char, ReadCharsError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_char().transpose()
+    }
+}
+
+/// 根据UTF-8首字节确定码点长度
+/// * 🚩非法首字节（含后续字节、过长编码的首字节）⇒`0`
+fn utf8_char_len(lead: u8) -> usize {
+    match lead {
+        0x00..=0x7F => 1,
+        0xC2..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF4 => 4,
+        _ => 0,
+    }
+}
+
+/// 判断「第`i`个后续字节」是否合法
+/// * 📌第二个字节的取值范围取决于首字节：排除过长编码、代理对与超出范围的码点
+fn is_valid_continuation(lead: u8, i: usize, byte: u8) -> bool {
+    match (lead, i) {
+        (0xE0, 1) => matches!(byte, 0xA0..=0xBF),
+        (0xED, 1) => matches!(byte, 0x80..=0x9F),
+        (0xF0, 1) => matches!(byte, 0x90..=0xBF),
+        (0xF4, 1) => matches!(byte, 0x80..=0x8F),
+        _ => matches!(byte, 0x80..=0xBF),
+    }
+}
+
+/// 「遇错即停」的字符迭代器
+/// * 🎯将[`ReadChars`]作为普通的「字符迭代器」使用
+/// * 🚩遇到错误时停止迭代，并将错误存储起来
+///   * 📌可在迭代结束后通过[`Self::error`]检查「是正常结束，还是出错了」
+/// * 💡可用`&mut`传入[`crate::BufferIterator`]，以便在之后检查错误
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ReadCharsStopOnError<R: BufRead> {
+    /// 内部的字符迭代器
+    chars: ReadChars<R>,
+    /// 遇到的错误
+    error: Option<ReadCharsError>,
+}
+
+impl<R: BufRead> ReadCharsStopOnError<R> {
+    /// 获取遇到的错误
+    /// * 📌尚未出错⇒[`None`]
+    pub fn error(&self) -> Option<&ReadCharsError> {
+        self.error.as_ref()
+    }
+
+    /// 取出遇到的错误
+    /// * 🚩取出后，迭代器可继续迭代
+    pub fn take_error(&mut self) -> Option<ReadCharsError> {
+        self.error.take()
+    }
+
+    /// 获取内部的字符迭代器
+    pub fn get_ref(&self) -> &ReadChars<R> {
+        &self.chars
+    }
+
+    /// 解包，拿回内部的字符迭代器
+    pub fn into_inner(self) -> ReadChars<R> {
+        self.chars
+    }
+}
+
+impl<R: BufRead> Iterator for ReadCharsStopOnError<R> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // 已出错⇒不再迭代
+        if self.error.is_some() {
+            return None;
+        }
+        match self.chars.next()? {
+            Ok(c) => Some(c),
+            Err(e) => {
+                self.error = Some(e);
+                None
+            }
+        }
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{asserts, BufferIterator};
+
+    /// 每次只读取一个字节的读取器
+    /// * 🎯模拟「码点跨越多次读取」的情况
+    struct OneByteReader<'a>(&'a [u8]);
+
+    impl Read for OneByteReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            match (self.0.split_first(), buf.first_mut()) {
+                (Some((&byte, rest)), Some(target)) => {
+                    *target = byte;
+                    self.0 = rest;
+                    Ok(1)
+                }
+                _ => Ok(0),
+            }
+        }
+    }
+
+    /// 总是出错的读取器
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "管道已断开"))
+        }
+    }
+
+    /// 收集所有结果，错误转换为字节序列以便比对
+    fn collect(
+        chars: impl Iterator<Item = Result<char, ReadCharsError>>,
+    ) -> Vec<Result<char, Vec<u8>>> {
+        chars
+            .map(|r| {
+                r.map_err(|e| match e {
+                    ReadCharsError::InvalidUtf8 { bytes, .. }
+                    | ReadCharsError::UnexpectedEof { bytes, .. } => bytes,
+                    ReadCharsError::Io(e) => panic!("意外的I/O错误：{e}"),
+                })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_valid() {
+        let s = "<A --> B>. 「A是B」。🤔\r\n";
+        // 一次性读取
+        let chars = ReadChars::new(s.as_bytes());
+        asserts! {
+            chars.map(Result::unwrap).collect::<String>() => s
+        }
+        // 码点跨越多次读取
+        let chars = ReadChars::from_read(OneByteReader(s.as_bytes()));
+        asserts! {
+            chars.map(Result::unwrap).collect::<String>() => s
+        }
+        // 以极小的缓冲区读取
+        let reader = BufReader::with_capacity(1, s.as_bytes());
+        asserts! {
+            ReadChars::new(reader).map(Result::unwrap).collect::<String>() => s
+        }
+    }
+
+    #[test]
+    fn test_invalid() {
+        asserts! {
+            // 非法首字节
+            collect(ReadChars::new(&b"a\x80b"[..])) => vec![Ok('a'), Err(vec![0x80]), Ok('b')]
+            // 失配的后续字节不会被吞掉
+            collect(ReadChars::new(&b"\xE4\xBDa"[..])) => vec![Err(vec![0xE4, 0xBD]), Ok('a')]
+            // 过长编码
+            collect(ReadChars::new(&b"\xC0\xAF"[..])) => vec![Err(vec![0xC0]), Err(vec![0xAF])]
+            collect(ReadChars::new(&b"\xE0\x80\x80"[..])) => vec![Err(vec![0xE0]), Err(vec![0x80]), Err(vec![0x80])]
+            // 代理对
+            collect(ReadChars::new(&b"\xED\xA0\x80"[..])) => vec![Err(vec![0xED]), Err(vec![0xA0]), Err(vec![0x80])]
+            // 超出Unicode范围
+            collect(ReadChars::new(&b"\xF4\x90\x80\x80"[..])).len() => 4
+            // 末尾不完整
+            collect(ReadChars::new(&b"a\xF0\x9F\xA4"[..])) => vec![Ok('a'), Err(vec![0xF0, 0x9F, 0xA4])]
+        }
+        // 错误位置
+        let mut chars =
+            ReadChars::from_read(OneByteReader("我\x7F".as_bytes()).chain(&b"\xFF"[..]));
+        asserts! {
+            chars.next().unwrap().unwrap() => '我',
+            chars.next().unwrap().unwrap() => '\x7F',
+            matches!(chars.next(), Some(Err(ReadCharsError::InvalidUtf8 { position: 4, .. }))),
+            chars.next().is_none(),
+        }
+    }
+
+    #[test]
+    fn test_io_error() {
+        let mut chars = ReadChars::from_read(FailingReader);
+        let error = chars.next().unwrap().unwrap_err();
+        asserts! {
+            matches!(&error, ReadCharsError::Io(e) if e.kind() == io::ErrorKind::BrokenPipe),
+            error.source().is_some(),
+        }
+    }
+
+    #[test]
+    fn test_stop_on_error() {
+        let mut chars = ReadChars::new(&b"<A --> B>\xFF."[..]).stop_on_error();
+        let mut iter = BufferIterator::new(&mut chars);
+        asserts! {
+            iter.skip_when_starts_with("<A".chars()),
+            iter.collect::<String>() => " --> B>",
+        }
+        // 出错后停止，可取出错误
+        asserts! {
+            matches!(chars.error(), Some(ReadCharsError::InvalidUtf8 { position: 9, .. })),
+            chars.next() => None,
+            chars.take_error().is_some(),
+            // 取出错误后继续迭代
+            chars.next() => Some('.'),
+            chars.next() => None,
+            chars.error().is_none(),
+        }
+    }
+}
@@ -2356,7 +2356,8 @@ macro_rules! list {
 ///     // 逐一验证多分派的值 | ⚠️不对空元组直接判等
 ///     tuple.0 == 3
 ///     tuple.2 == 4
-///     tuple.3 == "<{ SELF } --> [good]>."
+///     // * 📝`stringify!`的空白排版随编译器版本变化，此处忽略空白比对
+///     tuple.3.split_whitespace().collect::<String>() == "<{SELF}-->[good]>."
 ///     tuple.4 == "123"
 /// }
 /// ```
@@ -48,7 +48,6 @@ impl<T> OptionBoost<T> for Option<T> {
     }
 
     #[inline]
-    #[must_use]
     fn map_unwrap_or<U>(self, f: impl FnOnce(T) -> U, default: U) -> U {
         // self.map(f).unwrap_or(else_value)
         match self {
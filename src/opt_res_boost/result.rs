@@ -97,7 +97,6 @@ impl<T, E> ResultBoost<T, E> for Result<T, E> {
     }
 
     #[inline]
-    #[must_use]
     fn ok_or_run(self, f: impl FnOnce(E)) -> Option<T> {
         match self {
             Ok(v) => Some(v),
@@ -110,7 +110,7 @@ pub(crate) mod tests {
         // 遍历查找
         for target in boarder_range {
             // 默认结果「是否有」
-            let found = arr.iter().any(|item| *item == target);
+            let found = arr.contains(&target);
             // 算法结果
             let res = search(arr, &target);
             // 判断结果是否一致
@@ -0,0 +1,575 @@
+//! 源码位置与「插入符」式诊断信息
+//! * 🎯让Narsese、NAL脚本等解析器清楚地向用户指出「语法错误在哪里」
+//! * 🚩以[`SourceFile`]预先索引各行的起始位置，再由[`Diagnostic`]渲染带`^^^`下划线的出错行
+//! * 📌显示宽度：制表符按制表位展开，CJK等宽字符占两列，组合字符不占列
+//!
+//! ## 示例
+//!
+//! ```rust
+//! use nar_dev_utils::{Diagnostic, SourceFile};
+//! let source = SourceFile::new("<A -=> B>.").with_name("input.nal");
+//! let diagnostic = Diagnostic::error("未知的系词")
+//!     .with_label(3..6, "此处")
+//!     .with_note("您是不是想输入「==>」？");
+//! assert_eq!(
+//!     diagnostic.render(&source),
+//!     "\
+//! error: 未知的系词
+//!  --> input.nal:1:4
+//!   |
+//! 1 | <A -=> B>.
+//!   |    ^^^ 此处
+//!   |
+//!   = note: 您是不是想输入「==>」？
+//! "
+//! );
+//! ```
+
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::Range,
+};
+
+/// 制表位宽度
+pub const TAB_WIDTH: usize = 4;
+
+/// 计算单个字符的显示宽度
+/// * 📌CJK统一表意文字、全角符号、谚文、常见emoji等宽字符⇒2
+/// * 📌组合附加符号、零宽字符、控制字符⇒0
+/// * ⚠️制表符的宽度取决于所在列，此处按0计算，需另行处理
+/// * 🚩仅覆盖常见区段，不引入完整的Unicode宽度表
+pub fn char_display_width(c: char) -> usize {
+    match c as u32 {
+        // 控制字符
+        0..=0x1f | 0x7f..=0x9f => 0,
+        // 组合附加符号、零宽字符
+        0x300..=0x36f | 0x200b..=0x200f | 0xfe00..=0xfe0f => 0,
+        // 宽字符
+        0x1100..=0x115f
+        | 0x2e80..=0x303e
+        | 0x3041..=0x33ff
+        | 0x3400..=0x4dbf
+        | 0x4e00..=0x9fff
+        | 0xa000..=0xa4cf
+        | 0xac00..=0xd7a3
+        | 0xf900..=0xfaff
+        | 0xfe30..=0xfe4f
+        | 0xff00..=0xff60
+        | 0xffe0..=0xffe6
+        | 0x1f300..=0x1f64f
+        | 0x1f900..=0x1f9ff
+        | 0x20000..=0x3fffd => 2,
+        _ => 1,
+    }
+}
+
+/// 计算字串从第0列开始显示时的宽度
+/// * 📌制表符展开到下一个制表位
+pub fn str_display_width(s: &str) -> usize {
+    s.chars().fold(0, advance_column)
+}
+
+/// 计算显示字符`c`后所在的列
+fn advance_column(column: usize, c: char) -> usize {
+    match c {
+        '\t' => (column / TAB_WIDTH + 1) * TAB_WIDTH,
+        _ => column + char_display_width(c),
+    }
+}
+
+/// 源码中的位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Position {
+    /// 字节偏移
+    pub offset: usize,
+    /// 行号（从1开始）
+    pub line: usize,
+    /// 列号（从1开始，按字符计）
+    pub column: usize,
+}
+
+/// 显示为`行:列`
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// 源码中的一段范围
+/// * 📌左闭右开：`end`为范围之后的位置
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Span {
+    /// 起始位置
+    pub start: Position,
+    /// 结束位置（不含）
+    pub end: Position,
+}
+
+impl Span {
+    /// 获取字节范围
+    pub fn range(&self) -> Range<usize> {
+        self.start.offset..self.end.offset
+    }
+
+    /// 是否为空范围
+    pub fn is_empty(&self) -> bool {
+        self.start.offset == self.end.offset
+    }
+}
+
+impl From<Span> for Range<usize> {
+    fn from(span: Span) -> Self {
+        span.range()
+    }
+}
+
+/// 显示为`起始行:起始列-结束行:结束列`
+impl Display for Span {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+/// 带行索引的源码
+/// * 🎯一次索引、多次查询：二分查找行号，适合为同一源码生成大量位置与诊断
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceFile<'a> {
+    /// 源码名称（如文件路径）
+    name: Option<&'a str>,
+    /// 源码文本
+    text: &'a str,
+    /// 各行起始的字节偏移
+    line_starts: Vec<usize>,
+}
+
+impl<'a> SourceFile<'a> {
+    /// 索引源码
+    pub fn new(text: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            name: None,
+            text,
+            line_starts,
+        }
+    }
+
+    /// 设置源码名称
+    pub fn with_name(mut self, name: &'a str) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// 获取源码名称
+    pub fn name(&self) -> Option<&'a str> {
+        self.name
+    }
+
+    /// 获取源码文本
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// 行数
+    /// * 📌以换行符结尾时，末尾的空行也计入
+    pub fn n_lines(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// 获取指定行（从1开始）的内容，不含行尾的`\n`、`\r\n`
+    pub fn line(&self, line: usize) -> Option<&'a str> {
+        let start = *self.line_starts.get(line.checked_sub(1)?)?;
+        let end = self
+            .line_starts
+            .get(line)
+            .map_or(self.text.len(), |&next| next - 1);
+        let content = &self.text[start..end];
+        Some(content.strip_suffix('\r').unwrap_or(content))
+    }
+
+    /// 字节偏移⇒位置
+    /// * ⚠️偏移超出文本长度、或不在字符边界上时panic
+    pub fn position(&self, offset: usize) -> Position {
+        assert!(
+            self.text.is_char_boundary(offset),
+            "偏移{offset}不在字符边界上"
+        );
+        // * 📌`partition_point`得到「起始不超过偏移」的行数，即从1开始的行号
+        let line = self.line_starts.partition_point(|&start| start <= offset);
+        let line_start = self.line_starts[line - 1];
+        let column = self.text[line_start..offset].chars().count() + 1;
+        Position {
+            offset,
+            line,
+            column,
+        }
+    }
+
+    /// 字节范围⇒范围
+    pub fn span(&self, range: Range<usize>) -> Span {
+        Span {
+            start: self.position(range.start),
+            end: self.position(range.end),
+        }
+    }
+
+    /// 字符序号⇒字节偏移
+    /// * 🎯对接按字符计数的位置，如[`BufferIterator::n_consumed`](crate::BufferIterator::n_consumed)、
+    ///   [`UnescapeError::position`](crate::UnescapeError::position)
+    /// * 📌序号等于字符总数时，返回文本长度
+    /// * ⚠️序号超出字符总数时panic
+    pub fn char_offset(&self, char_index: usize) -> usize {
+        self.text
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(self.text.len()))
+            .nth(char_index)
+            .unwrap_or_else(|| panic!("字符序号{char_index}超出范围"))
+    }
+
+    /// 字符序号范围⇒范围
+    pub fn char_span(&self, chars: Range<usize>) -> Span {
+        self.span(self.char_offset(chars.start)..self.char_offset(chars.end))
+    }
+}
+
+/// 诊断的严重程度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// 错误
+    Error,
+    /// 警告
+    Warning,
+    /// 提示
+    Note,
+    /// 帮助
+    Help,
+}
+
+/// 显示为诊断开头的关键字
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Note => "note",
+            Self::Help => "help",
+        })
+    }
+}
+
+/// 诊断中的标注
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    /// 字节范围
+    pub range: Range<usize>,
+    /// 标注信息（可为空）
+    pub message: String,
+    /// 是否为主要标注
+    /// * 📌主要标注以`^`下划线，次要标注以`-`下划线
+    /// * 📌诊断开头的位置取第一个主要标注
+    pub primary: bool,
+}
+
+/// 诊断信息
+/// * 🚩构造后以`with_*`方法追加标注与附注，最后用[`Self::render`]对照源码渲染
+/// * 📄格式参照rustc：
+///   * 首行为`严重程度: 信息`，随后为`--> 名称:行:列`
+///   * 列出每个标注涉及的行，下方以`^^^`/`---`标出范围，并附标注信息
+///   * 不相邻的行之间以`...`省略
+///   * 末尾为`= note: 附注`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// 严重程度
+    pub severity: Severity,
+    /// 信息
+    pub message: String,
+    /// 标注
+    pub labels: Vec<Label>,
+    /// 附注
+    pub notes: Vec<String>,
+}
+
+impl Diagnostic {
+    /// 构造诊断
+    pub fn new(severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            severity,
+            message: message.into(),
+            labels: vec![],
+            notes: vec![],
+        }
+    }
+
+    /// 构造错误诊断
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(Severity::Error, message)
+    }
+
+    /// 构造警告诊断
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(Severity::Warning, message)
+    }
+
+    /// 追加主要标注
+    /// * 📌范围可为字节范围或[`Span`]
+    pub fn with_label(
+        mut self,
+        range: impl Into<Range<usize>>,
+        message: impl Into<String>,
+    ) -> Self {
+        self.labels.push(Label {
+            range: range.into(),
+            message: message.into(),
+            primary: true,
+        });
+        self
+    }
+
+    /// 追加次要标注
+    pub fn with_secondary_label(
+        mut self,
+        range: impl Into<Range<usize>>,
+        message: impl Into<String>,
+    ) -> Self {
+        self.labels.push(Label {
+            range: range.into(),
+            message: message.into(),
+            primary: false,
+        });
+        self
+    }
+
+    /// 追加附注
+    pub fn with_note(mut self, note: impl Into<String>) -> Self {
+        self.notes.push(note.into());
+        self
+    }
+
+    /// 对照源码渲染，写入任意[`fmt::Write`]目标
+    /// * ⚠️标注范围超出源码、或不在字符边界上时panic
+    pub fn render_to(&self, out: &mut impl fmt::Write, source: &SourceFile) -> fmt::Result {
+        // 标注的起止位置
+        let spans = self
+            .labels
+            .iter()
+            .map(|label| source.span(label.range.clone()))
+            .collect::<Vec<_>>();
+        // 涉及的行
+        let mut lines = spans
+            .iter()
+            .flat_map(|span| span.start.line..=span.end.line)
+            .collect::<Vec<_>>();
+        lines.sort_unstable();
+        lines.dedup();
+        let gutter = lines.last().map_or(0, |line| line.to_string().len());
+        let blank = "";
+
+        // 首行
+        writeln!(out, "{}: {}", self.severity, self.message)?;
+        // 位置
+        let main = self
+            .labels
+            .iter()
+            .position(|label| label.primary)
+            .or((!spans.is_empty()).then_some(0));
+        if let Some(main) = main {
+            write!(out, "{blank:gutter$}--> ")?;
+            if let Some(name) = source.name() {
+                write!(out, "{name}:")?;
+            }
+            writeln!(out, "{}", spans[main].start)?;
+        }
+        // 各行及其标注
+        if !lines.is_empty() {
+            writeln!(out, "{blank:gutter$} |")?;
+        }
+        let mut previous_line = None;
+        for &line in &lines {
+            if previous_line.is_some_and(|previous| line > previous + 1) {
+                writeln!(out, "...")?;
+            }
+            previous_line = Some(line);
+            let content = source.line(line).unwrap_or_default();
+            write!(out, "{line:>gutter$} |")?;
+            if !content.is_empty() {
+                out.write_char(' ')?;
+                write_expanded(out, content)?;
+            }
+            out.write_char('\n')?;
+            // 该行上的标注：按起始列排列
+            let line_start = source.line_starts[line - 1];
+            let line_end = line_start + content.len();
+            let mut on_line = self
+                .labels
+                .iter()
+                .zip(&spans)
+                .filter(|(_, span)| span.start.line <= line && line <= span.end.line)
+                // * 📌跨行标注：结束于下一行开头（未覆盖任何字符）时，不在该行标出
+                .filter(|(_, span)| !(line > span.start.line && span.end.offset == line_start))
+                .collect::<Vec<_>>();
+            on_line.sort_by_key(|(_, span)| span.start.offset.max(line_start));
+            for (label, span) in on_line {
+                let start = span.start.offset.max(line_start);
+                let end = span.end.offset.min(line_end).max(start);
+                let start_column = str_display_width(&content[..start - line_start]);
+                let end_column = str_display_width(&content[..end - line_start]);
+                let marker = if label.primary { "^" } else { "-" };
+                let n_markers = (end_column - start_column).max(1);
+                write!(
+                    out,
+                    "{blank:gutter$} | {blank:start_column$}{}",
+                    marker.repeat(n_markers)
+                )?;
+                // * 📌标注信息只写在标注的最后一行
+                let is_last_line = line == span.end.line
+                    || (line + 1 == span.end.line && span.end.offset == source.line_starts[line]);
+                if is_last_line && !label.message.is_empty() {
+                    write!(out, " {}", label.message)?;
+                }
+                out.write_char('\n')?;
+            }
+        }
+        // 附注
+        if !self.notes.is_empty() {
+            if !lines.is_empty() {
+                writeln!(out, "{blank:gutter$} |")?;
+            }
+            for note in &self.notes {
+                writeln!(out, "{blank:gutter$} = note: {note}")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 对照源码渲染到新字串
+    pub fn render(&self, source: &SourceFile) -> String {
+        let mut out = String::new();
+        self.render_to(&mut out, source)
+            .expect("向字串写入不会失败");
+        out
+    }
+}
+
+/// 输出一行源码，并将制表符展开为空格
+/// * 🎯保证下划线与源码对齐
+fn write_expanded(out: &mut impl fmt::Write, line: &str) -> fmt::Result {
+    let mut column = 0;
+    for c in line.chars() {
+        let next_column = advance_column(column, c);
+        match c {
+            '\t' => {
+                for _ in column..next_column {
+                    out.write_char(' ')?;
+                }
+            }
+            _ => out.write_char(c)?,
+        }
+        column = next_column;
+    }
+    Ok(())
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asserts;
+
+    #[test]
+    fn test_position() {
+        let source = SourceFile::new("ab\r\n词项\n\nlast");
+        asserts! {
+            source.n_lines() => 4,
+            source.line(1) => Some("ab"),
+            source.line(2) => Some("词项"),
+            source.line(3) => Some(""),
+            source.line(4) => Some("last"),
+            source.line(0) => None,
+            source.line(5) => None,
+            source.position(0) => Position { offset: 0, line: 1, column: 1 },
+            source.position(2) => Position { offset: 2, line: 1, column: 3 },
+            source.position(7) => Position { offset: 7, line: 2, column: 2 }, // 「项」
+            source.position(source.text().len()) => Position { offset: 16, line: 4, column: 5 },
+            source.span(4..10).to_string() => "2:1-2:3",
+            // 字符序号
+            source.char_offset(5) => 7,
+            source.char_offset(12) => 16,
+            source.char_span(4..6).range() => 4..10,
+        }
+    }
+
+    #[test]
+    fn test_display_width() {
+        asserts! {
+            str_display_width("abc") => 3,
+            str_display_width("词项") => 4,
+            str_display_width("\t") => TAB_WIDTH,
+            str_display_width("ab\tc") => TAB_WIDTH + 1,
+            str_display_width("e\u{301}") => 1, // 组合字符
+            char_display_width('😀') => 2,
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        // 宽字符与制表符
+        let source = SourceFile::new("<词项\t--> B>.");
+        let diagnostic = Diagnostic::error("无效的系词").with_label(8..11, "");
+        asserts! {
+            diagnostic.render(&source) => "\
+error: 无效的系词
+ --> 1:5
+  |
+1 | <词项   --> B>.
+  |         ^^^
+",
+        }
+        // 多个标注、跨行、省略与附注
+        let source = SourceFile::new("(&&,\n  A,\n  B,\n  C,\n  D\n]").with_name("test.nal");
+        let diagnostic = Diagnostic::error("括弧不配对")
+            .with_secondary_label(0..1, "左括弧在此")
+            .with_label(24..25, "期望`)`")
+            .with_note("括弧须成对出现");
+        asserts! {
+            diagnostic.render(&source) => "\
+error: 括弧不配对
+ --> test.nal:6:1
+  |
+1 | (&&,
+  | - 左括弧在此
+...
+6 | ]
+  | ^ 期望`)`
+  |
+  = note: 括弧须成对出现
+",
+        }
+        let diagnostic = Diagnostic::warning("跨行").with_label(source.span(2..9), "这里");
+        asserts! {
+            diagnostic.render(&source) => "\
+warning: 跨行
+ --> test.nal:1:3
+  |
+1 | (&&,
+  |   ^^
+2 |   A,
+  | ^^^^ 这里
+",
+        }
+        // 空范围（如意外的结尾）⇒单个插入符
+        let source = SourceFile::new("<A --> B");
+        asserts! {
+            Diagnostic::error("意外的结尾").with_label(8..8, "缺少`>`").render(&source) => "\
+error: 意外的结尾
+ --> 1:9
+  |
+1 | <A --> B
+  |         ^ 缺少`>`
+",
+            // 无标注
+            Diagnostic::error("空").with_note("无").render(&source) => "error: 空\n = note: 无\n",
+        }
+    }
+}
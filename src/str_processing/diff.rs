@@ -0,0 +1,556 @@
+//! 零依赖的文本差异比较
+//! * 🎯让多行文本（如大段Narsese输出）的断言失败时，只展示有差异的片段
+//! * 📝算法：Myers《An O(ND) Difference Algorithm and Its Variations》的最短编辑脚本（线性空间版本）
+//! * 🚩先以[`diff_slices`]得出「逐元素编辑操作」，再由[`TextDiff`]按「行」或「字符」渲染
+//!
+//! ## 示例
+//!
+//! ```rust
+//! use nar_dev_utils::TextDiff;
+//! let diff = TextDiff::lines("A\nB\nC\n", "A\nX\nC\n");
+//! assert_eq!(diff.to_string(), "@@ -1,3 +1,3 @@\n A\n-B\n+X\n C\n");
+//! assert_eq!(TextDiff::chars("<A --> B>", "<A --> C>").inline(), "<A --> [-B-]{+C+}>");
+//! ```
+
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::Range,
+};
+
+/// 统一格式差异中，默认的上下文行数
+pub const DEFAULT_DIFF_CONTEXT: usize = 3;
+
+/// 单个编辑操作
+/// * 📌其中的数值为元素在原序列中的索引
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DiffOp {
+    /// 两边相同
+    Equal {
+        /// 在旧序列中的索引
+        old: usize,
+        /// 在新序列中的索引
+        new: usize,
+    },
+    /// 仅在旧序列中：删除
+    Delete {
+        /// 在旧序列中的索引
+        old: usize,
+    },
+    /// 仅在新序列中：插入
+    Insert {
+        /// 在新序列中的索引
+        new: usize,
+    },
+}
+
+impl DiffOp {
+    /// 是否为「相同」操作
+    pub fn is_equal(&self) -> bool {
+        matches!(self, Self::Equal { .. })
+    }
+}
+
+/// 计算两个序列间的最短编辑脚本
+/// * 🚩先剥离公共前后缀，再对中间部分运行（线性空间的）Myers算法
+/// * 📌删除总是排在同位置的插入之前
+/// * ⚡时间复杂度O((N+M)D)，空间复杂度O(N+M)，其中D为编辑距离
+pub fn diff_slices<T: PartialEq>(old: &[T], new: &[T]) -> Vec<DiffOp> {
+    let mut ops = Vec::with_capacity(old.len().max(new.len()));
+    diff_range(old, new, 0, 0, &mut ops);
+    // 每个改动块内，删除排在插入之前 | 稳定排序，不改变各自的相对顺序
+    for block in ops.split_mut(DiffOp::is_equal) {
+        block.sort_by_key(|op| matches!(op, DiffOp::Insert { .. }));
+    }
+    ops
+}
+
+/// 【内部】计算两个（子）序列间的编辑脚本，并追加到`ops`中
+/// * 📌`old_offset`、`new_offset`为子序列在原序列中的起始索引
+/// * 🚩分治：剥离公共前后缀 ⇒ 找出「中间蛇形」⇒ 对其两侧递归
+///   * 📌递归深度为O(log D)：每次递归，两侧的编辑距离都至多为原来的一半
+fn diff_range<T: PartialEq>(
+    old: &[T],
+    new: &[T],
+    old_offset: usize,
+    new_offset: usize,
+    ops: &mut Vec<DiffOp>,
+) {
+    // 公共前缀
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    ops.extend((0..prefix).map(|i| DiffOp::Equal {
+        old: old_offset + i,
+        new: new_offset + i,
+    }));
+    // 公共后缀
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+    let (old_offset_middle, new_offset_middle) = (old_offset + prefix, new_offset + prefix);
+    // 中间部分
+    match (old_middle.is_empty(), new_middle.is_empty()) {
+        (true, true) => {}
+        // 只剩一边⇒全部插入或删除
+        (true, false) => ops.extend((0..new_middle.len()).map(|j| DiffOp::Insert {
+            new: new_offset_middle + j,
+        })),
+        (false, true) => ops.extend((0..old_middle.len()).map(|i| DiffOp::Delete {
+            old: old_offset_middle + i,
+        })),
+        // 以「中间蛇形」为界分治
+        (false, false) => {
+            let (x, y, u, v) = middle_snake(old_middle, new_middle);
+            diff_range(
+                &old_middle[..x],
+                &new_middle[..y],
+                old_offset_middle,
+                new_offset_middle,
+                ops,
+            );
+            ops.extend((x..u).map(|i| DiffOp::Equal {
+                old: old_offset_middle + i,
+                new: new_offset_middle + y + (i - x),
+            }));
+            diff_range(
+                &old_middle[u..],
+                &new_middle[v..],
+                old_offset_middle + u,
+                new_offset_middle + v,
+                ops,
+            );
+        }
+    }
+    ops.extend((0..suffix).map(|i| DiffOp::Equal {
+        old: old_offset + old.len() - suffix + i,
+        new: new_offset + new.len() - suffix + i,
+    }));
+}
+
+/// 【内部】找出最短编辑路径上的「中间蛇形」
+/// * 🚩从起点正向、从终点反向同时搜索，两者在某条对角线上重叠时，重叠处的蛇形即为所求
+///   * 📌只保存「各对角线上最远到达的位置」：空间O(N+M)
+/// * 📌返回`(x, y, u, v)`：蛇形从`(x, y)`沿对角线延伸到`(u, v)`
+/// * ⚠️要求两边均非空，且首尾元素均不相同（已剥离公共前后缀）
+///   * 📌此时分出的两侧都严格小于原问题，递归必定终止
+fn middle_snake<T: PartialEq>(old: &[T], new: &[T]) -> (usize, usize, usize, usize) {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    let delta = n - m;
+    let odd = delta % 2 != 0;
+    let max = (n + m + 1) / 2;
+    // `forward[k + offset]`：正向搜索中，对角线k上最远到达的x
+    // `backward[k + offset]`：反向搜索中（从终点倒着看），对角线k上最远到达的x
+    let offset = max + 1;
+    let index = |k: isize| (k + offset) as usize;
+    let mut forward = vec![0isize; 2 * offset as usize + 1];
+    let mut backward = vec![0isize; 2 * offset as usize + 1];
+    for d in 0..=max {
+        // 正向
+        for k in (-d..=d).step_by(2) {
+            let mut x = match k == -d || (k != d && forward[index(k - 1)] < forward[index(k + 1)]) {
+                true => forward[index(k + 1)],
+                false => forward[index(k - 1)] + 1,
+            };
+            let mut y = x - k;
+            let (x0, y0) = (x, y);
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            forward[index(k)] = x;
+            // 与上一步的反向搜索重叠
+            let k_backward = delta - k;
+            if odd && k_backward.abs() < d && x + backward[index(k_backward)] >= n {
+                return (x0 as usize, y0 as usize, x as usize, y as usize);
+            }
+        }
+        // 反向 | 坐标均为「距终点的距离」
+        for k in (-d..=d).step_by(2) {
+            let mut x = match k == -d || (k != d && backward[index(k - 1)] < backward[index(k + 1)])
+            {
+                true => backward[index(k + 1)],
+                false => backward[index(k - 1)] + 1,
+            };
+            let mut y = x - k;
+            let (x0, y0) = (x, y);
+            while x < n && y < m && old[(n - 1 - x) as usize] == new[(m - 1 - y) as usize] {
+                x += 1;
+                y += 1;
+            }
+            backward[index(k)] = x;
+            // 与同一步的正向搜索重叠
+            let k_forward = delta - k;
+            if !odd && k_forward.abs() <= d && x + forward[index(k_forward)] >= n {
+                return (
+                    (n - x) as usize,
+                    (m - y) as usize,
+                    (n - x0) as usize,
+                    (m - y0) as usize,
+                );
+            }
+        }
+    }
+    unreachable!("最短编辑路径的长度不会超过两序列长度之和")
+}
+
+/// 文本差异
+/// * 🎯按「行」或「字符」切分两段文本，并计算其差异
+/// * 📌切分出的片段均为原文本的切片，拼接后即为原文本
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextDiff<'a> {
+    /// 旧文本的片段
+    old: Vec<&'a str>,
+    /// 新文本的片段
+    new: Vec<&'a str>,
+    /// 编辑操作
+    ops: Vec<DiffOp>,
+}
+
+impl<'a> TextDiff<'a> {
+    /// 从已切分的片段构造
+    pub fn from_tokens(old: Vec<&'a str>, new: Vec<&'a str>) -> Self {
+        let ops = diff_slices(&old, &new);
+        Self { old, new, ops }
+    }
+
+    /// 按行比较
+    /// * 📌每行保留其行尾的换行符：「末尾有无换行」的差异也会被检出
+    pub fn lines(old: &'a str, new: &'a str) -> Self {
+        Self::from_tokens(
+            old.split_inclusive('\n').collect(),
+            new.split_inclusive('\n').collect(),
+        )
+    }
+
+    /// 按字符比较
+    pub fn chars(old: &'a str, new: &'a str) -> Self {
+        /// 将字串切分为单字符切片
+        fn char_tokens(s: &str) -> Vec<&str> {
+            s.char_indices()
+                .map(|(i, c)| &s[i..i + c.len_utf8()])
+                .collect()
+        }
+        Self::from_tokens(char_tokens(old), char_tokens(new))
+    }
+
+    /// 获取所有编辑操作
+    pub fn ops(&self) -> &[DiffOp] {
+        &self.ops
+    }
+
+    /// 两边是否完全相同
+    pub fn is_equal(&self) -> bool {
+        self.ops.iter().all(DiffOp::is_equal)
+    }
+
+    /// 计算各个「差异块」在[`Self::ops`]中的范围
+    /// * 🚩每处改动前后各带`context`个相同元素；间隔不超过`2 * context`的改动合并为一块
+    pub fn hunks(&self, context: usize) -> Vec<Range<usize>> {
+        let mut hunks: Vec<Range<usize>> = vec![];
+        for (i, op) in self.ops.iter().enumerate() {
+            if op.is_equal() {
+                continue;
+            }
+            let start = i.saturating_sub(context);
+            let end = (i + 1 + context).min(self.ops.len());
+            match hunks.last_mut() {
+                Some(last) if start <= last.end => last.end = end,
+                _ => hunks.push(start..end),
+            }
+        }
+        hunks
+    }
+
+    /// 以统一格式（unified diff）写入任意[`fmt::Write`]目标
+    /// * 📄格式同`diff -u`：`@@ -旧起始,旧行数 +新起始,新行数 @@`，随后为带` `/`-`/`+`前缀的行
+    /// * 📌无末尾换行的行，后跟一行`\ No newline at end of file`
+    /// * 📌两边相同时不输出任何内容
+    pub fn write_unified(&self, out: &mut impl fmt::Write, context: usize) -> fmt::Result {
+        for hunk in self.hunks(context) {
+            // 统计块头信息
+            let (mut old_start, mut new_start) = (None, None);
+            let (mut old_len, mut new_len) = (0, 0);
+            let (mut old_before, mut new_before) = (0, 0);
+            for op in &self.ops[..hunk.start] {
+                match op {
+                    DiffOp::Equal { .. } => {
+                        (old_before, new_before) = (old_before + 1, new_before + 1)
+                    }
+                    DiffOp::Delete { .. } => old_before += 1,
+                    DiffOp::Insert { .. } => new_before += 1,
+                }
+            }
+            for op in &self.ops[hunk.clone()] {
+                match *op {
+                    DiffOp::Equal { old, new } => {
+                        old_start.get_or_insert(old);
+                        new_start.get_or_insert(new);
+                        (old_len, new_len) = (old_len + 1, new_len + 1);
+                    }
+                    DiffOp::Delete { old } => {
+                        old_start.get_or_insert(old);
+                        old_len += 1;
+                    }
+                    DiffOp::Insert { new } => {
+                        new_start.get_or_insert(new);
+                        new_len += 1;
+                    }
+                }
+            }
+            // * 📌惯例：行数为0时，起始行号指向「其前一行」
+            let old_start = old_start.map_or(old_before, |i| i + 1);
+            let new_start = new_start.map_or(new_before, |i| i + 1);
+            writeln!(out, "@@ -{old_start},{old_len} +{new_start},{new_len} @@")?;
+            // 逐行输出
+            for op in &self.ops[hunk] {
+                let (prefix, line) = match *op {
+                    DiffOp::Equal { old, .. } => (' ', self.old[old]),
+                    DiffOp::Delete { old } => ('-', self.old[old]),
+                    DiffOp::Insert { new } => ('+', self.new[new]),
+                };
+                match line.strip_suffix('\n') {
+                    Some(line) => writeln!(out, "{prefix}{line}")?,
+                    None => writeln!(out, "{prefix}{line}\n\\ No newline at end of file")?,
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 以统一格式（unified diff）输出到新字串
+    pub fn unified(&self, context: usize) -> String {
+        let mut out = String::new();
+        self.write_unified(&mut out, context)
+            .expect("向字串写入不会失败");
+        out
+    }
+
+    /// 以行内格式写入任意[`fmt::Write`]目标
+    /// * 📄格式同`git diff --word-diff=plain`：删除`[-…-]`，插入`{+…+}`
+    /// * 🎯主要用于按字符比较的结果
+    pub fn write_inline(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        let mut ops = self.ops.iter().peekable();
+        while let Some(op) = ops.next() {
+            match *op {
+                DiffOp::Equal { old, .. } => out.write_str(self.old[old])?,
+                DiffOp::Delete { old } => {
+                    out.write_str("[-")?;
+                    out.write_str(self.old[old])?;
+                    while let Some(&&DiffOp::Delete { old }) = ops.peek() {
+                        out.write_str(self.old[old])?;
+                        ops.next();
+                    }
+                    out.write_str("-]")?;
+                }
+                DiffOp::Insert { new } => {
+                    out.write_str("{+")?;
+                    out.write_str(self.new[new])?;
+                    while let Some(&&DiffOp::Insert { new }) = ops.peek() {
+                        out.write_str(self.new[new])?;
+                        ops.next();
+                    }
+                    out.write_str("+}")?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 以行内格式输出到新字串
+    pub fn inline(&self) -> String {
+        let mut out = String::new();
+        self.write_inline(&mut out).expect("向字串写入不会失败");
+        out
+    }
+}
+
+/// 以默认上下文行数输出统一格式
+impl Display for TextDiff<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.write_unified(f, DEFAULT_DIFF_CONTEXT)
+    }
+}
+
+/// 判断两段文本相等，不等时仅展示差异块
+/// * 🎯替代[`assert_eq!`]比较大段多行文本：失败时输出统一格式差异，而非两大段原文
+/// * 📌两边可为任意[`AsRef<str>`]类型
+/// * ✨支持同[`assert_eq!`]的附加信息
+///
+/// ## 用例
+///
+/// ```rust
+/// use nar_dev_utils::assert_text_eq;
+/// assert_text_eq!("A\nB\n", String::from("A\nB\n"));
+/// ```
+///
+/// ```rust,should_panic
+/// use nar_dev_utils::assert_text_eq;
+/// assert_text_eq!("A\nB\nC\n", "A\nX\nC\n", "第{}次输出", 1);
+/// // 将输出：
+/// // 文本不相等（第1次输出）："A\nB\nC\n" != "A\nX\nC\n"
+/// // --- 左
+/// // +++ 右
+/// // @@ -1,3 +1,3 @@
+/// //  A
+/// // -B
+/// // +X
+/// //  C
+/// ```
+#[macro_export]
+macro_rules! assert_text_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_text_eq!(@INNER $left, $right, "")
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        $crate::assert_text_eq!(@INNER $left, $right, "（{}）", format_args!($($arg)+))
+    };
+    (@INNER $left:expr, $right:expr, $($note:tt)+) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                let left: &str = ::core::convert::AsRef::<str>::as_ref(left);
+                let right: &str = ::core::convert::AsRef::<str>::as_ref(right);
+                if left != right {
+                    panic!(
+                        "文本不相等{}：{} != {}\n--- 左\n+++ 右\n{}",
+                        format_args!($($note)+),
+                        stringify!($left),
+                        stringify!($right),
+                        $crate::TextDiff::lines(left, right),
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asserts;
+
+    /// 从编辑操作还原两边序列，验证其正确性与最短性
+    fn check_diff(old: &str, new: &str, expected_distance: usize) {
+        let (old, new) = (old.as_bytes(), new.as_bytes());
+        let ops = diff_slices(old, new);
+        let mut old_restored = vec![];
+        let mut new_restored = vec![];
+        for op in &ops {
+            match *op {
+                DiffOp::Equal { old: i, new: j } => {
+                    assert_eq!(old[i], new[j]);
+                    old_restored.push(old[i]);
+                    new_restored.push(new[j]);
+                }
+                DiffOp::Delete { old: i } => old_restored.push(old[i]),
+                DiffOp::Insert { new: j } => new_restored.push(new[j]),
+            }
+        }
+        assert_eq!(old_restored, old);
+        assert_eq!(new_restored, new);
+        let distance = ops.iter().filter(|op| !op.is_equal()).count();
+        assert_eq!(distance, expected_distance, "{ops:?}");
+    }
+
+    #[test]
+    fn test_diff_slices() {
+        check_diff("", "", 0);
+        check_diff("abc", "abc", 0);
+        check_diff("", "abc", 3);
+        check_diff("abc", "", 3);
+        check_diff("abc", "abd", 2);
+        // Myers论文中的例子
+        check_diff("ABCABBA", "CBABAC", 5);
+        check_diff("kitten", "sitting", 5);
+        check_diff("<A --> B>", "<B --> A>", 4);
+        check_diff("abcabba", "cbabac", 5);
+        check_diff("acebdabbabed", "acbdeacbed", 6);
+        // 删除在插入之前
+        asserts! {
+            diff_slices(&['a'], &['b']) => [DiffOp::Delete { old: 0 }, DiffOp::Insert { new: 0 }],
+            diff_slices(b"xaby", b"xcdy") => [
+                DiffOp::Equal { old: 0, new: 0 },
+                DiffOp::Delete { old: 1 },
+                DiffOp::Delete { old: 2 },
+                DiffOp::Insert { new: 1 },
+                DiffOp::Insert { new: 2 },
+                DiffOp::Equal { old: 3, new: 3 },
+            ],
+        }
+    }
+
+    /// 完全不同的大段文本
+    /// * 📌线性空间：无需为每一步保存整个「最远到达」数组
+    #[test]
+    fn test_diff_large() {
+        let old = (0..2000).map(|i| format!("旧{i}\n")).collect::<String>();
+        let new = (0..2000).map(|i| format!("新{i}\n")).collect::<String>();
+        let diff = TextDiff::lines(&old, &new);
+        asserts! {
+            diff.ops().len() => 4000,
+            diff.ops().iter().take(2000).all(|op| matches!(op, DiffOp::Delete { .. })),
+        }
+        // 大段相同、少量不同
+        let new = old.replacen("旧1000\n", "改\n", 1);
+        let diff = TextDiff::lines(&old, &new);
+        asserts! {
+            diff.ops().iter().filter(|op| !op.is_equal()).count() => 2,
+            diff.hunks(3).len() => 1,
+        }
+    }
+
+    #[test]
+    fn test_unified() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+        let new = "1\n2\nthree\n4\n5\n6\n7\n8\n9\n10\nend";
+        let diff = TextDiff::lines(old, new);
+        asserts! {
+            // 两处改动相距较远⇒两个块
+            diff.unified(1) => "\
+@@ -2,3 +2,3 @@
+ 2
+-3
++three
+ 4
+@@ -10,1 +10,2 @@
+ 10
++end
+\\ No newline at end of file
+",
+            // 两处改动间隔7行：超过两倍上下文⇒分块，否则合并为一块
+            diff.hunks(3).len() => 2,
+            diff.hunks(4).len() => 1,
+            // 纯插入时，旧起始行号指向其前一行
+            TextDiff::lines("a\n", "a\nb\n").unified(0) => "@@ -1,0 +2,1 @@\n+b\n",
+            TextDiff::lines("", "a\n").unified(3) => "@@ -0,0 +1,1 @@\n+a\n",
+            // 相同⇒无输出
+            TextDiff::lines(old, old).unified(3) => "",
+            TextDiff::lines(old, old).is_equal(),
+            !diff.is_equal(),
+            // 末尾换行的差异
+            TextDiff::lines("a\n", "a").to_string() => "@@ -1,1 +1,1 @@\n-a\n+a\n\\ No newline at end of file\n",
+        }
+    }
+
+    #[test]
+    fn test_inline() {
+        asserts! {
+            TextDiff::chars("<A --> B>", "<A ==> B>").inline() => "<A [----]{+==+}> B>",
+            TextDiff::chars("词项", "词条").inline() => "词[-项-]{+条+}",
+            TextDiff::chars("same", "same").inline() => "same",
+            TextDiff::lines("a\nb\n", "a\nc\n").inline() => "a\n[-b\n-]{+c\n+}",
+        }
+    }
+
+    #[test]
+    fn test_assert_text_eq() {
+        assert_text_eq!("A\nB\n", "A\nB\n");
+        assert_text_eq!(String::from("A"), "A", "附加信息{}", 1);
+        let result = std::panic::catch_unwind(|| assert_text_eq!("A\nB\nC\n", "A\nX\nC\n"));
+        let message = *result.unwrap_err().downcast::<String>().unwrap();
+        assert!(message.ends_with("--- 左\n+++ 右\n@@ -1,3 +1,3 @@\n A\n-B\n+X\n C\n"));
+    }
+}
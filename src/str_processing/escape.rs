@@ -0,0 +1,489 @@
+//! 可配置的字符串转义/反转义
+//! * 🎯统一Narsese字符串原子、操作参数、日志行等「字面量格式」中的转义处理
+//! * 🚩以[`EscapeTable`]描述「转义字符」「转义码对照」「Unicode转义」等规则
+//! * ✨转义、反转义均以「字符迭代器适配器」的形式流式进行
+//!   * 📄可直接套在[`IntoChars`](crate::IntoChars)、[`BufferIterator`](crate::BufferIterator)等字符迭代器上
+//!
+//! ## 示例
+//!
+//! ```rust
+//! use nar_dev_utils::EscapeTable;
+//! let table = EscapeTable::rust_like();
+//! let escaped = table.escape_str("say \"hi\"\n\u{7}");
+//! assert_eq!(escaped, r#"say \"hi\"\n\u{7}"#);
+//! assert_eq!(table.unescape_str(&escaped).unwrap(), "say \"hi\"\n\u{7}");
+//! ```
+
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// Unicode转义中，码点十六进制位数的上限
+const MAX_UNICODE_DIGITS: usize = 6;
+
+/// 单个转义序列的最大长度：`\u{10ffff}`
+const MAX_ESCAPE_LEN: usize = MAX_UNICODE_DIGITS + 4;
+
+/// 转义表
+/// * 📌由「转义字符」与若干「原字符⇄转义码」对照组成
+///   * 📄如`('\n', 'n')`：转义时`\n`⇒`\\n`，反转义时`\\n`⇒`\n`
+/// * 🚩可选启用Unicode转义`\u{..}`：
+///   * 转义时：用于「对照表中没有、但需要转义」的字符（控制字符，以及可选的非ASCII字符）
+///   * 反转义时：解析1~6位十六进制码点
+/// * ⚠️启用Unicode转义后，转义码`u`固定用于Unicode转义，对照表中的同名转义码将被忽略
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscapeTable {
+    /// 转义字符
+    escape_char: char,
+    /// 原字符⇄转义码 对照
+    pairs: Vec<(char, char)>,
+    /// 是否启用Unicode转义
+    unicode: bool,
+    /// 是否将非ASCII字符也转义为Unicode转义
+    /// * ⚠️仅在启用Unicode转义时生效
+    escape_non_ascii: bool,
+}
+
+/// 默认采用类Rust的转义规则
+impl Default for EscapeTable {
+    fn default() -> Self {
+        Self::rust_like()
+    }
+}
+
+impl EscapeTable {
+    /// 构造一个空的转义表
+    /// * 📌仅会转义「转义字符」本身：转义字符永远以「连续两个转义字符」表示
+    pub fn new(escape_char: char) -> Self {
+        Self {
+            escape_char,
+            pairs: vec![(escape_char, escape_char)],
+            unicode: false,
+            escape_non_ascii: false,
+        }
+    }
+
+    /// 类Rust字符串字面量的转义规则
+    /// * 📄`\\n` `\\r` `\\t` `\\0` `\\\\` `\\"` `\\'`，以及`\u{..}`
+    pub fn rust_like() -> Self {
+        Self::new('\\')
+            .with('\n', 'n')
+            .with('\r', 'r')
+            .with('\t', 't')
+            .with('\0', '0')
+            .with('"', '"')
+            .with('\'', '\'')
+            .with_unicode(true)
+    }
+
+    /// 添加一条「原字符⇄转义码」对照
+    /// * 🚩已有相同原字符或相同转义码的对照时，替换之
+    pub fn with(mut self, raw: char, code: char) -> Self {
+        self.pairs.retain(|&(r, c)| r != raw && c != code);
+        self.pairs.push((raw, code));
+        self
+    }
+
+    /// 启用/禁用Unicode转义`\u{..}`
+    pub fn with_unicode(mut self, unicode: bool) -> Self {
+        self.unicode = unicode;
+        self
+    }
+
+    /// 是否将非ASCII字符也转义为Unicode转义
+    /// * ⚠️仅在启用Unicode转义时生效
+    pub fn with_escape_non_ascii(mut self, escape_non_ascii: bool) -> Self {
+        self.escape_non_ascii = escape_non_ascii;
+        self
+    }
+
+    /// 获取转义字符
+    pub fn escape_char(&self) -> char {
+        self.escape_char
+    }
+
+    /// 查找原字符对应的转义码
+    fn code_of(&self, raw: char) -> Option<char> {
+        self.pairs
+            .iter()
+            .find(|&&(r, c)| r == raw && !(self.unicode && c == 'u'))
+            .map(|&(_, c)| c)
+    }
+
+    /// 查找转义码对应的原字符
+    fn raw_of(&self, code: char) -> Option<char> {
+        self.pairs
+            .iter()
+            .find(|&&(_, c)| c == code)
+            .map(|&(r, _)| r)
+    }
+
+    /// 判断字符是否需要Unicode转义
+    fn needs_unicode_escape(&self, c: char) -> bool {
+        self.unicode && (c.is_control() || (self.escape_non_ascii && !c.is_ascii()))
+    }
+
+    /// 流式转义字符迭代器
+    pub fn escape<I: Iterator<Item = char>>(&self, chars: I) -> Escape<'_, I> {
+        Escape {
+            table: self,
+            chars,
+            pending: ['\0'; MAX_ESCAPE_LEN],
+            pending_start: 0,
+            pending_end: 0,
+        }
+    }
+
+    /// 流式反转义字符迭代器
+    /// * 📌产出[`Result`]：遇到格式错误的转义序列时，产出带位置的错误，随后终止
+    pub fn unescape<I: Iterator<Item = char>>(&self, chars: I) -> Unescape<'_, I> {
+        Unescape {
+            table: self,
+            chars,
+            position: 0,
+            failed: false,
+        }
+    }
+
+    /// 转义字串，并写入任意[`fmt::Write`]目标
+    pub fn escape_to(&self, out: &mut impl fmt::Write, s: &str) -> fmt::Result {
+        for c in self.escape(s.chars()) {
+            out.write_char(c)?;
+        }
+        Ok(())
+    }
+
+    /// 转义字串到新字串
+    pub fn escape_str(&self, s: &str) -> String {
+        self.escape(s.chars()).collect()
+    }
+
+    /// 反转义字串，并追加到目标字串
+    /// * ⚠️出错时，错误之前的内容已被写入
+    pub fn unescape_to(&self, out: &mut String, s: &str) -> Result<(), UnescapeError> {
+        for c in self.unescape(s.chars()) {
+            out.push(c?);
+        }
+        Ok(())
+    }
+
+    /// 反转义字串到新字串
+    pub fn unescape_str(&self, s: &str) -> Result<String, UnescapeError> {
+        self.unescape(s.chars()).collect()
+    }
+}
+
+/// 反转义错误
+/// * 📌其中的位置均为**字符**位置（而非字节位置），指向转义序列开头的转义字符
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnescapeError {
+    /// 转义字符位于末尾，没有转义码
+    TrailingEscape {
+        /// 转义字符所在的位置
+        position: usize,
+    },
+    /// 未知的转义码
+    UnknownEscape {
+        /// 遇到的转义码
+        code: char,
+        /// 转义字符所在的位置
+        position: usize,
+    },
+    /// Unicode转义格式错误
+    /// * 📄缺少花括号、含非十六进制字符、位数为0或超过6位
+    MalformedUnicode {
+        /// 转义字符所在的位置
+        position: usize,
+    },
+    /// Unicode转义的码点无效
+    /// * 📄超出范围、代理对码点
+    InvalidCodePoint {
+        /// 解析出的码点
+        value: u32,
+        /// 转义字符所在的位置
+        position: usize,
+    },
+}
+
+impl UnescapeError {
+    /// 获取出错的位置
+    pub fn position(&self) -> usize {
+        match self {
+            Self::TrailingEscape { position }
+            | Self::UnknownEscape { position, .. }
+            | Self::MalformedUnicode { position }
+            | Self::InvalidCodePoint { position, .. } => *position,
+        }
+    }
+}
+
+impl Display for UnescapeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TrailingEscape { position } => {
+                write!(f, "转义不完整：位置{position}处的转义字符位于末尾")
+            }
+            Self::UnknownEscape { code, position } => {
+                write!(f, "未知转义：位置{position}处的转义码{code:?}未定义")
+            }
+            Self::MalformedUnicode { position } => {
+                write!(
+                    f,
+                    "Unicode转义格式错误：位置{position}处应为`u{{1~6位十六进制}}`"
+                )
+            }
+            Self::InvalidCodePoint { value, position } => {
+                write!(
+                    f,
+                    "无效码点：位置{position}处的Unicode转义码点{value:#x}无效"
+                )
+            }
+        }
+    }
+}
+
+impl Error for UnescapeError {}
+
+/// 流式转义迭代器
+/// * 🚩每次从原迭代器取一个字符，必要时缓存其转义序列的剩余部分
+#[derive(Debug, Clone)]
+pub struct Escape<'t, I> {
+    table: &'t EscapeTable,
+    chars: I,
+    /// 待输出的转义序列剩余部分
+    pending: [char; MAX_ESCAPE_LEN],
+    pending_start: usize,
+    pending_end: usize,
+}
+
+impl<I> Escape<'_, I> {
+    /// 缓存待输出的字符
+    fn push_pending(&mut self, c: char) {
+        self.pending[self.pending_end] = c;
+        self.pending_end += 1;
+    }
+}
+
+impl<I: Iterator<Item = char>> Iterator for Escape<'_, I> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        // 先输出缓存
+        if self.pending_start < self.pending_end {
+            let c = self.pending[self.pending_start];
+            self.pending_start += 1;
+            return Some(c);
+        }
+        let c = self.chars.next()?;
+        self.pending_start = 0;
+        self.pending_end = 0;
+        // 对照表
+        if let Some(code) = self.table.code_of(c) {
+            self.push_pending(code);
+            return Some(self.table.escape_char);
+        }
+        // Unicode转义
+        if self.table.needs_unicode_escape(c) {
+            self.push_pending('u');
+            self.push_pending('{');
+            let value = c as u32;
+            let n_digits = ((32 - value.leading_zeros()).max(1)).div_ceil(4);
+            for i in (0..n_digits).rev() {
+                let digit = (value >> (i * 4)) & 0xf;
+                // * 🚩`digit`必定小于16
+                self.push_pending(char::from_digit(digit, 16).unwrap());
+            }
+            self.push_pending('}');
+            return Some(self.table.escape_char);
+        }
+        // 原样输出
+        Some(c)
+    }
+}
+
+/// 流式反转义迭代器
+/// * 🚩产出`Result<char, UnescapeError>`，出错后不再产出任何内容
+#[derive(Debug, Clone)]
+pub struct Unescape<'t, I> {
+    table: &'t EscapeTable,
+    chars: I,
+    /// 下一个字符的位置
+    position: usize,
+    /// 是否已出错
+    failed: bool,
+}
+
+impl<I: Iterator<Item = char>> Unescape<'_, I> {
+    /// 取下一个字符，并推进位置
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.chars.next()?;
+        self.position += 1;
+        Some(c)
+    }
+
+    /// 解析Unicode转义`{..}`部分（`\u`之后）
+    fn parse_unicode(&mut self, start: usize) -> Result<char, UnescapeError> {
+        let malformed = UnescapeError::MalformedUnicode { position: start };
+        if self.next_char() != Some('{') {
+            return Err(malformed);
+        }
+        let mut value = 0u32;
+        let mut n_digits = 0;
+        loop {
+            match self.next_char() {
+                Some('}') if n_digits > 0 => break,
+                Some(c) if n_digits < MAX_UNICODE_DIGITS => match c.to_digit(16) {
+                    Some(digit) => {
+                        value = value * 16 + digit;
+                        n_digits += 1;
+                    }
+                    None => return Err(malformed),
+                },
+                _ => return Err(malformed),
+            }
+        }
+        char::from_u32(value).ok_or(UnescapeError::InvalidCodePoint {
+            value,
+            position: start,
+        })
+    }
+
+    /// 解析一个转义序列（转义字符之后）
+    fn parse_escape(&mut self, start: usize) -> Result<char, UnescapeError> {
+        let code = self
+            .next_char()
+            .ok_or(UnescapeError::TrailingEscape { position: start })?;
+        match code {
+            'u' if self.table.unicode => self.parse_unicode(start),
+            _ => self.table.raw_of(code).ok_or(UnescapeError::UnknownEscape {
+                code,
+                position: start,
+            }),
+        }
+    }
+}
+
+impl<I: Iterator<Item = char>> Iterator for Unescape<'_, I> {
+    type Item = Result<char, UnescapeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let start = self.position;
+        let c = self.next_char()?;
+        if c != self.table.escape_char {
+            return Some(Ok(c));
+        }
+        let result = self.parse_escape(start);
+        self.failed = result.is_err();
+        Some(result)
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asserts;
+
+    #[test]
+    fn test_escape() {
+        let table = EscapeTable::rust_like();
+        asserts! {
+            table.escape_str("plain") => "plain",
+            table.escape_str("a\\b") => r"a\\b",
+            table.escape_str("\"'\n\r\t\0") => r#"\"\'\n\r\t\0"#,
+            // 控制字符⇒Unicode转义
+            table.escape_str("\u{1b}[0m") => r"\u{1b}[0m",
+            table.escape_str("\u{7f}") => r"\u{7f}",
+            // 非ASCII字符默认原样保留
+            table.escape_str("词项→😀") => "词项→😀",
+            table.clone().with_escape_non_ascii(true).escape_str("é😀") => r"\u{e9}\u{1f600}",
+            // 禁用Unicode转义⇒控制字符原样保留
+            table.clone().with_unicode(false).escape_str("\u{1b}") => "\u{1b}",
+            // 与标准库一致
+            table.escape_str("\u{1}\u{1f}") => "\u{1}\u{1f}".escape_debug().to_string(),
+        }
+    }
+
+    #[test]
+    fn test_unescape() {
+        let table = EscapeTable::rust_like();
+        asserts! {
+            table.unescape_str(r#"\"\'\n\r\t\0\\"#) => Ok("\"'\n\r\t\0\\".into()),
+            table.unescape_str(r"\u{1F600}\u{e9}\u{0}") => Ok("😀é\0".into()),
+            // 往返
+            table.unescape_str(&table.escape_str("a\"b\u{1}c\\")) => Ok("a\"b\u{1}c\\".into()),
+            // 错误的位置为字符位置
+            table.unescape_str(r"词项\") => Err(UnescapeError::TrailingEscape { position: 2 }),
+            table.unescape_str(r"ab\q") => Err(UnescapeError::UnknownEscape { code: 'q', position: 2 }),
+            table.unescape_str(r"\uA") => Err(UnescapeError::MalformedUnicode { position: 0 }),
+            table.unescape_str(r"x\u{}") => Err(UnescapeError::MalformedUnicode { position: 1 }),
+            table.unescape_str(r"x\u{12g}") => Err(UnescapeError::MalformedUnicode { position: 1 }),
+            table.unescape_str(r"\u{1234567}") => Err(UnescapeError::MalformedUnicode { position: 0 }),
+            table.unescape_str(r"\u{41") => Err(UnescapeError::MalformedUnicode { position: 0 }),
+            table.unescape_str(r"\u{d800}") => Err(UnescapeError::InvalidCodePoint { value: 0xd800, position: 0 }),
+            table.unescape_str(r"\u{110000}") => Err(UnescapeError::InvalidCodePoint { value: 0x110000, position: 0 }),
+            // 出错后终止
+            table.unescape(r"a\qb".chars()).collect::<Vec<_>>()
+                => vec![Ok('a'), Err(UnescapeError::UnknownEscape { code: 'q', position: 1 })],
+            // 禁用Unicode转义⇒`\u`为未知转义
+            table.clone().with_unicode(false).unescape_str(r"\u{41}")
+                => Err(UnescapeError::UnknownEscape { code: 'u', position: 0 }),
+        }
+    }
+
+    #[test]
+    fn test_custom_table() {
+        // 以`%`为转义字符，`%s`表示空格，`%p`表示`%`（覆盖默认的`%%`）
+        let table = EscapeTable::new('%').with(' ', 's').with('%', 'p');
+        asserts! {
+            table.escape_str("50% off") => "50%p%soff",
+            table.unescape_str("50%p%soff") => Ok("50% off".into()),
+            table.unescape_str("%%") => Err(UnescapeError::UnknownEscape { code: '%', position: 0 }),
+            // 无Unicode转义⇒控制字符原样
+            table.escape_str("\n") => "\n",
+            table.escape_char() => '%',
+        }
+        // 写入到目标
+        let mut out = String::from(">");
+        table.escape_to(&mut out, "a b").unwrap();
+        table.unescape_to(&mut out, "%sc").unwrap();
+        assert_eq!(out, ">a%sb c");
+        assert_eq!(
+            UnescapeError::UnknownEscape {
+                code: 'q',
+                position: 3
+            }
+            .position(),
+            3
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "into_chars")]
+    fn test_into_chars() {
+        use crate::IntoChars;
+        let table = EscapeTable::rust_like();
+        let escaped: String = table.escape("a\tb".into_chars()).collect();
+        assert_eq!(escaped, r"a\tb");
+        let unescaped: Result<String, _> = table.unescape(IntoChars::into_chars(escaped)).collect();
+        assert_eq!(unescaped, Ok("a\tb".to_string()));
+    }
+
+    #[test]
+    #[cfg(feature = "iterators")]
+    fn test_buffer_iterator() {
+        use crate::BufferIterator;
+        let table = EscapeTable::rust_like();
+        let mut chars = BufferIterator::new(r#"\"x\" rest"#.chars());
+        // 只反转义到第一个空格为止，余下的交给后续处理
+        let unescaped: Result<String, _> = table
+            .unescape(chars.by_ref().take_while(|&c| c != ' '))
+            .collect();
+        assert_eq!(unescaped, Ok("\"x\"".to_string()));
+        assert_eq!(chars.collect::<String>(), "rest");
+    }
+}
@@ -0,0 +1,337 @@
+//! 字符串驻留（符号表）
+//! * 🎯让大量重复出现的原子词项、操作符只在内存中存一份，以轻量的[`Symbol`]指代
+//!   * ⚡[`Symbol`]可[`Copy`]，判等、哈希均为O(1)
+//! * 📌提供三种形式
+//!   * 单线程的[`Interner`]：解析出`&str`
+//!   * 可跨线程共享的[`SyncInterner`]：内部加读写锁，解析出[`Arc<str>`]
+//!   * 全局实例[`global_interner`]：首次使用时才初始化
+//! * 🚩字串只分配一次：「查找表」与「符号→字串」的数组共享同一份[`Rc`]/[`Arc`]
+//!
+//! ## 示例
+//!
+//! ```rust
+//! use nar_dev_utils::Interner;
+//! let mut interner = Interner::new();
+//! let a = interner.intern("A");
+//! let b = interner.intern("B");
+//! assert_eq!(interner.intern("A"), a);
+//! assert_ne!(a, b);
+//! assert_eq!(interner.resolve(a), "A");
+//! ```
+
+use std::{
+    borrow::Borrow,
+    collections::HashMap,
+    hash::Hash,
+    num::NonZeroU32,
+    rc::Rc,
+    sync::{Arc, OnceLock, RwLock},
+};
+
+/// 驻留后的符号
+/// * 📌本质是「驻留器中的序号」：同一驻留器中，相同字串⇔相同符号
+/// * ⚠️不同驻留器产生的符号之间没有可比性
+/// * 📌[`Ord`]按「驻留的先后顺序」比较，而非字串的字典序
+///   * 🎯可直接用作[`AutoOrderedVec`](crate::AutoOrderedVec)等有序容器的元素
+/// * ⚡基于[`NonZeroU32`]：`Option<Symbol>`与`Symbol`大小相同
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(NonZeroU32);
+
+impl Symbol {
+    /// 由序号构造
+    /// * ⚠️序号超出[`u32`]范围时panic
+    fn from_index(index: usize) -> Self {
+        u32::try_from(index + 1)
+            .ok()
+            .and_then(NonZeroU32::new)
+            .map(Self)
+            .expect("驻留的字串数量超出上限")
+    }
+
+    /// 获取符号的序号
+    /// * 📌从0开始，按驻留的先后顺序递增
+    pub fn index(self) -> usize {
+        self.0.get() as usize - 1
+    }
+
+    /// 在全局驻留器中驻留字串
+    /// * 📄参见[`global_interner`]
+    pub fn global(s: &str) -> Self {
+        global_interner().intern(s)
+    }
+
+    /// 从全局驻留器中解析字串
+    /// * ⚠️符号并非来自全局驻留器时，结果无意义（可能panic）
+    pub fn resolve_global(self) -> Arc<str> {
+        global_interner().resolve(self)
+    }
+}
+
+/// 驻留器的共用实现
+/// * 🚩以共享字串`S`同时作为「查找表的键」与「数组的元素」，字串只分配一次
+#[derive(Debug, Clone)]
+struct InternerCore<S> {
+    /// 字串⇒符号
+    map: HashMap<S, Symbol>,
+    /// 符号⇒字串
+    strings: Vec<S>,
+}
+
+impl<S> Default for InternerCore<S> {
+    fn default() -> Self {
+        Self {
+            map: HashMap::new(),
+            strings: Vec::new(),
+        }
+    }
+}
+
+impl<S> InternerCore<S>
+where
+    S: Borrow<str> + for<'s> From<&'s str> + Clone + Eq + Hash,
+{
+    fn get(&self, s: &str) -> Option<Symbol> {
+        self.map.get(s).copied()
+    }
+
+    fn intern(&mut self, s: &str) -> Symbol {
+        if let Some(symbol) = self.get(s) {
+            return symbol;
+        }
+        let symbol = Symbol::from_index(self.strings.len());
+        let shared = S::from(s);
+        self.strings.push(shared.clone());
+        self.map.insert(shared, symbol);
+        symbol
+    }
+
+    fn try_resolve(&self, symbol: Symbol) -> Option<&S> {
+        self.strings.get(symbol.index())
+    }
+}
+
+/// 单线程驻留器
+/// * 🚩基于[`Rc<str>`]共享字串
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    core: InternerCore<Rc<str>>,
+}
+
+impl Interner {
+    /// 构造空驻留器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 驻留字串，返回其符号
+    /// * 📌已驻留过⇒返回已有符号，不再分配
+    pub fn intern(&mut self, s: &str) -> Symbol {
+        self.core.intern(s)
+    }
+
+    /// 查找已驻留字串的符号，不驻留新字串
+    pub fn get(&self, s: &str) -> Option<Symbol> {
+        self.core.get(s)
+    }
+
+    /// 解析符号
+    /// * ⚠️符号并非来自此驻留器、且序号超出范围时panic
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        self.try_resolve(symbol).expect("符号不属于此驻留器")
+    }
+
+    /// 尝试解析符号
+    pub fn try_resolve(&self, symbol: Symbol) -> Option<&str> {
+        self.core.try_resolve(symbol).map(|s| &**s)
+    }
+
+    /// 已驻留的字串数量
+    pub fn len(&self) -> usize {
+        self.core.strings.len()
+    }
+
+    /// 是否未驻留任何字串
+    pub fn is_empty(&self) -> bool {
+        self.core.strings.is_empty()
+    }
+
+    /// 按驻留顺序遍历所有「符号-字串」
+    pub fn iter(&self) -> impl Iterator<Item = (Symbol, &str)> + '_ {
+        self.core
+            .strings
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (Symbol::from_index(i), &**s))
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for Interner {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut interner = Self::new();
+        interner.extend(iter);
+        interner
+    }
+}
+
+impl<S: AsRef<str>> Extend<S> for Interner {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        for s in iter {
+            self.intern(s.as_ref());
+        }
+    }
+}
+
+/// 可跨线程共享的驻留器
+/// * 🚩基于[`Arc<str>`]共享字串，内部以[`RwLock`]保护
+///   * 📌已驻留的字串只需读锁；新字串才需写锁
+/// * ⚠️因有锁，解析结果以[`Arc<str>`]而非`&str`返回
+#[derive(Debug, Default)]
+pub struct SyncInterner {
+    core: RwLock<InternerCore<Arc<str>>>,
+}
+
+impl SyncInterner {
+    /// 构造空驻留器
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 驻留字串，返回其符号
+    pub fn intern(&self, s: &str) -> Symbol {
+        if let Some(symbol) = self.get(s) {
+            return symbol;
+        }
+        // * 📌获取写锁后仍需再查一遍：其间可能已被其它线程驻留
+        self.core.write().expect("驻留器的锁已中毒").intern(s)
+    }
+
+    /// 查找已驻留字串的符号，不驻留新字串
+    pub fn get(&self, s: &str) -> Option<Symbol> {
+        self.core.read().expect("驻留器的锁已中毒").get(s)
+    }
+
+    /// 解析符号
+    /// * ⚠️符号并非来自此驻留器、且序号超出范围时panic
+    pub fn resolve(&self, symbol: Symbol) -> Arc<str> {
+        self.try_resolve(symbol).expect("符号不属于此驻留器")
+    }
+
+    /// 尝试解析符号
+    pub fn try_resolve(&self, symbol: Symbol) -> Option<Arc<str>> {
+        self.core
+            .read()
+            .expect("驻留器的锁已中毒")
+            .try_resolve(symbol)
+            .cloned()
+    }
+
+    /// 已驻留的字串数量
+    pub fn len(&self) -> usize {
+        self.core.read().expect("驻留器的锁已中毒").strings.len()
+    }
+
+    /// 是否未驻留任何字串
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// 全局驻留器
+/// * 🎯供「不便传递驻留器」的场合（如[`Display`](std::fmt::Display)实现）使用
+/// * 📌首次调用时才初始化；从不使用则没有任何开销
+pub fn global_interner() -> &'static SyncInterner {
+    static GLOBAL: OnceLock<SyncInterner> = OnceLock::new();
+    GLOBAL.get_or_init(SyncInterner::new)
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asserts;
+
+    #[test]
+    fn test_interner() {
+        let mut interner = Interner::new();
+        let a = interner.intern("A");
+        let b = interner.intern("B");
+        asserts! {
+            interner.intern("A") => a,
+            interner.get("B") => Some(b),
+            interner.get("C") => None,
+            interner.resolve(a) => "A",
+            interner.try_resolve(Symbol::from_index(2)) => None,
+            a.index() => 0,
+            b.index() => 1,
+            a < b, // 按驻留顺序
+            interner.len() => 2,
+            interner.iter().collect::<Vec<_>>() => [(a, "A"), (b, "B")],
+            std::mem::size_of::<Option<Symbol>>() => std::mem::size_of::<Symbol>(),
+        }
+        // 字串只分配一次
+        let shared = interner.core.map.keys().next().unwrap();
+        assert_eq!(Rc::strong_count(shared), 2);
+        // 批量驻留
+        let interner: Interner = ["-->", "==>", "-->"].into_iter().collect();
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn test_sync_interner() {
+        let interner = Arc::new(SyncInterner::new());
+        let handles = (0..4)
+            .map(|_| {
+                let interner = interner.clone();
+                std::thread::spawn(move || ["A", "B", "C"].map(|s| interner.intern(s)))
+            })
+            .collect::<Vec<_>>();
+        let results = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect::<Vec<_>>();
+        // 各线程得到相同的符号
+        assert!(results.windows(2).all(|w| w[0] == w[1]));
+        asserts! {
+            interner.len() => 3,
+            &*interner.resolve(results[0][2]) => "C",
+            interner.try_resolve(Symbol::from_index(3)) => None,
+        }
+        // 全局实例
+        let symbol = Symbol::global("<global>");
+        asserts! {
+            Symbol::global("<global>") => symbol,
+            &*symbol.resolve_global() => "<global>",
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "vec_tools")]
+    fn test_as_ordered_key() {
+        use crate::AutoOrderedVec;
+        let mut interner = Interner::new();
+        let [b, a] = ["B", "A"].map(|s| interner.intern(s));
+        let mut vec = AutoOrderedVec::new();
+        vec.insert(a);
+        vec.insert(b);
+        // 按驻留顺序而非字典序
+        assert_eq!(vec.get(0), Some(&b));
+    }
+
+    #[test]
+    fn test_as_dict_payload() {
+        use crate::{BiFixMatchDictPair, PrefixMatch, PrefixMatchDictPair};
+        let mut interner = Interner::new();
+        let prefixes = PrefixMatchDictPair::new(
+            ["-->", "==>", "<->"].map(|copula| (copula, interner.intern(copula))),
+        );
+        let matched = prefixes.match_prefix("==> B").unwrap();
+        assert_eq!(interner.resolve(matched.1), "==>");
+        // 双向字典
+        let compound = interner.intern("compound");
+        let brackets = BiFixMatchDictPair::with_terms([("(", ")", compound)]);
+        assert_eq!(
+            brackets.get_by_prefix("(").map(|term| term.2),
+            Some(compound)
+        );
+    }
+}
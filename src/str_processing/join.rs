@@ -2,6 +2,10 @@
 //! * 🎯用于各种定制的字符串join方式
 
 use crate::{catch_flow, push_str};
+use std::{
+    fmt::{self, Display},
+    io,
+};
 
 /// 拼接字串到指定目标
 /// * 🎯将字符串集中拼接到一个「目标字串」中，中途不创建任何辅助字符串
@@ -77,6 +81,84 @@ pub fn join_to_multi_new(
     catch_flow!(join_to_multi; iter, sep)
 }
 
+/// 拼接[`Display`]元素到任意[`fmt::Write`]目标
+/// * 🎯将[`join_to`]推广到「任意可格式化写入的目标」与「任意可显示的元素」
+///   * 📄如：[`String`]、[`fmt::Formatter`]（在[`Display`]实现中直接拼接）
+/// * ✨元素与分隔符均直接写入目标，**不会**先格式化成临时字串
+/// * 🚩写入出错时立即返回错误，已写入的内容不作回滚
+/// * 📌是[`join_to_multi_fmt`]只有一个分隔符的特例
+pub fn join_to_fmt(
+    out: &mut impl fmt::Write,
+    iter: impl Iterator<Item = impl Display>,
+    sep: impl Display,
+) -> fmt::Result {
+    join_to_multi_fmt(out, iter, std::slice::from_ref(&sep))
+}
+
+/// 拼接[`Display`]元素到任意[`fmt::Write`]目标/多个分隔符
+/// * 🎯类似[`join_to_multi`]，但面向任意[`fmt::Write`]目标
+/// * 📄参见[`join_to_fmt`]
+pub fn join_to_multi_fmt(
+    out: &mut impl fmt::Write,
+    iter: impl Iterator<Item = impl Display>,
+    separators: &[impl Display],
+) -> fmt::Result {
+    let mut is_first = true;
+    for item in iter {
+        // 添加分隔符
+        match is_first {
+            true => is_first = false,
+            false => {
+                for sep in separators {
+                    write!(out, "{sep}")?;
+                }
+            }
+        }
+        // 添加元素
+        write!(out, "{item}")?;
+    }
+    Ok(())
+}
+
+/// 拼接[`Display`]元素到任意[`io::Write`]目标
+/// * 🎯将拼接结果直接流式写入文件、套接字等字节流，而无需先在内存中拼出整个字串
+/// * ✨元素与分隔符均直接写入目标，**不会**先格式化成临时字串
+/// * ⚠️不会自动包装缓冲：对无缓冲的目标（如[`std::fs::File`]），建议先套一层[`io::BufWriter`]
+/// * 🚩写入出错时立即返回错误，已写入的内容不作回滚
+/// * 📌是[`join_to_multi_io`]只有一个分隔符的特例
+pub fn join_to_io(
+    out: &mut impl io::Write,
+    iter: impl Iterator<Item = impl Display>,
+    sep: impl Display,
+) -> io::Result<()> {
+    join_to_multi_io(out, iter, std::slice::from_ref(&sep))
+}
+
+/// 拼接[`Display`]元素到任意[`io::Write`]目标/多个分隔符
+/// * 🎯类似[`join_to_multi`]，但面向任意[`io::Write`]目标
+/// * 📄参见[`join_to_io`]
+pub fn join_to_multi_io(
+    out: &mut impl io::Write,
+    iter: impl Iterator<Item = impl Display>,
+    separators: &[impl Display],
+) -> io::Result<()> {
+    let mut is_first = true;
+    for item in iter {
+        // 添加分隔符
+        match is_first {
+            true => is_first = false,
+            false => {
+                for sep in separators {
+                    write!(out, "{sep}")?;
+                }
+            }
+        }
+        // 添加元素
+        write!(out, "{item}")?;
+    }
+    Ok(())
+}
+
 /// 工具函数/有内容时前缀分隔符
 /// * 🎯最初用于「多个用空格分隔的条目」中「若其中有空字串，就无需连续空格」的情况
 /// * 关键在「避免无用分隔符」
@@ -176,6 +258,46 @@ pub trait JoinTo {
     {
         join_to_multi_new(self, sep)
     }
+
+    /// 将元素集中写入任意[`fmt::Write`]目标，元素只需实现[`Display`]
+    /// * 📄参见全局函数[`join_to_fmt`]
+    fn join_to_fmt<D>(self, out: &mut impl fmt::Write, sep: impl Display) -> fmt::Result
+    where
+        Self: Iterator<Item = D> + Sized,
+        D: Display,
+    {
+        join_to_fmt(out, self, sep)
+    }
+
+    /// 将元素集中写入任意[`fmt::Write`]目标，使用多个分隔符
+    /// * 📄参见全局函数[`join_to_multi_fmt`]
+    fn join_to_multi_fmt<D>(self, out: &mut impl fmt::Write, sep: &[impl Display]) -> fmt::Result
+    where
+        Self: Iterator<Item = D> + Sized,
+        D: Display,
+    {
+        join_to_multi_fmt(out, self, sep)
+    }
+
+    /// 将元素集中写入任意[`io::Write`]目标，元素只需实现[`Display`]
+    /// * 📄参见全局函数[`join_to_io`]
+    fn join_to_io<D>(self, out: &mut impl io::Write, sep: impl Display) -> io::Result<()>
+    where
+        Self: Iterator<Item = D> + Sized,
+        D: Display,
+    {
+        join_to_io(out, self, sep)
+    }
+
+    /// 将元素集中写入任意[`io::Write`]目标，使用多个分隔符
+    /// * 📄参见全局函数[`join_to_multi_io`]
+    fn join_to_multi_io<D>(self, out: &mut impl io::Write, sep: &[impl Display]) -> io::Result<()>
+    where
+        Self: Iterator<Item = D> + Sized,
+        D: Display,
+    {
+        join_to_multi_io(out, self, sep)
+    }
 }
 
 impl<T> JoinTo for T {}
@@ -212,6 +334,40 @@ mod macro_join_to {
         }
     }
 
+    /// 特制的「写入」方法
+    /// * 🎯供`join!`的写入模式使用：调用者无需引入[`std::fmt::Write`]或[`std::io::Write`]
+    /// * 🚩以「标记类型」区分两种目标，避免两个批量实现相互冲突
+    ///   * ⚠️同时实现两者的目标会产生歧义
+    pub trait MacroWritable<Marker> {
+        /// 写入的结果
+        type Output;
+
+        /// 写入格式化参数
+        fn join_write_fmt(&mut self, args: std::fmt::Arguments<'_>) -> Self::Output;
+    }
+
+    /// 标记：[`std::fmt::Write`]目标
+    pub struct FmtWriteMarker;
+
+    /// 标记：[`std::io::Write`]目标
+    pub struct IoWriteMarker;
+
+    impl<W: std::fmt::Write + ?Sized> MacroWritable<FmtWriteMarker> for W {
+        type Output = std::fmt::Result;
+
+        fn join_write_fmt(&mut self, args: std::fmt::Arguments<'_>) -> Self::Output {
+            std::fmt::Write::write_fmt(self, args)
+        }
+    }
+
+    impl<W: std::io::Write + ?Sized> MacroWritable<IoWriteMarker> for W {
+        type Output = std::io::Result<()>;
+
+        fn join_write_fmt(&mut self, args: std::fmt::Arguments<'_>) -> Self::Output {
+            std::io::Write::write_fmt(self, args)
+        }
+    }
+
     // ! ❌【2024-05-10 21:54:36】放弃「先实现可变，再对『可变』批量实现『不可变』」的思路：生命周期问题
     //   ! `(&mut self).join_to(suffix)`不起作用：`(&mut self)`「不在生命周期内」「仍然一直引用」
     // * ✅现在通过特制的「自动转所有权」语法，实现「表达式体」「语句体」的兼备
@@ -257,8 +413,160 @@ mod macro_join_to {
     /// );
     /// assert_eq!(s2, "0012 1 2 0xABC 0b101 0o33653337357_u64 0123456789");
     /// ```
+    ///
+    /// ## 写入模式
+    ///
+    /// * 🎯以`write 目标`开头时，向任意[`std::fmt::Write`]或[`std::io::Write`]目标流式写入
+    /// * ✨`{# }`等格式化语法直接写入目标，不再经由`format!`产生临时字串
+    /// * 📌基于特征[`MacroWritable`]写入，无需引入[`std::fmt::Write`]或[`std::io::Write`]
+    /// * 📌整个表达式返回目标对应的`Result`；出错后不再写入后续元素
+    ///
+    /// ```rust
+    /// use nar_dev_utils::join;
+    /// let mut s = String::new();
+    /// let closed = true;
+    /// let result: std::fmt::Result = join!(
+    ///     write &mut s
+    ///     => "<" // 直接写入`&str`
+    ///     => {# 0xab ; X} // AB
+    ///     => {# ", {i}" in} for i in 1..=3 // `for`循环
+    ///     => '>' if closed // `if`条件
+    /// );
+    /// assert!(result.is_ok());
+    /// assert_eq!(s, "<AB, 1, 2, 3>");
+    ///
+    /// // 写入字节流
+    /// let mut bytes: Vec<u8> = vec![];
+    /// join!(write &mut bytes => "x=" => {# 42}).unwrap();
+    /// assert_eq!(bytes, b"x=42");
+    /// ```
     #[macro_export]
     macro_rules! join {
+        // 写入模式/主入口：`join!(write 目标 => ...)`
+        // * 🎯面向任意[`std::fmt::Write`]或[`std::io::Write`]目标，返回相应的`Result`
+        // * 🚩各元素经由`MacroWritable`直接写入目标，不经过临时字串；出错后跳过后续所有元素
+        (
+            write $out:expr
+            $( => $($tail:tt)*)?
+        ) => {
+            {
+                let out = $out;
+                let mut result = Ok(());
+                $crate::join!(@WRITE out, result $( => $($tail)*)?);
+                result
+            }
+        };
+        // 写入模式/元素：`{# }`格式化
+        (@WEX $out:ident {# $ex:expr}) => {
+            $crate::MacroWritable::join_write_fmt(&mut *$out, format_args!("{}", $ex))
+        };
+        // 写入模式/元素：`{# ;?#}`格式化
+        (@WEX $out:ident {# $ex:expr ; $($fmt:tt)*}) => {
+            $crate::MacroWritable::join_write_fmt(
+                &mut *$out,
+                format_args!(concat!("{:", stringify!($($fmt)*), "}"), $ex),
+            )
+        };
+        // 写入模式/元素：`{# "0x{:X}" in $ex}`格式化
+        (@WEX $out:ident {# $fmt:literal in $($ex:tt)*}) => {
+            $crate::MacroWritable::join_write_fmt(&mut *$out, format_args!($fmt, $($ex)*))
+        };
+        // 写入模式/元素：兜底表达式（按`Display`写入）
+        (@WEX $out:ident $ex:expr) => {
+            $crate::MacroWritable::join_write_fmt(&mut *$out, format_args!("{}", $ex))
+        };
+        // 写入模式/条件`if`语法
+        (
+            @WRITE $out:ident, $result:ident
+            => $ex:tt if $condition:expr
+            $( => $($tail:tt)*)?
+        ) => {
+            if $result.is_ok() && $condition {
+                $result = $crate::join!(@WEX $out $ex);
+            }
+            $crate::join!(@WRITE $out, $result $( => $($tail)*)?);
+        };
+        // 写入模式/条件`if let`语法
+        (
+            @WRITE $out:ident, $result:ident
+            => $ex:tt if let $pattern:pat = $condition:expr
+            $( => $($tail:tt)*)?
+        ) => {
+            if $result.is_ok() {
+                if let $pattern = $condition {
+                    $result = $crate::join!(@WEX $out $ex);
+                }
+            }
+            $crate::join!(@WRITE $out, $result $( => $($tail)*)?);
+        };
+        // 写入模式/循环`while`语法
+        (
+            @WRITE $out:ident, $result:ident
+            => $ex:tt while $condition:expr
+            $( => $($tail:tt)*)?
+        ) => {
+            while $result.is_ok() && $condition {
+                $result = $crate::join!(@WEX $out $ex);
+            }
+            $crate::join!(@WRITE $out, $result $( => $($tail)*)?);
+        };
+        // 写入模式/循环`while let`语法
+        (
+            @WRITE $out:ident, $result:ident
+            => $ex:tt while let $pattern:pat = $condition:expr
+            $( => $($tail:tt)*)?
+        ) => {
+            if $result.is_ok() {
+                while let $pattern = $condition {
+                    $result = $crate::join!(@WEX $out $ex);
+                    if $result.is_err() {
+                        break;
+                    }
+                }
+            }
+            $crate::join!(@WRITE $out, $result $( => $($tail)*)?);
+        };
+        // 写入模式/循环`for`语法
+        (
+            @WRITE $out:ident, $result:ident
+            => $ex:tt for $pattern:pat in $iter:expr
+            $( => $($tail:tt)*)?
+        ) => {
+            if $result.is_ok() {
+                for $pattern in $iter {
+                    $result = $crate::join!(@WEX $out $ex);
+                    if $result.is_err() {
+                        break;
+                    }
+                }
+            }
+            $crate::join!(@WRITE $out, $result $( => $($tail)*)?);
+        };
+        // 写入模式/统一语法 `(表达式)` `{#格式化}`
+        (
+            @WRITE $out:ident, $result:ident
+            => $ex:tt
+            $( => $($tail:tt)*)?
+        ) => {
+            if $result.is_ok() {
+                $result = $crate::join!(@WEX $out $ex);
+            }
+            $crate::join!(@WRITE $out, $result $( => $($tail)*)?);
+        };
+        // 写入模式/表达式简写（兜底）
+        (
+            @WRITE $out:ident, $result:ident
+            => $ex:expr
+            $( => $($tail:tt)*)?
+        ) => {
+            $crate::join!(
+                @WRITE $out, $result
+                => ($ex) // ! 圆括弧括起，转发
+                $( => $($tail)*)?
+            );
+        };
+        // 写入模式/兜底
+        (@WRITE $out:ident, $result:ident) => {};
         // `{# }`格式化
         (@EX {# $ex:expr}) => {
             format!("{}", $ex)
@@ -406,7 +714,7 @@ pub use macro_join_to::*;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{asserts, catch_flow};
+    use crate::{asserts, catch_flow, join};
 
     #[test]
     fn test_join_to() {
@@ -431,6 +739,68 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_join_to_writer() {
+        asserts! {
+            // `fmt::Write` + `Display`元素
+            {
+                let mut s = String::from(">");
+                [1, 2, 3].iter().join_to_fmt(&mut s, ", ").unwrap();
+                s
+            } => ">1, 2, 3"
+            {
+                let mut s = String::new();
+                join_to_multi_fmt(&mut s, ['a', 'b'].iter(), &[";", " "]).unwrap();
+                s
+            } => "a; b"
+            // 空迭代器⇒不写入
+            {
+                let mut s = String::new();
+                join_to_fmt(&mut s, std::iter::empty::<u8>(), ",").unwrap();
+                s
+            } => ""
+            // `io::Write`
+            {
+                let mut bytes = vec![];
+                (1..=3).join_to_io(&mut bytes, '-').unwrap();
+                bytes
+            } => b"1-2-3".to_vec()
+            {
+                let mut bytes = vec![];
+                ["A", "B"].iter().join_to_multi_io(&mut bytes, &[",", " "]).unwrap();
+                bytes
+            } => b"A, B".to_vec()
+            // `join!`写入模式
+            {
+                let mut s = String::new();
+                let result = join!(
+                    write &mut s
+                    => "["
+                    => {# i} for i in 0..3
+                    => {# 2.5 ; .2} if true
+                    => {# 'x'} if false
+                    => ']'
+                );
+                (result, s)
+            } => (Ok(()), "[0122.50]".to_string())
+            {
+                let mut bytes = vec![];
+                let result = join!(write &mut bytes => {# "{:>3}|" in 7} => "end");
+                (result.is_ok(), bytes)
+            } => (true, b"  7|end".to_vec())
+        }
+        // 在`Display`实现中直接写入`Formatter`
+        struct Pair(u8, u8);
+        impl Display for Pair {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                join!(write f => '(' => {# self.0} => ", " => {# self.1} => ')')
+            }
+        }
+        asserts! {
+            Pair(1, 2).to_string() => "(1, 2)",
+        }
+    }
+
     #[test]
     fn test_add_space_if_necessary_and_flush_buffer() {
         asserts! {
@@ -22,3 +22,23 @@ pub use join::*;
 // 带所有权拆分
 pub mod split_owned;
 pub use split_owned::*;
+
+// 美观打印
+pub mod pretty;
+pub use pretty::*;
+
+// 转义/反转义
+pub mod escape;
+pub use escape::*;
+
+// 文本差异比较
+pub mod diff;
+pub use diff::*;
+
+// 字符串驻留
+pub mod interner;
+pub use interner::*;
+
+// 源码位置与诊断信息
+pub mod diagnostic;
+pub use diagnostic::*;
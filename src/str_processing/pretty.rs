@@ -0,0 +1,438 @@
+//! 按行宽自动换行的「美观打印」文档模型
+//! * 🎯将嵌套结构（如长复合词项）按指定行宽排版：放得下就一行，放不下就在「组」边界处换行并缩进
+//! * 📝算法参考 Wadler《A prettier printer》与 Lindig 的严格求值版本（Oppen 式的「能否放下」判断）
+//! * 🚩先用[`Doc`]描述「可选的换行位置」，再由[`Doc::render_to`]按行宽决定实际排版
+//!
+//! ## 示例
+//!
+//! ```rust
+//! use nar_dev_utils::Doc;
+//! let term = Doc::bracket(
+//!     "(",
+//!     Doc::join(["&&", "<A --> B>", "<C --> D>"], Doc::text(",").append(Doc::line())),
+//!     ")",
+//!     2,
+//! );
+//! // 放得下⇒一行
+//! assert_eq!(term.pretty(80), "(&&, <A --> B>, <C --> D>)");
+//! // 放不下⇒在组边界换行并缩进
+//! assert_eq!(term.pretty(10), "(\n  &&,\n  <A --> B>,\n  <C --> D>\n)");
+//! // 亦可通过格式化宽度指定行宽
+//! assert_eq!(format!("{term:10}"), term.pretty(10));
+//! ```
+
+use crate::str_display_width;
+use std::{
+    borrow::Cow,
+    fmt::{self, Display},
+};
+
+/// 未指定行宽时的默认行宽
+/// * 🎯用于[`Display`]实现中「格式化参数未指定宽度」的情形
+pub const DEFAULT_PRETTY_WIDTH: usize = 80;
+
+/// 美观打印文档
+/// * 🎯描述「文本」与「可选换行位置」的树状结构
+/// * 📌文本中不应含有换行符：强制换行请用[`Doc::hard_line`]
+/// * 🚩所有「软换行」的取舍由其**最近的**外层[`Doc::group`]统一决定：整组要么全部平铺，要么全部换行
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Doc<'a> {
+    /// 空文档
+    #[default]
+    Nil,
+    /// 原样输出的文本
+    Text(Cow<'a, str>),
+    /// 软换行
+    /// * 📌平铺时输出`flat`，换行时输出「换行+缩进」
+    Line {
+        /// 平铺时输出的文本（如空格、空串）
+        flat: Cow<'a, str>,
+    },
+    /// 强制换行
+    /// * ⚠️所在的组永远不会被平铺
+    HardLine,
+    /// 增加内部换行后的缩进
+    Nest(usize, Box<Doc<'a>>),
+    /// 组：整体决定「平铺」还是「换行」
+    Group(Box<Doc<'a>>),
+    /// 依次拼接
+    Concat(Vec<Doc<'a>>),
+}
+
+/// 排版模式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    /// 平铺：软换行输出其平铺文本
+    Flat,
+    /// 换行：软换行输出换行与缩进
+    Break,
+}
+
+/// 排版指令：（缩进, 模式, 文档）
+type Command<'d, 'a> = (usize, Mode, &'d Doc<'a>);
+
+/// 计算文本的显示宽度
+/// * 📌与[`Diagnostic`](crate::Diagnostic)的计算方式一致：漢文等宽字符计为2
+/// * 📄参见[`str_display_width`]
+#[inline]
+fn text_width(s: &str) -> usize {
+    str_display_width(s)
+}
+
+impl<'a> Doc<'a> {
+    /// 空文档
+    pub fn nil() -> Self {
+        Self::Nil
+    }
+
+    /// 文本
+    pub fn text(s: impl Into<Cow<'a, str>>) -> Self {
+        Self::Text(s.into())
+    }
+
+    /// 软换行，平铺时为一个空格
+    /// * 📄常用于分隔符之后：`", "` ⇒ `","` + `line`
+    pub fn line() -> Self {
+        Self::Line { flat: " ".into() }
+    }
+
+    /// 软换行，平铺时不输出任何内容
+    /// * 📄常用于括弧内侧：`"("` + `soft_line` + 内容
+    pub fn soft_line() -> Self {
+        Self::Line { flat: "".into() }
+    }
+
+    /// 软换行，平铺时输出指定文本
+    pub fn line_or(flat: impl Into<Cow<'a, str>>) -> Self {
+        Self::Line { flat: flat.into() }
+    }
+
+    /// 强制换行
+    pub fn hard_line() -> Self {
+        Self::HardLine
+    }
+
+    /// 将多个文档依次拼接
+    pub fn concat(docs: impl IntoIterator<Item = impl Into<Doc<'a>>>) -> Self {
+        Self::Concat(docs.into_iter().map(Into::into).collect())
+    }
+
+    /// 在后边追加另一个文档
+    /// * 🚩自动展平连续的拼接，避免嵌套过深
+    pub fn append(self, other: impl Into<Doc<'a>>) -> Self {
+        match (self, other.into()) {
+            (Self::Nil, other) => other,
+            (this, Self::Nil) => this,
+            (Self::Concat(mut docs), other) => {
+                docs.push(other);
+                Self::Concat(docs)
+            }
+            (this, other) => Self::Concat(vec![this, other]),
+        }
+    }
+
+    /// 增加内部换行后的缩进
+    pub fn nest(self, indent: usize) -> Self {
+        Self::Nest(indent, Box::new(self))
+    }
+
+    /// 成组：放得下则整组平铺，否则整组换行
+    pub fn group(self) -> Self {
+        Self::Group(Box::new(self))
+    }
+
+    /// 用分隔符拼接多个文档
+    /// * 🎯对应[`join_to`](crate::join_to)：仅在元素之间插入分隔符
+    /// * 📌分隔符中可含软换行，如`Doc::text(",").append(Doc::line())`
+    pub fn join(items: impl IntoIterator<Item = impl Into<Doc<'a>>>, sep: Doc<'a>) -> Self {
+        Self::join_multi(items, std::slice::from_ref(&sep))
+    }
+
+    /// 用多个分隔符拼接多个文档
+    /// * 🎯对应[`join_to_multi`](crate::join_to_multi)：元素之间依次插入所有分隔符
+    pub fn join_multi(
+        items: impl IntoIterator<Item = impl Into<Doc<'a>>>,
+        separators: &[Doc<'a>],
+    ) -> Self {
+        let mut docs = vec![];
+        for item in items {
+            // 添加分隔符
+            if !docs.is_empty() {
+                docs.extend(separators.iter().cloned());
+            }
+            // 添加元素
+            docs.push(item.into());
+        }
+        Self::Concat(docs)
+    }
+
+    /// 括弧包裹的组
+    /// * 🎯最常用的「复合结构」排版：
+    ///   * 平铺：`开括弧` + 内容 + `闭括弧`
+    ///   * 换行：开括弧独占行尾，内容缩进`indent`，闭括弧另起一行
+    pub fn bracket(
+        open: impl Into<Doc<'a>>,
+        body: impl Into<Doc<'a>>,
+        close: impl Into<Doc<'a>>,
+        indent: usize,
+    ) -> Self {
+        open.into()
+            .append(Self::soft_line().append(body).nest(indent))
+            .append(Self::soft_line())
+            .append(close)
+            .group()
+    }
+
+    /// 按指定行宽渲染到任意[`fmt::Write`]目标
+    /// * 🚩对每个组，检查「平铺后直到下一个换行前」能否放进剩余宽度
+    /// * 📌不会输出行尾空白：缩进延迟到该行第一个非空文本前输出
+    /// * ⚠️单个文本本身超出行宽时无法再拆分，将原样溢出
+    pub fn render_to(&self, width: usize, out: &mut impl fmt::Write) -> fmt::Result {
+        let mut column = 0;
+        let mut pending_indent = None;
+        let mut stack: Vec<Command> = vec![(0, Mode::Break, self)];
+        while let Some((indent, mode, doc)) = stack.pop() {
+            match doc {
+                Doc::Nil => {}
+                Doc::Text(s) => {
+                    Self::write_text(out, s, &mut column, &mut pending_indent)?;
+                }
+                Doc::Line { flat } if mode == Mode::Flat => {
+                    Self::write_text(out, flat, &mut column, &mut pending_indent)?;
+                }
+                Doc::Line { .. } | Doc::HardLine => {
+                    out.write_char('\n')?;
+                    column = indent;
+                    pending_indent = Some(indent);
+                }
+                Doc::Nest(j, d) => stack.push((indent + j, mode, d)),
+                Doc::Group(d) => {
+                    let mode = match mode {
+                        Mode::Flat => Mode::Flat,
+                        Mode::Break => {
+                            let remaining = width as isize - column as isize;
+                            match Self::fits(remaining, (indent, Mode::Flat, d), &stack) {
+                                true => Mode::Flat,
+                                false => Mode::Break,
+                            }
+                        }
+                    };
+                    stack.push((indent, mode, d));
+                }
+                Doc::Concat(docs) => stack.extend(docs.iter().rev().map(|d| (indent, mode, d))),
+            }
+        }
+        Ok(())
+    }
+
+    /// 按指定行宽渲染到新字串
+    pub fn pretty(&self, width: usize) -> String {
+        let mut out = String::new();
+        self.render_to(width, &mut out).expect("向字串写入不会失败");
+        out
+    }
+
+    /// 输出文本，并在需要时补上延迟的缩进
+    fn write_text(
+        out: &mut impl fmt::Write,
+        s: &str,
+        column: &mut usize,
+        pending_indent: &mut Option<usize>,
+    ) -> fmt::Result {
+        if s.is_empty() {
+            return Ok(());
+        }
+        if let Some(indent) = pending_indent.take() {
+            for _ in 0..indent {
+                out.write_char(' ')?;
+            }
+        }
+        out.write_str(s)?;
+        *column += text_width(s);
+        Ok(())
+    }
+
+    /// 判断「以平铺模式排版`next`，再接上剩余指令」直到下一个换行前，能否放进剩余宽度
+    /// * 📌剩余指令从栈顶往下依次取用，保持其原有模式
+    /// * 📌平铺模式下遇到强制换行⇒必定放不下（该组必须换行）
+    fn fits(mut remaining: isize, next: Command, rest: &[Command]) -> bool {
+        let mut stack = vec![next];
+        let mut rest = rest.iter().rev();
+        while remaining >= 0 {
+            let (indent, mode, doc) = match stack.pop() {
+                Some(command) => command,
+                None => match rest.next() {
+                    Some(&command) => command,
+                    None => return true,
+                },
+            };
+            match doc {
+                Doc::Nil => {}
+                Doc::Text(s) => remaining -= text_width(s) as isize,
+                Doc::Line { flat } => match mode {
+                    Mode::Flat => remaining -= text_width(flat) as isize,
+                    Mode::Break => return true,
+                },
+                Doc::HardLine => return mode == Mode::Break,
+                Doc::Nest(j, d) => stack.push((indent + j, mode, d)),
+                Doc::Group(d) => stack.push((indent, Mode::Flat, d)),
+                Doc::Concat(docs) => stack.extend(docs.iter().rev().map(|d| (indent, mode, d))),
+            }
+        }
+        false
+    }
+}
+
+impl<'a> From<&'a str> for Doc<'a> {
+    fn from(s: &'a str) -> Self {
+        Self::text(s)
+    }
+}
+
+impl From<String> for Doc<'_> {
+    fn from(s: String) -> Self {
+        Self::text(s)
+    }
+}
+
+impl<'a> From<Cow<'a, str>> for Doc<'a> {
+    fn from(s: Cow<'a, str>) -> Self {
+        Self::Text(s)
+    }
+}
+
+/// 以格式化宽度作为行宽渲染
+/// * 📌如`format!("{doc:40}")`以40为行宽；未指定时使用[`DEFAULT_PRETTY_WIDTH`]
+/// * 🎯便于在其它类型的[`Display`]实现中直接转发
+impl Display for Doc<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render_to(f.width().unwrap_or(DEFAULT_PRETTY_WIDTH), f)
+    }
+}
+
+/// 可转换为美观打印文档的类型
+/// * 🎯供词项等嵌套结构统一接入[`Doc`]排版
+pub trait ToDoc {
+    /// 生成借用自身的文档
+    fn to_doc(&self) -> Doc<'_>;
+
+    /// 按指定行宽美观打印
+    fn to_pretty(&self, width: usize) -> String {
+        self.to_doc().pretty(width)
+    }
+}
+
+impl ToDoc for str {
+    fn to_doc(&self) -> Doc<'_> {
+        Doc::text(self)
+    }
+}
+
+impl ToDoc for String {
+    fn to_doc(&self) -> Doc<'_> {
+        Doc::text(self.as_str())
+    }
+}
+
+impl<T: ToDoc> ToDoc for [T] {
+    /// 列表：方括弧包裹、逗号分隔，放不下时每行一个元素
+    fn to_doc(&self) -> Doc<'_> {
+        Doc::bracket(
+            "[",
+            Doc::join(
+                self.iter().map(ToDoc::to_doc),
+                Doc::text(",").append(Doc::line()),
+            ),
+            "]",
+            4,
+        )
+    }
+}
+
+impl<T: ToDoc> ToDoc for Vec<T> {
+    fn to_doc(&self) -> Doc<'_> {
+        self.as_slice().to_doc()
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asserts;
+
+    /// 构造一个类Narsese复合词项
+    fn compound<'a>(connecter: &'a str, components: Vec<Doc<'a>>) -> Doc<'a> {
+        Doc::bracket(
+            "(",
+            Doc::join(
+                std::iter::once(Doc::text(connecter)).chain(components),
+                Doc::text(",").append(Doc::line()),
+            ),
+            ")",
+            2,
+        )
+    }
+
+    #[test]
+    fn test_render() {
+        let inner = compound("*", vec!["A".into(), "B".into()]);
+        let term = compound("&&", vec![inner.clone(), "<C --> D>".into()]);
+        asserts! {
+            // 放得下⇒一行
+            term.pretty(80) => "(&&, (*, A, B), <C --> D>)",
+            // 外层放不下、内层放得下⇒只在外层换行
+            term.pretty(20) => "(\n  &&,\n  (*, A, B),\n  <C --> D>\n)",
+            // 都放不下⇒逐层换行缩进
+            term.pretty(8) => "(\n  &&,\n  (\n    *,\n    A,\n    B\n  ),\n  <C --> D>\n)",
+            // 恰好放下
+            inner.pretty(9) => "(*, A, B)",
+            inner.pretty(8) => "(\n  *,\n  A,\n  B\n)",
+            // 格式化宽度
+            format!("{term:20}") => term.pretty(20),
+            format!("{term}") => term.pretty(DEFAULT_PRETTY_WIDTH),
+        }
+        // 宽字符计为2：与诊断信息的宽度计算一致
+        let han = compound("与", vec!["甲是乙".into(), "丙".into()]);
+        asserts! {
+            han.pretty(16) => "(与, 甲是乙, 丙)",
+            han.pretty(15) => "(\n  与,\n  甲是乙,\n  丙\n)",
+        }
+    }
+
+    #[test]
+    fn test_lines() {
+        asserts! {
+            // 强制换行⇒所在组必定换行
+            Doc::join(["a", "b"], Doc::line()).append(Doc::hard_line()).append("c").group().pretty(80)
+                => "a\nb\nc",
+            // 自定义平铺文本
+            Doc::join(["a", "b"], Doc::line_or(" | ")).group().pretty(80) => "a | b",
+            Doc::join(["a", "b"], Doc::line_or(" | ")).group().pretty(3) => "a\nb",
+            // 不输出行尾空白：空行不带缩进
+            Doc::text("x")
+                .append(Doc::hard_line().append(Doc::hard_line()).append("y").nest(4))
+                .pretty(80) => "x\n\n    y",
+            // 多个分隔符
+            Doc::join_multi(["a", "b", "c"], &[Doc::text(";"), Doc::line()]).group().pretty(80)
+                => "a; b; c",
+            // 组外的软换行总是换行
+            Doc::join(["a", "b"], Doc::line()).pretty(80) => "a\nb",
+            // 后续内容也计入「能否放下」
+            Doc::join(["a", "b"], Doc::line()).group().append("cdef").pretty(6) => "a\nbcdef",
+            Doc::join(["a", "b"], Doc::line()).group().append("cdef").pretty(7) => "a bcdef",
+        }
+    }
+
+    #[test]
+    fn test_to_doc() {
+        let v = vec!["alpha".to_string(), "beta".to_string(), "gamma".to_string()];
+        asserts! {
+            v.to_pretty(80) => "[alpha, beta, gamma]",
+            v.to_pretty(10) => "[\n    alpha,\n    beta,\n    gamma\n]",
+            vec![vec!["a"; 2]; 2].iter().map(|v| v.iter().map(|s| s.to_string()).collect::<Vec<_>>()).collect::<Vec<_>>().to_pretty(80)
+                => "[[a, a], [a, a]]",
+            Vec::<String>::new().to_pretty(80) => "[]",
+        }
+    }
+}
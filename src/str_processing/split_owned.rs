@@ -1,17 +1,37 @@
 //! 有关「字符串带所有权拆分」的模块
 //! * 🎯提供【零额外空间开销】的字符串拆分功能
 
+use crate::{PrefixMatch, SuffixMatch, XFixMatchDict};
+
 /// 用于补足[`Pattern`](crate::str::Pattern)不稳定性的短板
 /// * 📌主要功能：一次查找返还两个量
 ///   * 📍首个字符的索引位置
 ///   * 📍整个图式的[`u8`]长度
 /// * 🚩【2024-08-17 21:45:44】目前需要[`Copy`]实属「保存在结构体中」的无奈
 ///   * ⚠️对于`&[char]`无法确定「选中的是哪个[`char`]」因此导致「无法确认选中的图式长度」
+///   * ✨多个字符串图式可用`&[&str]`或[`XFixMatchDict`]：同一位置上**最长的**胜出
 /// * ✨后续可扩展，或直接基于稳定后的[`Pattern`](crate::str::Pattern)特征加入
 pub trait PatternWithLen {
     /// 获取第一个匹配字符的索引位置和长度
     fn find_with_len(&self, haystack: &str) -> Option<(usize, usize)>;
 
+    /// 获取最后一个匹配字符的索引位置和长度
+    /// * 🎯用于从右往左拆分
+    /// * 📜默认实现：从左往右反复查找，取最后一个
+    ///   * ⚠️对「可重叠」的图式，结果可能与[`str::rfind`]不同
+    fn rfind_with_len(&self, haystack: &str) -> Option<(usize, usize)> {
+        let mut last = None;
+        let mut start = 0;
+        while let Some((i, len)) = self.find_with_len(&haystack[start..]) {
+            last = Some((start + i, len));
+            start += i + len;
+            if len == 0 || start >= haystack.len() {
+                break;
+            }
+        }
+        last
+    }
+
     /// 是否忽略最后一个空子串
     /// * 🎯同时适配「拆分行」与「拆分普通图式」
     ///   * 📄「拆分行」在`"abc\n"`仅拆分出`["abc"]`而不会拆出`""`
@@ -29,6 +49,11 @@ impl<F: Fn(char) -> bool> PatternWithLen for F {
             None => None,
         }
     }
+
+    fn rfind_with_len(&self, haystack: &str) -> Option<(usize, usize)> {
+        let i = haystack.rfind(self)?;
+        Some((i, next_char_len(haystack, i)?))
+    }
 }
 
 fn next_char_len(haystack: &str, i: usize) -> Option<usize> {
@@ -39,15 +64,104 @@ impl PatternWithLen for char {
     fn find_with_len(&self, haystack: &str) -> Option<(usize, usize)> {
         haystack.find(*self).map(|i| (i, self.len_utf8()))
     }
+
+    fn rfind_with_len(&self, haystack: &str) -> Option<(usize, usize)> {
+        haystack.rfind(*self).map(|i| (i, self.len_utf8()))
+    }
 }
 
 impl PatternWithLen for &str {
     fn find_with_len(&self, haystack: &str) -> Option<(usize, usize)> {
         // ! ❌【2024-08-17 22:57:19】禁用空字串的使用
-        // * 🔗参考：<https://github.com/rust-lang/rust/issues/33882>
-        assert!(!self.is_empty(), "Empty pattern is not allowed. Discussions see <https://github.com/rust-lang/rust/issues/33882>");
+        assert_non_empty_pattern(self);
         haystack.find(self).map(|i| (i, self.len()))
     }
+
+    fn rfind_with_len(&self, haystack: &str) -> Option<(usize, usize)> {
+        assert_non_empty_pattern(self);
+        haystack.rfind(self).map(|i| (i, self.len()))
+    }
+}
+
+/// 禁用空字串图式
+/// * 🔗参考：<https://github.com/rust-lang/rust/issues/33882>
+fn assert_non_empty_pattern(pattern: &str) {
+    assert!(!pattern.is_empty(), "Empty pattern is not allowed. Discussions see <https://github.com/rust-lang/rust/issues/33882>");
+}
+
+/// 多个字符串图式
+/// * 🚩取最靠左的匹配；同一位置有多个匹配时，取**最长的**
+///   * 📄`["-", "-->"]`拆分`"A-->B"`⇒`["A", "B"]`
+/// * 🚩从右往左时，取「结尾」最靠右的匹配；结尾相同时，同样取最长的
+/// * ⚠️同样禁用空字串图式
+impl PatternWithLen for &[&str] {
+    fn find_with_len(&self, haystack: &str) -> Option<(usize, usize)> {
+        self.iter()
+            .filter_map(|pattern| {
+                assert_non_empty_pattern(pattern);
+                haystack.find(pattern).map(|i| (i, pattern.len()))
+            })
+            // 位置靠左者优先，位置相同则长者优先
+            .min_by_key(|&(i, len)| (i, std::cmp::Reverse(len)))
+    }
+
+    fn rfind_with_len(&self, haystack: &str) -> Option<(usize, usize)> {
+        self.iter()
+            .filter_map(|pattern| {
+                assert_non_empty_pattern(pattern);
+                haystack.rfind(pattern).map(|i| (i, pattern.len()))
+            })
+            // 结尾靠右者优先，结尾相同则长者优先 | 对称于`find_with_len`
+            .max_by_key(|&(i, len)| (i + len, len))
+    }
+}
+
+/// 多个字符串图式（数组）
+/// * 📄参见`&[&str]`的实现
+impl<const N: usize> PatternWithLen for [&str; N] {
+    fn find_with_len(&self, haystack: &str) -> Option<(usize, usize)> {
+        self.as_slice().find_with_len(haystack)
+    }
+
+    fn rfind_with_len(&self, haystack: &str) -> Option<(usize, usize)> {
+        self.as_slice().rfind_with_len(haystack)
+    }
+}
+
+/// 以「词缀匹配字典」中的词缀为图式
+/// * 🚩逐个字符位置尝试前缀匹配；同一位置取**最长的**非空词缀
+/// * 🚩从右往左时，逐个结尾位置尝试后缀匹配；同样取最长的
+/// * 📌字典中的空词缀`""`会被忽略
+impl PatternWithLen for &XFixMatchDict {
+    fn find_with_len(&self, haystack: &str) -> Option<(usize, usize)> {
+        haystack
+            .char_indices()
+            .find_map(|(i, _)| Some((i, longest_non_empty_prefix(self, &haystack[i..])?)))
+    }
+
+    fn rfind_with_len(&self, haystack: &str) -> Option<(usize, usize)> {
+        haystack.char_indices().rev().find_map(|(i, c)| {
+            let end = i + c.len_utf8();
+            let len = longest_non_empty_suffix(self, &haystack[..end])?;
+            Some((end - len, len))
+        })
+    }
+}
+
+/// 在字典中找出「最长的非空前缀」的长度
+fn longest_non_empty_prefix(dict: &XFixMatchDict, to_match: &str) -> Option<usize> {
+    dict.match_prefix_all(to_match)
+        .map(|m| m.len)
+        .find(|&len| len > 0)
+}
+
+/// 在字典中找出「最长的非空后缀」的长度
+/// * ⚠️后缀匹配的迭代顺序不一定「从长到短」，故取最大值
+fn longest_non_empty_suffix(dict: &XFixMatchDict, to_match: &str) -> Option<usize> {
+    dict.match_suffix_all(to_match)
+        .map(|m| m.len)
+        .filter(|&len| len > 0)
+        .max()
 }
 
 /// 用于作为「换行」的搜索图式
@@ -77,6 +191,15 @@ impl PatternWithLen for NewLine {
         // 不然只有换行
         Some((lf_index, LEN_LF))
     }
+
+    /// 从右往左查找：先拿到最后一个换行`\n`，再回头看是否有回车`\r`
+    fn rfind_with_len(&self, haystack: &str) -> Option<(usize, usize)> {
+        let lf_index = haystack.rfind('\n')?;
+        match haystack[..lf_index].ends_with('\r') {
+            true => Some((lf_index - "\r".len(), "\r\n".len())),
+            false => Some((lf_index, "\n".len())),
+        }
+    }
 }
 
 /// 用于「根据指定字符拆分字符串」的迭代器
@@ -88,6 +211,26 @@ pub struct IterSplitCharOwned<Pattern: PatternWithLen> {
     residual: Option<String>,
     /// 分隔用图式（可拷贝）
     pattern: Pattern,
+    /// 还能拆出的子串数量
+    /// * 🎯[`SplitOwned::splitn_owned`]
+    /// * 📌[`None`]⇒不限
+    limit: Option<usize>,
+    /// 是否忽略最后一个空子串
+    /// * 🎯[`SplitOwned::split_terminator_owned`]
+    /// * 📌默认取[`PatternWithLen::IGNORE_FINAL_EMPTY`]
+    ignore_final_empty: bool,
+}
+
+impl<Pattern: PatternWithLen> IterSplitCharOwned<Pattern> {
+    /// 构造函数
+    fn new(s: String, pattern: Pattern) -> Self {
+        Self {
+            residual: Some(s),
+            pattern,
+            limit: None,
+            ignore_final_empty: Pattern::IGNORE_FINAL_EMPTY,
+        }
+    }
 }
 
 impl<Pattern: PatternWithLen> Iterator for IterSplitCharOwned<Pattern> {
@@ -95,6 +238,16 @@ impl<Pattern: PatternWithLen> Iterator for IterSplitCharOwned<Pattern> {
 
     fn next(&mut self) -> Option<Self::Item> {
         let residual = self.residual.as_mut()?;
+        // 数量限制：只剩一个⇒返回剩余的整个字符串
+        match self.limit {
+            Some(0) => return None,
+            Some(1) => {
+                self.limit = Some(0);
+                return self.residual.take();
+            }
+            Some(n) => self.limit = Some(n - 1),
+            None => {}
+        }
         // 寻找下一个换行符
         let mut new_residual = match self.pattern.find_with_len(residual) {
             // 空字串情况⇒单独处理
@@ -113,11 +266,16 @@ impl<Pattern: PatternWithLen> Iterator for IterSplitCharOwned<Pattern> {
                 new_residual
             }
             // 没分隔符了⇒返回自身所持有的字符串
+            // * 📌整个字符串为空时，也视作「最后一个空子串」
+            None if self.ignore_final_empty && residual.is_empty() => {
+                self.residual = None;
+                return None;
+            }
             None => return self.residual.take(),
         };
         // 将剩余的字符串移动到 residual 中
         std::mem::swap(residual, &mut new_residual);
-        if Pattern::IGNORE_FINAL_EMPTY && residual.is_empty() {
+        if self.ignore_final_empty && residual.is_empty() {
             // 剩余的字符串为空，则直接返回
             self.residual = None;
         }
@@ -127,6 +285,84 @@ impl<Pattern: PatternWithLen> Iterator for IterSplitCharOwned<Pattern> {
     }
 }
 
+/// 用于「从右往左拆分字符串」的迭代器
+/// * 🎯[`SplitOwned::rsplit_owned`]
+/// * 📌产出的子串与[`IterSplitCharOwned`]顺序相反
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IterRSplitOwned<Pattern: PatternWithLen> {
+    /// 剩余的字符串
+    residual: Option<String>,
+    /// 分隔用图式
+    pattern: Pattern,
+    /// 是否已产出过子串
+    /// * 🎯「忽略最后一个空子串」时，从右往左就是「忽略第一个空子串」
+    started: bool,
+}
+
+impl<Pattern: PatternWithLen> Iterator for IterRSplitOwned<Pattern> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let residual = self.residual.as_mut()?;
+            let started = std::mem::replace(&mut self.started, true);
+            let Some((index_begin_of_delim, len_delim)) = self.pattern.rfind_with_len(residual)
+            else {
+                // 没分隔符了⇒返回自身所持有的字符串
+                if Pattern::IGNORE_FINAL_EMPTY && !started && residual.is_empty() {
+                    self.residual = None;
+                }
+                return self.residual.take();
+            };
+            // 截去右侧的子串，再抛掉分隔符
+            let splitted_out = residual.split_off(index_begin_of_delim + len_delim);
+            residual.truncate(index_begin_of_delim);
+            if Pattern::IGNORE_FINAL_EMPTY && !started && splitted_out.is_empty() {
+                continue;
+            }
+            return Some(splitted_out);
+        }
+    }
+}
+
+/// 用于「拆分字符串，并保留分隔符」的迭代器
+/// * 🎯[`SplitOwned::split_inclusive_owned`]
+/// * 📌分隔符留在每个子串的末尾
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IterSplitInclusiveOwned<Pattern: PatternWithLen> {
+    /// 剩余的字符串
+    residual: Option<String>,
+    /// 分隔用图式
+    pattern: Pattern,
+}
+
+impl<Pattern: PatternWithLen> Iterator for IterSplitInclusiveOwned<Pattern> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let residual = self.residual.as_mut()?;
+        match self.pattern.find_with_len(residual) {
+            Some((index_begin_of_delim, len_delim)) => {
+                let mut new_residual = residual.split_off(index_begin_of_delim + len_delim);
+                // 将剩余的字符串移动到 residual 中，返回带分隔符的前半段
+                std::mem::swap(residual, &mut new_residual);
+                if residual.is_empty() {
+                    self.residual = None;
+                }
+                Some(new_residual)
+            }
+            // 与[`str::split_inclusive`]一致：不产出最后的空子串
+            None if residual.is_empty() => {
+                self.residual = None;
+                None
+            }
+            None => self.residual.take(),
+        }
+    }
+}
+
 /// 通用的「带所有权拆分」特征
 /// * 🎯对占用空间较大的字符串 无拷贝拆分
 ///   * 📄超长JSON文本
@@ -151,6 +387,34 @@ pub trait SplitOwned: Sized {
     /// owned = `["中", "文", "1", "2", "3", "🤣", "👉", "⇑", "🤡", "↑", "\n", "E", "n", "g", "l", "i", "s", "h", "😆", "\n", "あ", "💭", "t", "h", "i", "s", "\n", "Y", "o", "u", "!", "\r", "\n", "\t", " ", "\u{12}", "1", "\n"]`
     fn split_owned<Pattern: PatternWithLen>(self, pat: Pattern) -> impl Iterator<Item = String>;
 
+    /// 以某个图式分隔字符串，最多拆出`n`个子串
+    /// * 🎯[`str::splitn`]的带所有权版本
+    /// * 📌最后一个子串包含剩余的全部内容
+    fn splitn_owned<Pattern: PatternWithLen>(
+        self,
+        n: usize,
+        pat: Pattern,
+    ) -> impl Iterator<Item = String>;
+
+    /// 以某个图式从右往左分隔字符串
+    /// * 🎯[`str::rsplit`]的带所有权版本
+    /// * 📌对不重叠的图式，结果为[`SplitOwned::split_owned`]的倒序
+    fn rsplit_owned<Pattern: PatternWithLen>(self, pat: Pattern) -> impl Iterator<Item = String>;
+
+    /// 以某个图式分隔字符串，分隔符保留在子串末尾
+    /// * 🎯[`str::split_inclusive`]的带所有权版本
+    fn split_inclusive_owned<Pattern: PatternWithLen>(
+        self,
+        pat: Pattern,
+    ) -> impl Iterator<Item = String>;
+
+    /// 以某个图式分隔字符串，忽略最后的空子串
+    /// * 🎯[`str::split_terminator`]的带所有权版本
+    fn split_terminator_owned<Pattern: PatternWithLen>(
+        self,
+        pat: Pattern,
+    ) -> impl Iterator<Item = String>;
+
     /// 带所有权地拆分字符串的行
     /// * 🎯无空间开销地拆分字符串
     ///   * 📄场景：一个数十Kb级大小的JSON文本要拆成两行，需要尽可能避免内容复制
@@ -186,13 +450,52 @@ impl SplitOwned for String {
     fn split_owned<Pattern: PatternWithLen>(
         self,
         pattern: Pattern,
+    ) -> impl Iterator<Item = String> {
+        IterSplitCharOwned::new(self, pattern)
+    }
+
+    fn splitn_owned<Pattern: PatternWithLen>(
+        self,
+        n: usize,
+        pattern: Pattern,
     ) -> impl Iterator<Item = String> {
         IterSplitCharOwned {
+            limit: Some(n),
+            ..IterSplitCharOwned::new(self, pattern)
+        }
+    }
+
+    fn rsplit_owned<Pattern: PatternWithLen>(
+        self,
+        pattern: Pattern,
+    ) -> impl Iterator<Item = String> {
+        IterRSplitOwned {
+            residual: Some(self),
+            pattern,
+            started: false,
+        }
+    }
+
+    fn split_inclusive_owned<Pattern: PatternWithLen>(
+        self,
+        pattern: Pattern,
+    ) -> impl Iterator<Item = String> {
+        IterSplitInclusiveOwned {
             residual: Some(self),
             pattern,
         }
     }
 
+    fn split_terminator_owned<Pattern: PatternWithLen>(
+        self,
+        pattern: Pattern,
+    ) -> impl Iterator<Item = String> {
+        IterSplitCharOwned {
+            ignore_final_empty: true,
+            ..IterSplitCharOwned::new(self, pattern)
+        }
+    }
+
     fn split_owned_once<Pattern: PatternWithLen>(
         mut self,
         pattern: Pattern,
@@ -361,12 +664,24 @@ mod tests {
                 cloned_lines, owned_lines,
                 "两种方式拆分不等：\ns = {s:?}\ncloned = {cloned_lines:?}\n!=\nowned = {owned_lines:?}"
             );
+            // 从右往左拆分
+            let cloned_lines = s.lines().rev().map(ToString::to_string).collect::<Vec<_>>();
+            let owned_lines = s
+                .clone()
+                .rsplit_owned(NewLine)
+                .take(0xffff)
+                .collect::<Vec<_>>();
+            assert_eq!(
+                cloned_lines, owned_lines,
+                "两种方式拆分不等：\ns = {s:?}\ncloned = {cloned_lines:?}\n!=\nowned = {owned_lines:?}"
+            );
         }
         macro_once! {
             macro test( $($input:expr)* ) {
                 $(test($input);)*
             }
             "中文123🤣👉⇑🤡↑\nEnglish😆\nあ💭this\nYou!\r\n\t \x121\n"
+            ""
             "r \r n \n rn \r\n换行最后有内容"
             "俩\\n \n\n 后边"
             "俩\\r \r\r 后边"
@@ -397,4 +712,131 @@ mod tests {
             "\r\n ".repeat(0xff)
         }
     }
+
+    /// 实用宏 @ 比对「带所有权拆分」与标准库拆分的各个变体
+    macro_rules! test_split_variants {
+        ($pat:expr, $s:expr) => {{
+            let (pat, s) = ($pat, $s.to_string());
+            let to_vec = |iter: &mut dyn Iterator<Item = &str>| {
+                iter.map(ToString::to_string).collect::<Vec<_>>()
+            };
+            for n in 0..5 {
+                assert_eq!(
+                    to_vec(&mut s.splitn(n, pat)),
+                    s.clone().splitn_owned(n, pat).collect::<Vec<_>>(),
+                    "splitn不等：n = {n}, s = {s:?}, pat = {pat:?}"
+                );
+            }
+            assert_eq!(
+                to_vec(&mut s.rsplit(pat)),
+                s.clone().rsplit_owned(pat).collect::<Vec<_>>(),
+                "rsplit不等：s = {s:?}, pat = {pat:?}"
+            );
+            assert_eq!(
+                to_vec(&mut s.split_inclusive(pat)),
+                s.clone().split_inclusive_owned(pat).collect::<Vec<_>>(),
+                "split_inclusive不等：s = {s:?}, pat = {pat:?}"
+            );
+            assert_eq!(
+                to_vec(&mut s.split_terminator(pat)),
+                s.clone().split_terminator_owned(pat).collect::<Vec<_>>(),
+                "split_terminator不等：s = {s:?}, pat = {pat:?}"
+            );
+        }};
+    }
+
+    #[test]
+    fn split_owned_variants() {
+        fn test_char(c: char, s: &str) {
+            test_split_variants!(c, s)
+        }
+        fn test_str(pat: &str, s: &str) {
+            test_split_variants!(pat, s)
+        }
+        f_tensor! {
+            test_char;
+            '\r' '\n' '中' 'a';
+            ""
+            "中文123🤣👉⇑🤡↑\nEnglish😆\nあ💭this\nYou!\r\n\t \x121\n"
+            "r \r n \n rn \r\n换行最后有内容"
+            "\n换行最前有内容"
+            "换行最后无内容\r"
+            "换行最后无内容\n"
+            "中中\n\naa"
+        };
+        f_tensor! {
+            test_str;
+            "\r\n" "\n" "中文" "aa";
+            ""
+            "中文123🤣👉⇑🤡↑\nEnglish😆\nあ💭this\nYou!\r\n\t \x121\n"
+            "r \r n \n rn \r\n换行最后有内容"
+            "换行最后无内容\r\n"
+            "中文中文\n\naaa"
+        };
+    }
+
+    /// 多个字符串图式：最长者胜出
+    #[test]
+    fn split_owned_multi_pattern() {
+        use crate::{x_fix_match_dict, PrefixMatchDict};
+        let patterns: &[&str] = &["-", "-->", "=="];
+        let dict = x_fix_match_dict!("-" "-->" "==" "");
+        let split = |s: &str| s.to_string().split_owned(patterns).collect::<Vec<_>>();
+        let split_dict = |s: &str| s.to_string().split_owned(&dict).collect::<Vec<_>>();
+        assert_eq!(split("A-->B-C==D"), ["A", "B", "C", "D"]);
+        assert_eq!(split_dict("A-->B-C==D"), ["A", "B", "C", "D"]);
+        assert_eq!(split("A--B"), ["A", "", "B"]);
+        assert_eq!(split_dict("A--B"), ["A", "", "B"]);
+        assert_eq!(split(""), [""]);
+        assert_eq!(split_dict("中文"), ["中文"]);
+        // 数组
+        assert_eq!(
+            "A-->B"
+                .to_string()
+                .split_owned(["-", "-->"])
+                .collect::<Vec<_>>(),
+            ["A", "B"]
+        );
+        // 从右往左
+        assert_eq!(
+            "A-->B-C"
+                .to_string()
+                .rsplit_owned(patterns)
+                .collect::<Vec<_>>(),
+            ["C", "B", "A"]
+        );
+        assert_eq!(
+            "A-->B-C"
+                .to_string()
+                .rsplit_owned(&dict)
+                .collect::<Vec<_>>(),
+            ["C", "B", "A"]
+        );
+        // 一次拆分、限定次数、保留分隔符
+        assert_eq!(
+            "A-->B-C".to_string().split_owned_once(&dict),
+            Ok(("A".to_string(), "B-C".to_string()))
+        );
+        assert_eq!(
+            "A-->B-C"
+                .to_string()
+                .splitn_owned(2, patterns)
+                .collect::<Vec<_>>(),
+            ["A", "B-C"]
+        );
+        assert_eq!(
+            "A-->B-C"
+                .to_string()
+                .split_inclusive_owned(&dict)
+                .collect::<Vec<_>>(),
+            ["A-->", "B-", "C"]
+        );
+        assert_eq!(
+            "A-->B-"
+                .to_string()
+                .split_terminator_owned(&dict)
+                .collect::<Vec<_>>(),
+            ["A", "B"]
+        );
+    }
 }
@@ -4,11 +4,11 @@
 //! * ✨可由前缀搜后缀，亦可后缀搜前缀
 
 use super::traits::*;
-use crate::{search_by, PrefixMatchDictPair, SuffixMatchDictPair};
+use crate::search_by;
 
 /// 「双向配对条目」
-/// * 🎯实际就是`(前缀, 后缀)`的简写
-type BiFixTerm<P = Prefix, S = Suffix> = (P, S);
+/// * 🎯实际就是`(前缀, 后缀, 关联内容)`的简写
+type BiFixTerm<T = (), P = Prefix, S = Suffix> = (P, S, T);
 
 // /// 「双向配对引用条目」
 // /// * 🎯实际就是`(&前缀, &后缀)`的简写
@@ -18,12 +18,23 @@ type BiFixTerm<P = Prefix, S = Suffix> = (P, S);
 /// 双向配对字典
 /// * 🎯用于（通常左右括弧不重复的）括弧匹配场景
 ///   * ✨既能前缀匹配（返回后缀），也能后缀匹配（返回前缀）
+/// * ✨每对前后缀可携带一个「关联内容」`T`
+///   * 📄case: 括弧对所开启的「复合词项连接词」
+///   * 📌前缀匹配、后缀匹配都返回整个条目`&(前缀, 后缀, 关联内容)`
+///   * 📌不需要时默认为`()`
 /// * 📌虽「前缀⇔后缀」为双射，但「前缀の排列」不一定与「后缀の排列」相同
 ///   * 📄case: `("a", "")` & `("", "a")` ←这两者在「前缀匹配」与「后缀匹配」时有不同的顺序
 ///   * 💡但可以存储与其相关的引用
 ///
 /// ---
 ///
+/// * 📜现在使用「条目序列（按前缀排序） + 后缀序索引序列」的方式
+///   * 📌「前缀配对字典」的条目为二元组，无法直接存储三元组`(前缀, 后缀, 关联内容)`
+///     * 故将其「按前缀倒序排列的条目数组」直接内联到此处
+///   * 📌其它与下边的「捆绑」方式一致
+///
+/// ---
+///
 /// * 📜【2024-03-17 17:06:22】使用「前缀配对字典 + 后缀序索引序列」的「捆绑」方式
 ///   * 📌通过「前缀配对字典」实现「前后缀存储」与「前缀顺序信息」
 ///   * 📌通过「后缀序索引序列」实现「后缀顺序信息」
@@ -61,12 +72,12 @@ type BiFixTerm<P = Prefix, S = Suffix> = (P, S);
 ///   * 🎯在「迭代前缀/迭代后缀」时，需要【一次性返回整个条目的引用】而避免「东拼西凑」
 ///   * 📝对于「返回复杂数据之引用」的迭代器实现，最好的方法只能是「迭代出的数据本身就在内部存在」
 ///     * ❗否则就要走`.collect::<Vec<_>>().into_iter()`的下策（内存开销）
-#[derive(Debug, Clone, Default)]
-pub struct BiFixMatchDictPair {
-    /// 内部封装的「前缀配对字典」
-    /// * 📌存储「后缀」
-    /// * 🚩后缀唯一性在插入时判定
-    prefix_dict: PrefixMatchDictPair<Suffix>,
+#[derive(Debug, Clone)]
+pub struct BiFixMatchDictPair<T = ()> {
+    /// 条目序列
+    /// * 📌按前缀【倒序】排列，与[`crate::PrefixMatchDictPair`]一致
+    /// * 🚩前后缀唯一性在插入时判定
+    terms: Vec<BiFixTerm<T>>,
 
     /// 后缀序索引序列
     /// * 用于存储按后缀的排列顺序（后缀字母序反向）
@@ -75,75 +86,271 @@ pub struct BiFixMatchDictPair {
     suffix_ordered_refs: Vec<usize>,
 }
 
+/// 实现「默认构造函数」
+/// * 🚩通过「初始化空数组」完成
+/// * 📌不要求`T: Default`
+impl<T> Default for BiFixMatchDictPair<T> {
+    fn default() -> Self {
+        Self {
+            terms: Vec::new(),
+            suffix_ordered_refs: Vec::new(),
+        }
+    }
+}
+
 impl BiFixMatchDictPair {
     /// 构造函数
     /// * ⚠️实际上不推荐从所谓「迭代器」直接创建数组：**难以预先排序**
     /// * 🚩现在采用「先新建空值，然后逐个添加」来实现
     ///   * 📌复杂度：∑ 1 log 1 ~ n log n
-    /// * 📌格式：`条目=(其它元素, 后缀)`
-    pub fn new(
-        bi_fixes: impl IntoIterator<Item = BiFixTerm<impl Into<Prefix>, impl Into<Suffix>>>,
+    /// * 📌格式：`条目=(前缀, 后缀)`
+    /// * 📌会额外插入一个「空前缀 ⇄ 空后缀」的条目作为fallback
+    /// * 📄需要「关联内容」时，参见[`BiFixMatchDictPair::with_terms`]
+    pub fn new(bi_fixes: impl IntoIterator<Item = (impl Into<Prefix>, impl Into<Suffix>)>) -> Self {
+        let mut dict = Self::with_terms(
+            bi_fixes
+                .into_iter()
+                .map(|(prefix, suffix)| (prefix, suffix, ())),
+        );
+        dict.insert(Self::new_term(String::new(), String::new(), ()));
+        // 返回
+        dict
+    }
+}
+
+impl<T> BiFixMatchDictPair<T> {
+    /// 从「带关联内容的条目」构造
+    /// * 🚩逐个添加；前缀或后缀重复的条目会被跳过
+    /// * 📌格式：`条目=(前缀, 后缀, 关联内容)`
+    /// * ⚠️不会额外插入「空前缀 ⇄ 空后缀」的条目：没有可用的「关联内容」
+    pub fn with_terms(
+        terms: impl IntoIterator<Item = BiFixTerm<T, impl Into<Prefix>, impl Into<Suffix>>>,
     ) -> Self {
-        // ? 后续可以抽象提取`insert_suffix_terms`乃至`insert_terms`？
-        // 使用`default`创建一个空值
         let mut dict = Self::default();
-        // 逐个添加
-        for term in bi_fixes.into_iter() {
-            let term = Self::new_term(term.0.into(), term.1.into());
-            dict.insert(term);
+        for (prefix, suffix, associated) in terms {
+            dict.insert(Self::new_term(prefix.into(), suffix.into(), associated));
         }
-        dict.insert((String::new(), String::new()));
-        // 返回
         dict
     }
 
-    /// 从「前缀」与「后缀」构造「条目」
+    /// 从「前缀」「后缀」与「关联内容」构造「条目」
+    #[inline(always)]
+    pub fn new_term(prefix: Prefix, suffix: Suffix, associated: T) -> BiFixTerm<T> {
+        (prefix, suffix, associated)
+    }
+
+    /// 从「条目」中获取「关联内容」
     #[inline(always)]
-    pub fn new_term(prefix: Prefix, suffix: Suffix) -> BiFixTerm {
-        (prefix, suffix)
+    pub fn get_associated_from_term(term: &BiFixTerm<T>) -> &T {
+        &term.2
     }
 
     /// 从「后缀序索引」获取条目
-    /// * 🚩实际上转发到「前缀配对字典」
     /// * ⚠️**调用者注意：需要检查索引是否在界内**
-    pub(super) fn get_term_by_index(&self, index: usize) -> Option<&BiFixTerm> {
-        self.prefix_dict.prefixes.get(index)
+    pub(super) fn get_term_by_index(&self, index: usize) -> Option<&BiFixTerm<T>> {
+        self.terms.get(index)
+    }
+
+    /// 条目数量
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// 是否为空
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
     }
 
     /// 统一的「插入」方法
     /// * 🎯前后缀对称
     /// * 🚩要确保「前缀」「后缀」各自唯一
     /// * 🚩返回「是否成功插入」
-    pub fn insert(&mut self, term: BiFixTerm) -> bool {
-        // 先确保「后缀唯一」
-        let search_result = self.search_suffix(SuffixMatchDictPair::get_suffix_from_term(&term));
-        // 新后缀⇒插入
-        if let Err(i_insert) = search_result {
-            // 先尝试将元素插入「前缀配对字典」
-            // * 🎯返回并继续持有条目引用
-            if let Some(i_term) = self.prefix_dict.insert(term) {
-                // * ⚠️「前缀配对字典」中的「条目索引」会随着插入而改变
-                // * 需要在每次插入前更新「后缀序索引」中的「前缀配对字典条目索引」
-                //   * 🚩更新规则：先前大于等于自己的⇒自增1
-                self.suffix_ordered_refs
-                    // 获取可变引用
-                    .iter_mut()
-                    // 先前大于「要插入的地方」的
-                    .filter(|i_prefix_index| **i_prefix_index >= i_term)
-                    // 自增1
-                    .for_each(|i_prefix_index| *i_prefix_index += 1);
-                // ! 不要插入引用，插入索引
-                self.suffix_ordered_refs.insert(i_insert, i_term);
-                return true;
+    pub fn insert(&mut self, term: BiFixTerm<T>) -> bool {
+        // 先确保「前缀唯一」「后缀唯一」
+        let (Err(i_term), Err(i_insert)) = (
+            self.search_prefix(Self::get_prefix_from_term(&term)),
+            self.search_suffix(Self::get_suffix_from_term(&term)),
+        ) else {
+            return false;
+        };
+        // 插入条目
+        self.terms.insert(i_term, term);
+        // * ⚠️「条目序列」中的「条目索引」会随着插入而改变
+        // * 需要在每次插入前更新「后缀序索引」中的「条目索引」
+        //   * 🚩更新规则：先前大于等于自己的⇒自增1
+        self.suffix_ordered_refs
+            // 获取可变引用
+            .iter_mut()
+            // 先前大于「要插入的地方」的
+            .filter(|i_prefix_index| **i_prefix_index >= i_term)
+            // 自增1
+            .for_each(|i_prefix_index| *i_prefix_index += 1);
+        // ! 不要插入引用，插入索引
+        self.suffix_ordered_refs.insert(i_insert, i_term);
+        true
+    }
+
+    /// 根据前缀获取条目
+    pub fn get_by_prefix(&self, prefix: &PrefixStr) -> Option<&BiFixTerm<T>> {
+        let i_term = self.search_prefix(prefix).ok()?;
+        self.get_term_by_index(i_term)
+    }
+
+    /// 根据后缀获取条目
+    pub fn get_by_suffix(&self, suffix: &SuffixStr) -> Option<&BiFixTerm<T>> {
+        let i_suffix = self.search_suffix(suffix).ok()?;
+        self.get_term_by_index(self.suffix_ordered_refs[i_suffix])
+    }
+
+    /// 根据前缀获取后缀
+    pub fn get_suffix(&self, prefix: &PrefixStr) -> Option<&Suffix> {
+        self.get_by_prefix(prefix).map(|term| &term.1)
+    }
+
+    /// 根据后缀获取前缀
+    pub fn get_prefix(&self, suffix: &SuffixStr) -> Option<&Prefix> {
+        self.get_by_suffix(suffix).map(|term| &term.0)
+    }
+
+    /// 根据前缀获取「关联内容」
+    pub fn get(&self, prefix: &PrefixStr) -> Option<&T> {
+        self.get_by_prefix(prefix)
+            .map(Self::get_associated_from_term)
+    }
+
+    /// 根据前缀获取「关联内容」的可变引用
+    /// * 📌只开放「关联内容」的修改：前后缀决定了条目顺序，不能在原地修改
+    pub fn get_mut(&mut self, prefix: &PrefixStr) -> Option<&mut T> {
+        let i_term = self.search_prefix(prefix).ok()?;
+        Some(&mut self.terms[i_term].2)
+    }
+
+    /// 替换前缀所对应的「关联内容」
+    /// * 🚩有此前缀⇒替换并返回旧的关联内容
+    /// * 🚩无此前缀⇒不插入，返回[`None`]
+    pub fn replace_associated(&mut self, prefix: &PrefixStr, associated: T) -> Option<T> {
+        self.get_mut(prefix)
+            .map(|old| std::mem::replace(old, associated))
+    }
+
+    /// 替换前缀所对应的后缀
+    /// * 🚩成功⇒返回旧的后缀
+    /// * 🚩无此前缀，或新后缀已被其它条目占用⇒不修改，返回[`None`]
+    /// * 📌因「后缀」也参与排序，只能整体替换
+    pub fn replace_suffix(&mut self, prefix: &PrefixStr, suffix: Suffix) -> Option<Suffix> {
+        let i_term = self.search_prefix(prefix).ok()?;
+        // 新后缀已存在⇒只有「就是自身」时允许（相当于没改）
+        if let Ok(i_suffix) = self.search_suffix(&suffix) {
+            return match self.suffix_ordered_refs[i_suffix] == i_term {
+                true => Some(suffix),
+                false => None,
+            };
+        }
+        // 先移除再插入，以便同时维护前后缀的顺序
+        let (prefix, old_suffix, associated) = self.remove_by_index(i_term);
+        self.insert((prefix, suffix, associated));
+        Some(old_suffix)
+    }
+
+    /// 根据前缀移除条目
+    /// * 🚩返回被移除的条目
+    pub fn remove_by_prefix(&mut self, prefix: &PrefixStr) -> Option<BiFixTerm<T>> {
+        let i_term = self.search_prefix(prefix).ok()?;
+        Some(self.remove_by_index(i_term))
+    }
+
+    /// 根据后缀移除条目
+    /// * 🚩返回被移除的条目
+    pub fn remove_by_suffix(&mut self, suffix: &SuffixStr) -> Option<BiFixTerm<T>> {
+        let i_suffix = self.search_suffix(suffix).ok()?;
+        Some(self.remove_by_index(self.suffix_ordered_refs[i_suffix]))
+    }
+
+    /// 根据「条目序列」中的索引移除条目
+    /// * 🚩同时维护「后缀序索引序列」
+    ///   * 移除指向自身的索引
+    ///   * 先前大于自己的⇒自减1
+    /// * ⚠️**调用者注意：需要检查索引是否在界内**
+    fn remove_by_index(&mut self, i_term: usize) -> BiFixTerm<T> {
+        let term = self.terms.remove(i_term);
+        self.suffix_ordered_refs.retain(|&i| i != i_term);
+        self.suffix_ordered_refs
+            .iter_mut()
+            .filter(|i_prefix_index| **i_prefix_index > i_term)
+            .for_each(|i_prefix_index| *i_prefix_index -= 1);
+        term
+    }
+
+    /// 只保留满足条件的条目
+    /// * 🚩先记下「要保留的条目」，再同时筛选「条目序列」与「后缀序索引序列」
+    /// * 📌前后缀的顺序都不变
+    pub fn retain(&mut self, mut f: impl FnMut(&BiFixTerm<T>) -> bool) {
+        let keep = self.terms.iter().map(&mut f).collect::<Vec<_>>();
+        // 旧索引⇒新索引 | 对被移除的条目无意义
+        let new_indexes = keep
+            .iter()
+            .scan(0, |n_kept, &kept| {
+                let new_index = *n_kept;
+                *n_kept += kept as usize;
+                Some(new_index)
+            })
+            .collect::<Vec<_>>();
+        let mut keep_iter = keep.iter();
+        self.terms.retain(|_| *keep_iter.next().unwrap());
+        self.suffix_ordered_refs.retain(|&i| keep[i]);
+        self.suffix_ordered_refs
+            .iter_mut()
+            .for_each(|i| *i = new_indexes[*i]);
+    }
+
+    /// 找出条目在自身中的「冲突词缀」
+    /// * 📌「冲突」：前缀相同但后缀不同，或后缀相同但前缀不同
+    /// * 📌前后缀都相同的条目不算冲突（不论关联内容）
+    fn conflicting_x_fix<'t>(&self, (prefix, suffix, _): &'t BiFixTerm<T>) -> Option<&'t String> {
+        if matches!(self.get_suffix(prefix), Some(existed) if existed != suffix) {
+            return Some(prefix);
+        }
+        if matches!(self.get_prefix(suffix), Some(existed) if existed != prefix) {
+            return Some(suffix);
+        }
+        None
+    }
+
+    /// 合并另一个双向配对字典
+    /// * 📌「冲突」：前缀相同但后缀不同，或后缀相同但前缀不同；按[`MergePolicy`]处理
+    ///   * ⚠️覆盖时，前缀冲突与后缀冲突的条目**都会被移除**
+    ///   * 📌覆盖时，前后缀都相同的条目也会更新「关联内容」
+    /// * 🚩[`MergePolicy::Error`]策略下，先检查所有冲突再合并：报错时自身不变
+    pub fn merge(&mut self, other: Self, policy: MergePolicy) -> Result<(), MergeConflict> {
+        if policy == MergePolicy::Error {
+            let conflict = other
+                .terms
+                .iter()
+                .find_map(|term| self.conflicting_x_fix(term));
+            if let Some(x_fix) = conflict {
+                return Err(MergeConflict {
+                    x_fix: x_fix.clone(),
+                });
+            }
+        }
+        for term in other.terms {
+            if policy == MergePolicy::Overwrite {
+                self.remove_by_prefix(&term.0);
+                self.remove_by_suffix(&term.1);
             }
+            // 「保留已有」时，插入会因「前缀或后缀已存在」自动失败
+            self.insert(term);
         }
-        false
+        Ok(())
     }
 
     /// 搜索前缀
-    /// * 🚩直接转发到「前缀字典」
+    /// * 🚩在「条目序列」中按前缀搜索
+    /// * 📌与[`crate::PrefixMatchDictPair::search`]逻辑一致
     pub fn search_prefix(&self, prefix: &PrefixStr) -> Result<usize, usize> {
-        self.prefix_dict.search(prefix)
+        search_by(&self.terms, &prefix, |prefix, existed| {
+            Self::cmp_prefix(existed, prefix)
+        })
     }
 
     /// 搜索后缀
@@ -153,11 +360,24 @@ impl BiFixMatchDictPair {
         search_by(&self.suffix_ordered_refs, &suffix, |suffix, term_index| {
             // ! 此时因为是在「后缀」自身中搜索，故一定确保索引正确
             let term_ref = self.get_term_by_index(*term_index).unwrap();
-            SuffixMatchDictPair::cmp_suffix(term_ref, suffix)
+            Self::cmp_suffix(term_ref, suffix)
         })
     }
 }
 
+/// 批量插入条目
+/// * 🚩逐个调用[`BiFixMatchDictPair::insert`]，前缀或后缀已有的条目会被跳过
+impl<T> Extend<BiFixTerm<T>> for BiFixMatchDictPair<T> {
+    fn extend<I: IntoIterator<Item = BiFixTerm<T>>>(&mut self, iter: I) {
+        for term in iter {
+            self.insert(term);
+        }
+    }
+}
+
+/// 通过宏快捷构造「双向配对字典」
+/// * 📌格式：「前缀 => 后缀」或「前缀 => 后缀 => 关联内容」
+///   * 省略关联内容时，关联内容为`()`
 #[macro_export]
 macro_rules! bi_fix_match_dict_pair {
     // 转换其中的值 | 静态字串⇒动态字串 自动`into`
@@ -168,13 +388,22 @@ macro_rules! bi_fix_match_dict_pair {
     (@value $v:expr) => {
         $v
     };
+    // 关联内容 | 省略⇒`()`
+    (@associated) => {
+        ()
+    };
+    // 关联内容 | 表达式⇒直接加入
+    (@associated $v:expr) => {
+        $v
+    };
     // 统一的表 | 自面量也是一种表达式
-    [$($bi_fix:expr => $item:expr $(,)?)*] => {{
+    [$($prefix:expr => $suffix:expr $(=> $associated:expr)? $(,)?)*] => {{
         let mut d = $crate::BiFixMatchDictPair::default();
         $(
             d.insert((
-                bi_fix_match_dict_pair!(@value $bi_fix),
-                bi_fix_match_dict_pair!(@value $item),
+                $crate::bi_fix_match_dict_pair!(@value $prefix),
+                $crate::bi_fix_match_dict_pair!(@value $suffix),
+                $crate::bi_fix_match_dict_pair!(@associated $($associated)?),
             ));
         )*
         d
@@ -182,31 +411,29 @@ macro_rules! bi_fix_match_dict_pair {
 }
 
 /// 实现「前缀匹配」
-impl PrefixMatch<BiFixTerm> for BiFixMatchDictPair {
-    // 重定向到「前缀配对字典」的方法
-    fn get_prefix_from_term(term: &BiFixTerm) -> &PrefixStr {
-        PrefixMatchDictPair::get_prefix_from_term(term)
+impl<T> PrefixMatch<BiFixTerm<T>> for BiFixMatchDictPair<T> {
+    fn get_prefix_from_term(term: &BiFixTerm<T>) -> &PrefixStr {
+        &term.0
     }
 
-    // 重定向到内部的「前缀配对字典」
-    fn prefix_terms<'a>(&'a self) -> impl Iterator<Item = &'a BiFixTerm> + 'a
+    // 条目序列本身就按前缀倒序排列
+    fn prefix_terms<'a>(&'a self) -> impl Iterator<Item = &'a BiFixTerm<T>> + 'a
     where
-        BiFixTerm: 'a,
+        BiFixTerm<T>: 'a,
     {
-        self.prefix_dict.prefix_terms()
+        self.terms.iter()
     }
 }
 
 /// 实现「后缀匹配」
-impl SuffixMatch<BiFixTerm> for BiFixMatchDictPair {
-    // 重定向到内部的「后缀配对字典」的方法
-    fn get_suffix_from_term(term: &BiFixTerm) -> &SuffixStr {
-        SuffixMatchDictPair::get_suffix_from_term(term)
+impl<T> SuffixMatch<BiFixTerm<T>> for BiFixMatchDictPair<T> {
+    fn get_suffix_from_term(term: &BiFixTerm<T>) -> &SuffixStr {
+        &term.1
     }
 
-    fn suffix_terms<'a>(&'a self) -> impl Iterator<Item = &BiFixTerm> + 'a
+    fn suffix_terms<'a>(&'a self) -> impl Iterator<Item = &'a BiFixTerm<T>> + 'a
     where
-        BiFixTerm: 'a,
+        BiFixTerm<T>: 'a,
     {
         // * 直接在「后缀序索引序列」
         // ! ⚠️此处必须确保索引有效
@@ -220,7 +447,7 @@ impl SuffixMatch<BiFixTerm> for BiFixMatchDictPair {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{show, test_match_prefix, test_match_suffix};
+    use crate::{asserts, show, test_match_prefix, test_match_suffix};
 
     /// 测试/边缘
     #[test]
@@ -299,4 +526,130 @@ mod tests {
             "word" => None
         }
     }
+
+    /// 测试/移除、修改、合并
+    /// * 🎯重点检查「后缀序索引序列」是否被正确维护
+    #[test]
+    fn test_update_merge() {
+        let base: BiFixMatchDictPair = bi_fix_match_dict_pair!(
+            "(" => ")"
+            "[" => "]"
+            "{" => "}"
+            "<" => ">"
+        );
+        let mut d = base.clone();
+        asserts! {
+            d.remove_by_prefix("[") => Some(("[".into(), "]".into(), ())),
+            d.remove_by_suffix("}") => Some(("{".into(), "}".into(), ())),
+            d.remove_by_suffix("}") => None,
+            d.get_prefix(">") => Some(&"<".into()),
+            d.get_suffix("(") => Some(&")".into()),
+            d.match_suffix("<A>") => Some(&("<".into(), ">".into(), ())),
+            d.match_suffix("[A]") => None,
+            // 替换后缀
+            d.replace_suffix("(", "）".into()) => Some(")".into()),
+            d.replace_suffix("(", ">".into()) => None,
+            d.replace_suffix("(", "）".into()) => Some("）".into()),
+            d.replace_suffix("[", "]".into()) => None,
+            d.match_suffix("(A）") => Some(&("(".into(), "）".into(), ())),
+            d.match_suffix("(A)") => None,
+            d.match_prefix("<A>") => Some(&("<".into(), ">".into(), ())),
+        }
+        // 保留 | 前后缀均能正确匹配
+        let mut d = base.clone();
+        d.retain(|(l, ..)| l != "(" && l != "{");
+        asserts! {
+            d.suffix_terms().count() => 2,
+            d.match_suffix("[A]") => Some(&("[".into(), "]".into(), ())),
+            d.match_suffix("<A>") => Some(&("<".into(), ">".into(), ())),
+            d.match_suffix("(A)") => None,
+            d.match_prefix("[A]") => Some(&("[".into(), "]".into(), ())),
+        }
+
+        // 合并 | 变体方言：`(`改用全角右括弧，新增`【】`
+        let variant: BiFixMatchDictPair = bi_fix_match_dict_pair!(
+            "(" => "）"
+            "【" => "】"
+            "<" => ">"
+        );
+        let mut keep = base.clone();
+        keep.merge(variant.clone(), MergePolicy::KeepExisting)
+            .unwrap();
+        let mut overwrite = base.clone();
+        overwrite
+            .merge(variant.clone(), MergePolicy::Overwrite)
+            .unwrap();
+        let mut error = base.clone();
+        asserts! {
+            keep.get_suffix("(") => Some(&")".into()),
+            keep.get_suffix("【") => Some(&"】".into()),
+            overwrite.get_suffix("(") => Some(&"）".into()),
+            overwrite.get_prefix(")") => None,
+            overwrite.match_suffix("【A】") => Some(&("【".into(), "】".into(), ())),
+            overwrite.suffix_terms().count() => 5,
+            error.merge(variant, MergePolicy::Error) => Err(MergeConflict { x_fix: "(".into() }),
+            error.get_suffix("【") => None,
+        }
+        // 后缀冲突也会报错 | 完全相同的条目不算冲突
+        let mut d = base.clone();
+        asserts! {
+            d.merge(bi_fix_match_dict_pair!("<" => ">" "（" => ")"), MergePolicy::Error)
+                => Err(MergeConflict { x_fix: ")".into() }),
+            d.merge(bi_fix_match_dict_pair!("<" => ">"), MergePolicy::Error) => Ok(()),
+        }
+        // 批量插入
+        d.extend([
+            ("《".into(), "》".into(), ()),
+            ("（".into(), ")".into(), ()),
+        ]);
+        asserts! {
+            d.get_prefix("》") => Some(&"《".into()),
+            d.get_prefix(")") => Some(&"(".into()),
+        }
+    }
+
+    /// 测试/携带关联内容
+    /// * 🎯前缀匹配、后缀匹配都返回`&(前缀, 后缀, 关联内容)`
+    #[test]
+    fn test_associated() {
+        // 括弧对 ⇒ 所开启的复合词项连接词
+        let mut d: BiFixMatchDictPair<&str> = bi_fix_match_dict_pair!(
+            "(" => ")" => "product"
+            "{" => "}" => "ext_set"
+            "[" => "]" => "int_set"
+            r"\left(" => r"\right)" => "product"
+        );
+        asserts! {
+            d.match_prefix("{A, B}") => Some(&("{".into(), "}".into(), "ext_set")),
+            d.match_suffix("{A, B}") => Some(&("{".into(), "}".into(), "ext_set")),
+            d.match_prefix(r"\left(A\right)").map(|(.., c)| *c) => Some("product"),
+            d.match_suffix(r"\left(A\right)").map(|(.., c)| *c) => Some("product"),
+            d.get("[") => Some(&"int_set"),
+            d.get_by_suffix("]") => Some(&("[".into(), "]".into(), "int_set")),
+        }
+        // 修改关联内容不影响前后缀
+        asserts! {
+            d.replace_associated("[", "int_image") => Some("int_set"),
+            d.replace_suffix("[", "】".into()) => Some("]".into()),
+            d.match_suffix("[A】") => Some(&("[".into(), "】".into(), "int_image")),
+        }
+        if let Some(c) = d.get_mut("(") {
+            *c = "sequence";
+        }
+        asserts! {
+            d.match_suffix("(A)").map(|(.., c)| *c) => Some("sequence"),
+        }
+        // 构造函数
+        let d = BiFixMatchDictPair::with_terms([("<", ">", 1), ("(", ")", 2), ("「", ">", 3)]);
+        asserts! {
+            d.len() => 2, // 后缀重复的被跳过
+            d.match_suffix("<A>").map(|(.., n)| *n) => Some(1),
+            d.get_prefix(")") => Some(&"(".into()),
+        }
+        let d = BiFixMatchDictPair::new([("(", ")")]);
+        asserts! {
+            d.len() => 2, // 空前后缀fallback
+            d.match_prefix("A") => Some(&("".into(), "".into(), ())),
+        }
+    }
 }
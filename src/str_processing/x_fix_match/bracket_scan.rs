@@ -0,0 +1,685 @@
+//! 基于「双向配对字典」的括弧扫描
+//! * 🎯在字符串中找出配对的括弧，并构建「括弧跨度树」
+//!   * 📄拆分复合词项：`(&&, <a --> b>, (*, c, d))`
+//! * 📌支持嵌套与多字符括弧
+//!   * 🚩每个位置总是取**最长**的记号：左括弧、右括弧、引号、「不透明记号」
+//!   * 📄`<`与`-->`：将`-->`登记为「不透明记号」，其中的`>`便不会被当作右括弧
+//! * 📌可选地支持引号与转义
+//!   * 📌引号内的括弧不参与配对
+//!   * 📌转义字符只在引号内生效：避免与`\left(`之类的括弧冲突
+//! * 📌可在顶层（括弧、引号之外）按分隔符拆分字符串
+//! * 📌所有位置均为**字节**位置，可直接用于切分字符串
+
+use super::{traits::*, BiFixMatchDictPair, XFixMatchDict};
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    ops::Range,
+};
+
+/// 括弧跨度
+/// * 📌记录一对配对的括弧，以及其内嵌套的括弧
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BracketSpan<'d, T = ()> {
+    /// 配对的括弧 | `(左括弧, 右括弧, 关联内容)`
+    pub pair: &'d (String, String, T),
+    /// 左括弧所在的范围
+    pub open: Range<usize>,
+    /// 右括弧所在的范围
+    pub close: Range<usize>,
+    /// 直接嵌套在其中的括弧跨度
+    pub children: Vec<BracketSpan<'d, T>>,
+}
+
+impl<'d, T> BracketSpan<'d, T> {
+    /// 获取「括弧内部」的范围
+    /// * 📌不含括弧本身
+    pub fn inner(&self) -> Range<usize> {
+        self.open.end..self.close.start
+    }
+
+    /// 获取「括弧连同内部」的范围
+    pub fn outer(&self) -> Range<usize> {
+        self.open.start..self.close.end
+    }
+
+    /// 获取原字符串中「括弧内部」的切片
+    pub fn inner_str<'s>(&self, s: &'s str) -> &'s str {
+        &s[self.inner()]
+    }
+
+    /// 获取原字符串中「括弧连同内部」的切片
+    pub fn outer_str<'s>(&self, s: &'s str) -> &'s str {
+        &s[self.outer()]
+    }
+}
+
+/// 顶层拆分出的子串
+/// * 🎯[`BracketScanner::split_top_level`]的结果
+/// * 📌`S`为`&str`（借用）或[`String`]（带所有权）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TopLevelPiece<S> {
+    /// 子串在原字符串中的范围
+    /// * 📌字节位置，不含分隔符
+    pub span: Range<usize>,
+    /// 子串内容
+    pub text: S,
+}
+
+/// 括弧扫描的错误
+/// * 📌所有位置均为字节位置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BracketScanError {
+    /// 右括弧与最近的左括弧不配对
+    Mismatched {
+        /// 左括弧
+        open: String,
+        /// 左括弧所在的位置
+        open_position: usize,
+        /// 期望的右括弧
+        expected: String,
+        /// 实际遇到的右括弧
+        found: String,
+        /// 右括弧所在的位置
+        position: usize,
+    },
+    /// 没有对应左括弧的右括弧
+    UnexpectedClose {
+        /// 实际遇到的右括弧
+        found: String,
+        /// 右括弧所在的位置
+        position: usize,
+    },
+    /// 直到末尾都未闭合的左括弧
+    /// * 📌有多个时，报告最内层的那个
+    Unclosed {
+        /// 左括弧
+        open: String,
+        /// 左括弧所在的位置
+        open_position: usize,
+        /// 期望的右括弧
+        expected: String,
+    },
+    /// 直到末尾都未闭合的引号
+    UnclosedQuote {
+        /// 左引号
+        open: String,
+        /// 左引号所在的位置
+        open_position: usize,
+    },
+}
+
+impl Display for BracketScanError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mismatched {
+                open,
+                open_position,
+                expected,
+                found,
+                position,
+            } => write!(
+                f,
+                "括弧不配对：位置{open_position}处的{open:?}期望{expected:?}，但在位置{position}处遇到{found:?}"
+            ),
+            Self::UnexpectedClose { found, position } => {
+                write!(f, "多余的右括弧：位置{position}处的{found:?}没有对应的左括弧")
+            }
+            Self::Unclosed {
+                open,
+                open_position,
+                expected,
+            } => write!(
+                f,
+                "括弧未闭合：位置{open_position}处的{open:?}缺少{expected:?}"
+            ),
+            Self::UnclosedQuote {
+                open,
+                open_position,
+            } => write!(f, "引号未闭合：位置{open_position}处的{open:?}"),
+        }
+    }
+}
+
+impl Error for BracketScanError {}
+
+/// 括弧扫描器
+/// * 🚩以「双向配对字典」为括弧表，附加可选的「不透明记号」「引号」「转义字符」
+/// * ⚠️字典中的「空括弧」（`("", "")`）会被忽略
+#[derive(Debug, Clone)]
+pub struct BracketScanner<'d, T = ()> {
+    /// 括弧表
+    brackets: &'d BiFixMatchDictPair<T>,
+    /// 不透明记号：作为整体跳过，不参与配对
+    /// * 🎯避免`-->`中的`>`被当作右括弧
+    opaque: Option<&'d XFixMatchDict>,
+    /// 引号 | `(左引号, 右引号)`
+    quotes: Vec<(String, String)>,
+    /// 转义字符 | 仅在引号内生效
+    escape: Option<char>,
+    /// 最长左括弧的字节数
+    /// * 🎯查找左括弧时，只需按索引查找不超过此长度的前缀
+    max_open_len: usize,
+    /// 最长右括弧的字节数
+    max_close_len: usize,
+}
+
+/// 扫描时未闭合的左括弧
+struct OpenBracket<'d, T> {
+    /// 配对的括弧
+    pair: &'d (String, String, T),
+    /// 左括弧所在的范围
+    open: Range<usize>,
+    /// 已闭合的子跨度
+    children: Vec<BracketSpan<'d, T>>,
+}
+
+/// 扫描的结果
+struct ScanOutput<'d, T> {
+    /// 顶层的括弧跨度
+    roots: Vec<BracketSpan<'d, T>>,
+    /// 顶层分隔符的范围
+    separators: Vec<Range<usize>>,
+}
+
+/// 扫描时遇到的记号
+enum Token<'d, T> {
+    /// 左括弧
+    Open(&'d (String, String, T)),
+    /// 右括弧
+    Close(&'d (String, String, T)),
+    /// 左引号
+    Quote(usize),
+    /// 不透明记号
+    Opaque,
+}
+
+/// 【内部】从长到短，按索引查找字符串的各个前缀
+/// * 🚩只尝试不超过`max_len`字节、且在字符边界上的前缀，返回第一个找到的结果及其长度
+/// * 📌不会尝试空前缀
+fn longest_prefix_lookup<'s, R>(
+    s: &'s str,
+    max_len: usize,
+    lookup: impl Fn(&'s str) -> Option<R>,
+) -> Option<(R, usize)> {
+    (1..=max_len.min(s.len()))
+        .rev()
+        .filter(|&len| s.is_char_boundary(len))
+        .find_map(|len| lookup(&s[..len]).map(|found| (found, len)))
+}
+
+impl<'d, T> BracketScanner<'d, T> {
+    /// 构造函数
+    pub fn new(brackets: &'d BiFixMatchDictPair<T>) -> Self {
+        Self {
+            brackets,
+            opaque: None,
+            quotes: vec![],
+            escape: None,
+            max_open_len: brackets
+                .prefix_terms()
+                .map(|(open, ..)| open.len())
+                .max()
+                .unwrap_or(0),
+            max_close_len: brackets
+                .suffix_terms()
+                .map(|(_, close, _)| close.len())
+                .max()
+                .unwrap_or(0),
+        }
+    }
+
+    /// 设置「不透明记号」
+    pub fn with_opaque(mut self, opaque: &'d XFixMatchDict) -> Self {
+        self.opaque = Some(opaque);
+        self
+    }
+
+    /// 添加一对引号
+    pub fn with_quote(mut self, open: impl Into<String>, close: impl Into<String>) -> Self {
+        self.quotes.push((open.into(), close.into()));
+        self
+    }
+
+    /// 设置转义字符
+    /// * 📌仅在引号内生效
+    pub fn with_escape(mut self, escape: char) -> Self {
+        self.escape = Some(escape);
+        self
+    }
+
+    /// 扫描字符串，返回顶层的括弧跨度
+    /// * 🚩从左到右扫描，以栈维护未闭合的左括弧
+    /// * 📌同一记号既可作左括弧也可作右括弧时（如`|`），优先闭合栈顶
+    pub fn scan(&self, s: &str) -> Result<Vec<BracketSpan<'d, T>>, BracketScanError> {
+        self.scan_impl(s, None).map(|output| output.roots)
+    }
+
+    /// 在顶层（括弧、引号之外）按分隔符拆分字符串
+    /// * 🎯拆分复合词项的组分：`*, {a, b}, "x,y", c` ⇒ `*`、` {a, b}`、` "x,y"`、` c`
+    /// * 🚩扫描括弧的同时，在「栈为空」处查找分隔符
+    ///   * 📌分隔符优先于括弧等记号
+    /// * 📌返回的子串不含分隔符，也不做修剪
+    /// * 📌括弧不平衡⇒报错
+    ///
+    /// # Panics
+    ///
+    /// ⚠️分隔符不能为空字串
+    pub fn split_top_level<'s>(
+        &self,
+        s: &'s str,
+        separator: &str,
+    ) -> Result<Vec<TopLevelPiece<&'s str>>, BracketScanError> {
+        let spans = self.split_top_level_spans(s, separator)?;
+        Ok(spans
+            .into_iter()
+            .map(|span| TopLevelPiece {
+                text: &s[span.clone()],
+                span,
+            })
+            .collect())
+    }
+
+    /// 在顶层按分隔符拆分字符串（带所有权版本）
+    /// * 🚩先确定范围，再从右往左截断原字符串
+    ///   * 📌与[`crate::SplitOwned`]一样，不复制「最左侧的子串」
+    /// * 📄参见[`Self::split_top_level`]
+    pub fn split_top_level_owned(
+        &self,
+        mut s: String,
+        separator: &str,
+    ) -> Result<Vec<TopLevelPiece<String>>, BracketScanError> {
+        let spans = self.split_top_level_spans(&s, separator)?;
+        let mut pieces = Vec::with_capacity(spans.len());
+        for span in spans.into_iter().rev() {
+            // 抛掉右侧的分隔符，再截出子串
+            s.truncate(span.end);
+            let text = match span.start {
+                // 最左侧的子串⇒直接取走原字符串
+                0 => std::mem::take(&mut s),
+                start => s.split_off(start),
+            };
+            pieces.push(TopLevelPiece { span, text });
+        }
+        pieces.reverse();
+        Ok(pieces)
+    }
+
+    /// 【内部】计算顶层拆分后各个子串的范围
+    fn split_top_level_spans(
+        &self,
+        s: &str,
+        separator: &str,
+    ) -> Result<Vec<Range<usize>>, BracketScanError> {
+        assert!(!separator.is_empty(), "分隔符不能为空字串");
+        let separators = self.scan_impl(s, Some(separator))?.separators;
+        // 分隔符之间的范围
+        let mut start = 0;
+        let mut spans = Vec::with_capacity(separators.len() + 1);
+        for separator in separators {
+            spans.push(start..separator.start);
+            start = separator.end;
+        }
+        spans.push(start..s.len());
+        Ok(spans)
+    }
+
+    /// 【内部】扫描字符串
+    /// * 🚩从左到右扫描，以栈维护未闭合的左括弧
+    /// * 🚩若有分隔符，则一并返回顶层分隔符的范围
+    fn scan_impl(
+        &self,
+        s: &str,
+        separator: Option<&str>,
+    ) -> Result<ScanOutput<'d, T>, BracketScanError> {
+        // 未闭合的左括弧
+        let mut stack: Vec<OpenBracket<'d, T>> = vec![];
+        let mut roots = vec![];
+        let mut separators = vec![];
+        let mut i = 0;
+        while i < s.len() {
+            let rest = &s[i..];
+            // 顶层⇒先尝试分隔符
+            if let Some(separator) =
+                separator.filter(|sep| stack.is_empty() && rest.starts_with(sep))
+            {
+                separators.push(i..i + separator.len());
+                i += separator.len();
+                continue;
+            }
+            let expected = stack.last().map(|open| open.pair.1.as_str());
+            let Some((token, len)) = self.longest_token(rest, expected) else {
+                // 普通字符⇒跳过
+                i += rest.chars().next().map_or(1, char::len_utf8);
+                continue;
+            };
+            match token {
+                Token::Opaque => {}
+                // 引号⇒直接跳到右引号之后
+                Token::Quote(index) => {
+                    i = self.skip_quote(s, i, index)?;
+                    continue;
+                }
+                Token::Open(pair) => stack.push(OpenBracket {
+                    pair,
+                    open: i..i + len,
+                    children: vec![],
+                }),
+                Token::Close(pair) => match stack.pop() {
+                    // 配对⇒闭合
+                    Some(open) if open.pair.1 == pair.1 => {
+                        let span = BracketSpan {
+                            pair: open.pair,
+                            open: open.open,
+                            close: i..i + len,
+                            children: open.children,
+                        };
+                        match stack.last_mut() {
+                            Some(parent) => parent.children.push(span),
+                            None => roots.push(span),
+                        }
+                    }
+                    // 不配对
+                    Some(open) => {
+                        return Err(BracketScanError::Mismatched {
+                            open: open.pair.0.clone(),
+                            open_position: open.open.start,
+                            expected: open.pair.1.clone(),
+                            found: pair.1.clone(),
+                            position: i,
+                        })
+                    }
+                    // 没有左括弧
+                    None => {
+                        return Err(BracketScanError::UnexpectedClose {
+                            found: pair.1.clone(),
+                            position: i,
+                        })
+                    }
+                },
+            }
+            i += len;
+        }
+        // 未闭合⇒报告最内层
+        match stack.pop() {
+            Some(open) => Err(BracketScanError::Unclosed {
+                open: open.pair.0.clone(),
+                open_position: open.open.start,
+                expected: open.pair.1.clone(),
+            }),
+            None => Ok(ScanOutput { roots, separators }),
+        }
+    }
+
+    /// 【内部】取出当前位置上最长的记号
+    /// * 📌长度相同时的优先级：闭合栈顶的右括弧 > 左括弧 > 其它右括弧 > 引号 > 不透明记号
+    /// * 🚩括弧按索引查找，且只记录当前最长的记号，不分配候选列表
+    fn longest_token(&self, rest: &str, expected: Option<&str>) -> Option<(Token<'d, T>, usize)> {
+        let brackets = self.brackets;
+        let mut longest = None;
+        // 只有更长时才替换：一样长的取先来的
+        let mut consider = |token: Token<'d, T>, len: usize| {
+            if longest
+                .as_ref()
+                .is_none_or(|(_, longest_len)| len > *longest_len)
+            {
+                longest = Some((token, len));
+            }
+        };
+        // 闭合栈顶的右括弧
+        if let Some(expected) = expected.filter(|e| !e.is_empty() && rest.starts_with(e)) {
+            if let Some(pair) = brackets.get_by_suffix(expected) {
+                consider(Token::Close(pair), expected.len());
+            }
+        }
+        // 左括弧
+        if let Some((pair, len)) =
+            longest_prefix_lookup(rest, self.max_open_len, |open| brackets.get_by_prefix(open))
+        {
+            consider(Token::Open(pair), len);
+        }
+        // 右括弧
+        if let Some((pair, len)) = longest_prefix_lookup(rest, self.max_close_len, |close| {
+            brackets.get_by_suffix(close)
+        }) {
+            consider(Token::Close(pair), len);
+        }
+        // 引号
+        for (index, (open, _)) in self.quotes.iter().enumerate() {
+            if !open.is_empty() && rest.starts_with(open.as_str()) {
+                consider(Token::Quote(index), open.len());
+            }
+        }
+        // 不透明记号
+        if let Some(m) = self
+            .opaque
+            .and_then(|opaque| opaque.match_prefix_longest(rest))
+            .filter(|m| m.len > 0)
+        {
+            consider(Token::Opaque, m.len);
+        }
+        longest
+    }
+
+    /// 【内部】跳过引号
+    /// * 🚩从左引号处开始，返回右引号之后的位置
+    fn skip_quote(&self, s: &str, start: usize, index: usize) -> Result<usize, BracketScanError> {
+        let (open, close) = &self.quotes[index];
+        let mut i = start + open.len();
+        while i < s.len() {
+            let rest = &s[i..];
+            if rest.starts_with(close.as_str()) && !close.is_empty() {
+                return Ok(i + close.len());
+            }
+            let mut chars = rest.chars();
+            let c = chars.next().unwrap();
+            i += c.len_utf8();
+            // 转义⇒连同下一个字符一并跳过
+            if Some(c) == self.escape {
+                i += chars.next().map_or(0, char::len_utf8);
+            }
+        }
+        Err(BracketScanError::UnclosedQuote {
+            open: open.clone(),
+            open_position: start,
+        })
+    }
+}
+
+impl<T> BiFixMatchDictPair<T> {
+    /// 扫描字符串中配对的括弧
+    /// * 🚩使用默认设置（无引号、无转义、无不透明记号）的[`BracketScanner`]
+    /// * 📌返回顶层的括弧跨度
+    pub fn scan_brackets<'d>(
+        &'d self,
+        s: &str,
+    ) -> Result<Vec<BracketSpan<'d, T>>, BracketScanError> {
+        BracketScanner::new(self).scan(s)
+    }
+
+    /// 在顶层（括弧之外）按分隔符拆分字符串
+    /// * 🚩使用默认设置（无引号、无转义、无不透明记号）的[`BracketScanner`]
+    /// * 📄需要引号、转义时，参见[`BracketScanner::split_top_level`]
+    pub fn split_top_level<'s>(
+        &self,
+        s: &'s str,
+        separator: &str,
+    ) -> Result<Vec<TopLevelPiece<&'s str>>, BracketScanError> {
+        BracketScanner::new(self).split_top_level(s, separator)
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{asserts, bi_fix_match_dict_pair, x_fix_match_dict, PrefixMatchDict};
+
+    /// Narsese的括弧表
+    fn narsese_brackets() -> BiFixMatchDictPair {
+        bi_fix_match_dict_pair!(
+            "(" => ")"
+            "[" => "]"
+            "{" => "}"
+            "<" => ">"
+        )
+    }
+
+    /// 测试/嵌套与多字符记号
+    #[test]
+    fn test_scan_compound() {
+        let brackets = narsese_brackets();
+        let copulas: XFixMatchDict = x_fix_match_dict!("-->" "<->" "==>" "<=>");
+        let scanner = BracketScanner::new(&brackets).with_opaque(&copulas);
+        let s = "(&&, <a --> b>, (*, c, d))";
+        let roots = scanner.scan(s).unwrap();
+        let root = &roots[0];
+        asserts! {
+            roots.len() => 1,
+            root.outer() => 0..s.len(),
+            root.inner_str(s) => "&&, <a --> b>, (*, c, d)",
+            root.children.len() => 2,
+            root.children[0].outer_str(s) => "<a --> b>",
+            root.children[0].pair.1 => ">",
+            root.children[1].inner_str(s) => "*, c, d",
+            root.children[1].children.len() => 0,
+        }
+        // 以`<`开头的系词也不会被当作括弧
+        let roots = scanner.scan("<a <-> b>").unwrap();
+        asserts! {
+            roots.len() => 1,
+            roots[0].close => 8..9,
+        }
+        // 不登记系词⇒`-->`中的`>`会提前闭合`<`，导致错误
+        asserts! {
+            brackets.scan_brackets(s) => @ Err(BracketScanError::Mismatched { position: 13, .. }),
+        }
+    }
+
+    /// 测试/多字符括弧
+    #[test]
+    fn test_scan_multi_char() {
+        let brackets: BiFixMatchDictPair = bi_fix_match_dict_pair!(
+            "(" => ")"
+            r"\left(" => r"\right)"
+            "「" => "」"
+        );
+        let s = r"\left(A, (B)\right)「C」";
+        let roots = brackets.scan_brackets(s).unwrap();
+        asserts! {
+            roots.len() => 2,
+            roots[0].inner_str(s) => "A, (B)",
+            roots[0].children[0].inner_str(s) => "B",
+            roots[1].inner_str(s) => "C",
+        }
+    }
+
+    /// 测试/引号与转义
+    #[test]
+    fn test_scan_quote() {
+        let brackets = narsese_brackets();
+        let scanner = BracketScanner::new(&brackets)
+            .with_quote("\"", "\"")
+            .with_escape('\\');
+        let s = r#"(say, "a ) \" (", b)"#;
+        let roots = scanner.scan(s).unwrap();
+        asserts! {
+            roots.len() => 1,
+            roots[0].children.len() => 0,
+            roots[0].outer() => 0..s.len(),
+            scanner.scan(r#"("abc)"#) => Err(BracketScanError::UnclosedQuote { open: "\"".into(), open_position: 1 }),
+        }
+        // 不使用引号⇒引号内的括弧参与配对
+        asserts! {
+            brackets.scan_brackets(s).map(|roots| roots.len()) => Ok(2),
+        }
+    }
+
+    /// 测试/错误
+    #[test]
+    fn test_scan_error() {
+        let brackets = narsese_brackets();
+        asserts! {
+            brackets.scan_brackets("(A]") => Err(BracketScanError::Mismatched {
+                open: "(".into(),
+                open_position: 0,
+                expected: ")".into(),
+                found: "]".into(),
+                position: 2,
+            }),
+            brackets.scan_brackets("A)") => Err(BracketScanError::UnexpectedClose { found: ")".into(), position: 1 }),
+            brackets.scan_brackets("(A, [B]") => Err(BracketScanError::Unclosed {
+                open: "(".into(),
+                open_position: 0,
+                expected: ")".into(),
+            }),
+            brackets.scan_brackets("no brackets") => Ok(vec![]),
+        }
+        asserts! {
+            brackets.scan_brackets("(A]").unwrap_err().to_string()
+                => r#"括弧不配对：位置0处的"("期望")"，但在位置2处遇到"]""#,
+        }
+    }
+
+    /// 测试/左右相同的括弧
+    #[test]
+    fn test_scan_same_open_close() {
+        let brackets: BiFixMatchDictPair = bi_fix_match_dict_pair!(
+            "(" => ")"
+            "|" => "|"
+        );
+        let s = "|(|a|)|";
+        let roots = brackets.scan_brackets(s).unwrap();
+        asserts! {
+            roots.len() => 1,
+            roots[0].children[0].children[0].inner_str(s) => "a",
+        }
+    }
+
+    /// 测试/顶层拆分
+    #[test]
+    fn test_split_top_level() {
+        let brackets = narsese_brackets();
+        let scanner = BracketScanner::new(&brackets)
+            .with_quote("\"", "\"")
+            .with_escape('\\');
+        let s = r#"(*, {a, b}, "x,y\",z", c)"#;
+        // 先取出最外层括弧的内部，再在顶层拆分
+        let roots = scanner.scan(s).unwrap();
+        let inner = roots[0].inner();
+        let pieces = scanner.split_top_level(&s[inner.clone()], ",").unwrap();
+        asserts! {
+            pieces.iter().map(|piece| piece.text.trim()).collect::<Vec<_>>()
+                => vec!["*", "{a, b}", r#""x,y\",z""#, "c"],
+            pieces[1].span => 2..9,
+            &s[inner][pieces[3].span.clone()] => " c",
+        }
+        // 带所有权版本：结果一致
+        let owned = scanner.split_top_level_owned(s.to_string(), ",").unwrap();
+        let borrowed = scanner.split_top_level(s, ",").unwrap();
+        asserts! {
+            owned.len() => 1,
+            owned.iter().map(|piece| (piece.span.clone(), piece.text.as_str())).collect::<Vec<_>>()
+                => borrowed.iter().map(|piece| (piece.span.clone(), piece.text)).collect::<Vec<_>>(),
+        }
+        let owned = scanner
+            .split_top_level_owned("a, (b, c),, d".into(), ", ")
+            .unwrap();
+        asserts! {
+            owned.iter().map(|piece| piece.text.as_str()).collect::<Vec<_>>() => vec!["a", "(b, c),", "d"],
+            owned[2].span => 12..13,
+        }
+        // 边界情况
+        asserts! {
+            brackets.split_top_level("", ",").unwrap() => vec![TopLevelPiece { span: 0..0, text: "" }],
+            brackets.split_top_level(",", ",").unwrap().len() => 2,
+            brackets.split_top_level("{A, B}, [C, D]", ",").unwrap().len() => 2,
+        }
+        // 不平衡⇒报错
+        asserts! {
+            brackets.split_top_level("(a, b", ",") => @ Err(BracketScanError::Unclosed { .. }),
+            brackets.split_top_level("a, b]", ",") => @ Err(BracketScanError::UnexpectedClose { position: 4, .. }),
+            scanner.split_top_level(r#"a, "b, c"#, ",") => @ Err(BracketScanError::UnclosedQuote { open_position: 3, .. }),
+        }
+    }
+}
@@ -0,0 +1,568 @@
+//! 基于词缀字典的Narsese方言转换
+//! * 🎯在ASCII、LaTeX、漢文等Narsese格式间，逐词符改写系词、连接词、括弧与标点
+//! * 🚩以两张「按角色对齐」的[`DialectTable`]构造[`DialectTranslator`]，对输入作单趟流式改写
+//!   * 📌「对齐」：两边用相同的[`TokenKey`]（角色+名称）指代同一词符，如`(系词, "继承")`
+//!   * 📌源方言中有、目标方言中没有的词符，原样保留并报告为[`UnmappedToken`]
+//! * ⚠️仅在词法层面改写，不作语法分析：原子词项中恰好含有的词符也会被改写
+//!   * ✨可将真值、字符串等区段声明为「逐字括弧」，其中内容原样保留
+//!
+//! ## 示例
+//!
+//! ```rust
+//! use nar_dev_utils::{DialectTable, DialectTranslator, TokenRole};
+//! let ascii = DialectTable::new()
+//!     .with_token(TokenRole::Copula, "inheritance", "-->")
+//!     .with_bracket("statement", "<", ">")
+//!     .with_token(TokenRole::Punctuation, "judgement", ".");
+//! let han = DialectTable::new()
+//!     .with_token(TokenRole::Copula, "inheritance", "是")
+//!     .with_bracket("statement", "「", "」")
+//!     .with_token(TokenRole::Punctuation, "judgement", "。");
+//! let translator = DialectTranslator::new(&ascii, &han);
+//! let translated = translator.translate("<A-->B>.");
+//! assert_eq!(translated.text, "「A是B」。");
+//! assert!(translated.unmapped.is_empty());
+//! ```
+
+use super::{
+    BiFixMatchDictPair, PrefixMatch, PrefixMatchDictPair, PrefixTrieDictPair, StartsWithStr,
+};
+use std::{collections::VecDeque, fmt, ops::Range};
+
+/// 词符的角色
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum TokenRole {
+    /// 系词，如`-->`
+    Copula,
+    /// 连接词，如`&&`
+    Connector,
+    /// 括弧，如`<` `>`
+    Bracket,
+    /// 逐字括弧：括弧本身被改写，其中内容原样保留，如真值的`%` `%`
+    Verbatim,
+    /// 标点，如`.` `?`
+    Punctuation,
+    /// 分隔符，如`,`
+    Separator,
+    /// 其它
+    Other,
+}
+
+/// 词符的「角色+名称」
+/// * 🎯作为不同方言间对齐词符的依据
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TokenKey {
+    /// 角色
+    pub role: TokenRole,
+    /// 名称，如`"inheritance"`
+    pub name: String,
+}
+
+impl TokenKey {
+    /// 构造
+    pub fn new(role: TokenRole, name: impl Into<String>) -> Self {
+        Self {
+            role,
+            name: name.into(),
+        }
+    }
+}
+
+/// 方言表
+/// * 📌普通词符存于前缀字典，括弧对存于双向字典；关联内容均为[`TokenKey`]
+/// * 🚩与字典的插入语义一致：词符重复时，先加入的优先，后加入的被忽略
+/// * ⚠️空词符会被忽略，相当于「此方言中没有该词符」
+#[derive(Debug, Clone, Default)]
+pub struct DialectTable {
+    /// 词符⇒角色
+    tokens: PrefixMatchDictPair<TokenKey>,
+    /// 左括弧⇄右括弧，附带角色
+    brackets: BiFixMatchDictPair<TokenKey>,
+}
+
+impl DialectTable {
+    /// 构造空方言表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 添加普通词符
+    /// * 📌括弧请使用[`Self::with_bracket`]、[`Self::with_verbatim`]
+    pub fn with_token(
+        mut self,
+        role: TokenRole,
+        name: impl Into<String>,
+        token: impl Into<String>,
+    ) -> Self {
+        let token = token.into();
+        if !token.is_empty() {
+            self.tokens.insert((token, TokenKey::new(role, name)));
+        }
+        self
+    }
+
+    /// 添加括弧对
+    pub fn with_bracket(
+        self,
+        name: impl Into<String>,
+        open: impl Into<String>,
+        close: impl Into<String>,
+    ) -> Self {
+        self.with_bracket_role(TokenRole::Bracket, name, open, close)
+    }
+
+    /// 添加逐字括弧对
+    /// * 📌转换时其中内容原样保留，直到遇见对应的右括弧
+    pub fn with_verbatim(
+        self,
+        name: impl Into<String>,
+        open: impl Into<String>,
+        close: impl Into<String>,
+    ) -> Self {
+        self.with_bracket_role(TokenRole::Verbatim, name, open, close)
+    }
+
+    fn with_bracket_role(
+        mut self,
+        role: TokenRole,
+        name: impl Into<String>,
+        open: impl Into<String>,
+        close: impl Into<String>,
+    ) -> Self {
+        let (open, close) = (open.into(), close.into());
+        if !open.is_empty() && !close.is_empty() {
+            self.brackets
+                .insert((open, close, TokenKey::new(role, name)));
+        }
+        self
+    }
+
+    /// 查找指定角色的普通词符
+    pub fn token_of(&self, key: &TokenKey) -> Option<&str> {
+        self.tokens
+            .prefix_terms()
+            .find(|(_, k)| k == key)
+            .map(|(token, _)| token.as_str())
+    }
+
+    /// 查找指定角色的括弧对
+    pub fn bracket_of(&self, key: &TokenKey) -> Option<(&str, &str)> {
+        self.brackets
+            .prefix_terms()
+            .find(|(_, _, k)| k == key)
+            .map(|(open, close, _)| (open.as_str(), close.as_str()))
+    }
+}
+
+/// 转换规则：源方言中的一个词符
+#[derive(Debug, Clone)]
+struct Rule {
+    /// 词符的角色
+    key: TokenKey,
+    /// 目标方言中的词符（没有⇒原样保留并报告）
+    target: Option<String>,
+    /// 左括弧：源方言的右括弧，及其在目标方言中的词符
+    /// * 🎯转换时记下「期待的右括弧」：左右括弧相同（如`|`…`|`）时也能正确转换
+    close: Option<(String, Option<String>)>,
+}
+
+/// 无法转换的词符
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnmappedToken {
+    /// 词符的角色
+    pub key: TokenKey,
+    /// 源方言中的词符
+    pub token: String,
+    /// 在输入中的字节范围
+    /// * 🎯可直接用于[`Diagnostic`](crate::Diagnostic)等位置展示
+    pub range: Range<usize>,
+}
+
+/// 转换结果
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Translation {
+    /// 转换后的文本
+    pub text: String,
+    /// 无法转换、被原样保留的词符
+    pub unmapped: Vec<UnmappedToken>,
+}
+
+/// 方言转换器
+/// * 🚩构造时将两张方言表编译为一棵「源词符⇒转换规则」的字典树
+/// * 📌转换时从左到右单趟流式扫描，每个位置取最长匹配的源词符
+///   * 📌复杂度：`O(输入长度×最长词符长度)`，与规则数量无关
+///   * 📌只预读「判定词符」所需的字符：可直接转换字符迭代器，如`BufferIterator`
+///   * 📌与[`BracketScanner`](super::BracketScanner)相同，以栈记录「期待的右括弧」，优先于字典中的规则
+#[derive(Debug, Clone, Default)]
+pub struct DialectTranslator {
+    rules: PrefixTrieDictPair<Rule>,
+}
+
+/// 【内部】转换时的「预读字符」
+/// * 🚩从字符迭代器中按需拉取，并记录「已消耗」的字节位置
+struct CharLookahead<I> {
+    /// 字符来源
+    chars: I,
+    /// 已预读、尚未消耗的字符
+    buffer: VecDeque<char>,
+    /// 缓冲区开头在输入中的字节位置
+    position: usize,
+}
+
+impl<I: Iterator<Item = char>> CharLookahead<I> {
+    /// 预读到缓冲区至少有`n`个字符
+    /// * 📌来源耗尽时提前停止
+    fn fill_to(&mut self, n: usize) {
+        while self.buffer.len() < n {
+            match self.chars.next() {
+                Some(c) => self.buffer.push_back(c),
+                None => break,
+            }
+        }
+    }
+
+    /// 逐个预读，直到「更多的字符」不会改变词符的匹配结果
+    fn fill_for(&mut self, rules: &PrefixTrieDictPair<Rule>) {
+        self.fill_to(1);
+        while rules.prefix_needs_more_chars(&self.buffer) {
+            match self.chars.next() {
+                Some(c) => self.buffer.push_back(c),
+                None => break,
+            }
+        }
+    }
+
+    /// 判断接下来是否为指定的词符
+    fn starts_with(&mut self, token: &str) -> bool {
+        self.fill_to(token.chars().count());
+        self.buffer.starts_with_str(token)
+    }
+
+    /// 消耗`n_chars`个（已预读的）字符，返回其字节范围
+    fn consume(&mut self, n_chars: usize) -> Range<usize> {
+        let start = self.position;
+        self.position += self
+            .buffer
+            .drain(..n_chars)
+            .map(char::len_utf8)
+            .sum::<usize>();
+        start..self.position
+    }
+
+    /// 原样写入一个字符
+    /// * 📌来源耗尽⇒返回`false`
+    fn pass(&mut self, out: &mut impl fmt::Write) -> Result<bool, fmt::Error> {
+        self.fill_to(1);
+        let Some(c) = self.buffer.pop_front() else {
+            return Ok(false);
+        };
+        self.position += c.len_utf8();
+        out.write_char(c)?;
+        Ok(true)
+    }
+}
+
+impl DialectTranslator {
+    /// 从「源方言」与「目标方言」构造
+    /// * ⚠️源方言中的括弧与普通词符重复时，括弧优先
+    pub fn new(from: &DialectTable, to: &DialectTable) -> Self {
+        let mut rules = PrefixTrieDictPair::default();
+        for (open, close, key) in from.brackets.prefix_terms() {
+            let target = to.bracket_of(key);
+            let target_open = target.map(|(open, _)| open.to_owned());
+            let target_close = target.map(|(_, close)| close.to_owned());
+            rules.insert((
+                open.clone(),
+                Rule {
+                    key: key.clone(),
+                    target: target_open,
+                    close: Some((close.clone(), target_close.clone())),
+                },
+            ));
+            // * 📌左右括弧相同⇒不会插入：由「期待的右括弧」处理
+            rules.insert((
+                close.clone(),
+                Rule {
+                    key: key.clone(),
+                    target: target_close,
+                    close: None,
+                },
+            ));
+        }
+        for (token, key) in from.tokens.prefix_terms() {
+            rules.insert((
+                token.clone(),
+                Rule {
+                    key: key.clone(),
+                    target: to.token_of(key).map(str::to_owned),
+                    close: None,
+                },
+            ));
+        }
+        Self { rules }
+    }
+
+    /// 转换文本，并写入任意[`fmt::Write`]目标
+    /// * 📄参见[`Self::translate_chars_to`]
+    pub fn translate_to(
+        &self,
+        out: &mut impl fmt::Write,
+        input: &str,
+    ) -> Result<Vec<UnmappedToken>, fmt::Error> {
+        self.translate_chars_to(out, input.chars())
+    }
+
+    /// 转换字符流，并写入任意[`fmt::Write`]目标
+    /// * 🚩单趟流式扫描：未匹配的字符原样写入，匹配到的词符逐个改写
+    ///   * 📌只预读「判定词符」所需的字符
+    /// * 📌逐字括弧未闭合时，其后的内容全部原样保留
+    /// * 📌返回无法转换的词符
+    ///   * 📌其位置为「按UTF-8编码」的字节范围，与对应字符串中的位置一致
+    pub fn translate_chars_to(
+        &self,
+        out: &mut impl fmt::Write,
+        input: impl IntoIterator<Item = char>,
+    ) -> Result<Vec<UnmappedToken>, fmt::Error> {
+        let mut unmapped = vec![];
+        let mut input = CharLookahead {
+            chars: input.into_iter(),
+            buffer: VecDeque::new(),
+            position: 0,
+        };
+        // 期待的右括弧 | `(规则, 源方言的右括弧, 目标方言的右括弧)`
+        let mut expected_closes: Vec<(&Rule, &String, &Option<String>)> = vec![];
+        loop {
+            input.fill_for(&self.rules);
+            let matched = self.rules.match_prefix_chars_longest(&input.buffer);
+            // 期待的右括弧⇒优先（除非有更长的词符）
+            let expected = expected_closes.last().copied().filter(|(_, close, _)| {
+                input.starts_with(close) && matched.as_ref().is_none_or(|m| m.len <= close.len())
+            });
+            // * 📌`opened`：新打开的括弧
+            let (key, token, target, opened) = match (expected, matched) {
+                (Some((rule, close, target_close)), _) => {
+                    expected_closes.pop();
+                    (&rule.key, close, target_close, None)
+                }
+                (None, Some(m)) => {
+                    let (token, rule) = m.term;
+                    (&rule.key, token, &rule.target, Some(rule))
+                }
+                // 无词符⇒原样写入一个字符
+                (None, None) => match input.pass(out)? {
+                    true => continue,
+                    false => break,
+                },
+            };
+            let range = input.consume(token.chars().count());
+            Self::write_token(out, &mut unmapped, key, token, target, range)?;
+            match opened.and_then(|rule| Some((rule, rule.close.as_ref()?))) {
+                // 逐字括弧：原样写入直到右括弧
+                Some((rule, (close, target_close))) if rule.key.role == TokenRole::Verbatim => {
+                    while !input.starts_with(close) && input.pass(out)? {}
+                    // * 📌来源耗尽⇒未闭合，无需处理
+                    if input.starts_with(close) {
+                        let range = input.consume(close.chars().count());
+                        Self::write_token(
+                            out,
+                            &mut unmapped,
+                            &rule.key,
+                            close,
+                            target_close,
+                            range,
+                        )?;
+                    }
+                }
+                // 普通括弧：记下期待的右括弧
+                Some((rule, (close, target_close))) => {
+                    expected_closes.push((rule, close, target_close))
+                }
+                None => {}
+            }
+        }
+        Ok(unmapped)
+    }
+
+    /// 转换文本到新字串
+    pub fn translate(&self, input: &str) -> Translation {
+        let mut text = String::with_capacity(input.len());
+        let unmapped = self
+            .translate_to(&mut text, input)
+            .expect("向字串写入不会失败");
+        Translation { text, unmapped }
+    }
+
+    /// 写入一个词符：有目标词符⇒写入目标词符；否则原样写入并记录
+    fn write_token(
+        out: &mut impl fmt::Write,
+        unmapped: &mut Vec<UnmappedToken>,
+        key: &TokenKey,
+        token: &str,
+        target: &Option<String>,
+        range: Range<usize>,
+    ) -> fmt::Result {
+        match target {
+            Some(target) => out.write_str(target),
+            None => {
+                unmapped.push(UnmappedToken {
+                    key: key.clone(),
+                    token: token.to_owned(),
+                    range,
+                });
+                out.write_str(token)
+            }
+        }
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asserts;
+    use TokenRole::*;
+
+    /// ASCII方言（节选）
+    fn ascii() -> DialectTable {
+        DialectTable::new()
+            .with_token(Copula, "inheritance", "-->")
+            .with_token(Copula, "implication", "==>")
+            .with_token(Copula, "instance", "{--")
+            .with_token(Connector, "conjunction", "&&")
+            .with_token(Connector, "product", "*")
+            .with_token(Separator, "separator", ",")
+            .with_token(Punctuation, "judgement", ".")
+            .with_token(Punctuation, "question", "?")
+            .with_bracket("compound", "(", ")")
+            .with_bracket("statement", "<", ">")
+            .with_bracket("ext_set", "{", "}")
+            .with_verbatim("truth", "%", "%")
+    }
+
+    /// 漢文方言（节选）
+    fn han() -> DialectTable {
+        DialectTable::new()
+            .with_token(Copula, "inheritance", "是")
+            .with_token(Copula, "implication", "得")
+            .with_token(Connector, "conjunction", "与")
+            .with_token(Connector, "product", "积")
+            .with_token(Separator, "separator", "，")
+            .with_token(Punctuation, "judgement", "。")
+            .with_token(Punctuation, "question", "？")
+            .with_bracket("compound", "（", "）")
+            .with_bracket("statement", "「", "」")
+            .with_bracket("ext_set", "『", "』")
+            .with_verbatim("truth", "真值【", "】")
+    }
+
+    #[test]
+    fn test_translate() {
+        let to_han = DialectTranslator::new(&ascii(), &han());
+        let to_ascii = DialectTranslator::new(&han(), &ascii());
+        asserts! {
+            // 系词、连接词、括弧、标点
+            to_han.translate("<(&&,A,B)==><C-->D>>.").text => "「（与，A，B）得「C是D」」。",
+            // 逐字括弧：其中的`.`、`,`不被改写
+            to_han.translate("<A-->B>. %1.0;0.9%").text => "「A是B」。 真值【1.0;0.9】",
+            // 反向转换
+            to_ascii.translate("「（积，A，B）是C」？").text => "<(*,A,B)-->C>?",
+            to_ascii.translate("「A是B」。 真值【1.0;0.9】").text => "<A-->B>. %1.0;0.9%",
+            // 无词符⇒原样
+            to_han.translate("plain").text => "plain",
+            to_han.translate("").text => "",
+            // 逐字括弧未闭合⇒其后原样保留
+            to_han.translate("%1.0;").text => "真值【1.0;",
+        }
+    }
+
+    #[test]
+    fn test_unmapped() {
+        let to_han = DialectTranslator::new(&ascii(), &han());
+        // `{--`在漢文方言中没有对应词符；优先于`{`匹配
+        let translated = to_han.translate("<{A}{--B>.");
+        asserts! {
+            translated.text => "「『A』{--B」。",
+            translated.unmapped => [UnmappedToken {
+                key: TokenKey::new(Copula, "instance"),
+                token: "{--".into(),
+                range: 4..7,
+            }],
+        }
+        // 写入到任意目标
+        let mut out = String::from(">");
+        let unmapped = to_han.translate_to(&mut out, "A-->B").unwrap();
+        asserts! {
+            out => ">A是B",
+            unmapped.is_empty(),
+        }
+    }
+
+    #[test]
+    fn test_translate_chars() {
+        let to_han = DialectTranslator::new(&ascii(), &han());
+        let to_ascii = DialectTranslator::new(&han(), &ascii());
+        // 字符流：无需先收集为字符串
+        let mut out = String::new();
+        let source = ["「A是", "B」", "。"].into_iter().flat_map(str::chars);
+        let unmapped = to_ascii.translate_chars_to(&mut out, source).unwrap();
+        asserts! {
+            out => "<A-->B>.",
+            unmapped.is_empty(),
+        }
+        // 位置按UTF-8字节计算
+        let mut out = String::new();
+        let unmapped = to_han
+            .translate_chars_to(&mut out, "中文{--B".chars())
+            .unwrap();
+        asserts! {
+            out => "中文{--B",
+            unmapped[0].range => 6..9,
+        }
+        // 缓冲迭代器
+        #[cfg(feature = "iterators")]
+        {
+            let mut out = String::new();
+            let source = crate::BufferIterator::new("<A-->B>. %1.0;0.9%".chars());
+            to_han.translate_chars_to(&mut out, source).unwrap();
+            asserts! {
+                out => "「A是B」。 真值【1.0;0.9】",
+            }
+        }
+    }
+
+    #[test]
+    fn test_same_open_close() {
+        let from = DialectTable::new()
+            .with_bracket("abs", "|", "|")
+            .with_bracket("compound", "(", ")")
+            .with_token(Connector, "or", "||");
+        let to = DialectTable::new()
+            .with_bracket("abs", "⌈", "⌉")
+            .with_bracket("compound", "（", "）")
+            .with_token(Connector, "or", "或");
+        let translator = DialectTranslator::new(&from, &to);
+        asserts! {
+            translator.translate("|A|").text => "⌈A⌉",
+            translator.translate("(|A|,|(B)|)").text => "（⌈A⌉,⌈（B）⌉）",
+            // 更长的词符优先
+            translator.translate("|A||B|").text => "⌈A或B⌉",
+            // 多余的右括弧⇒按字典中的规则
+            translator.translate("A)").text => "A）",
+        }
+    }
+
+    #[test]
+    fn test_table() {
+        let table = ascii()
+            .with_token(Copula, "inheritance2", "-->") // 重复⇒忽略
+            .with_token(Other, "empty", ""); // 空词符⇒忽略
+        asserts! {
+            table.token_of(&TokenKey::new(Copula, "inheritance")) => Some("-->"),
+            table.token_of(&TokenKey::new(Copula, "inheritance2")) => None,
+            table.token_of(&TokenKey::new(Other, "empty")) => None,
+            table.bracket_of(&TokenKey::new(Bracket, "statement")) => Some(("<", ">")),
+            table.bracket_of(&TokenKey::new(Verbatim, "truth")) => Some(("%", "%")),
+            table.bracket_of(&TokenKey::new(Bracket, "truth")) => None, // 角色不同
+        }
+    }
+}
@@ -0,0 +1,484 @@
+//! 词缀匹配字典的「文本格式」
+//! * 🎯让Narsese方言可以定义在用户可编辑的纯文本文件中，在运行时加载
+//! * 📌格式（无外部依赖）
+//!   * 每行一个条目，字段之间以空白分隔
+//!   * 字段可以是「裸词」或「双引号字符串」
+//!     * 📌双引号字符串支持转义：`\"` `\\` `\n` `\t` `\r`
+//!     * 📌空字串、含空白等特殊字符的字段须用双引号
+//!   * `#`之后（引号外）为注释；空行会被忽略
+//! * 📌各字典的字段
+//!   * [`XFixMatchDict`]：`词缀`
+//!   * [`PrefixMatchDictPair<String>`]：`前缀 关联名称`
+//!   * [`SuffixMatchDictPair<String>`]：`关联名称 后缀`
+//!   * [`BiFixMatchDictPair`]：`前缀 后缀`
+//!   * [`BiFixMatchDictPair<String>`]：`前缀 后缀 关联名称`
+//! * 📌保存后再加载，能得到相同的字典
+//!
+//! ## 示例
+//!
+//! ```plaintext
+//! # 陈述系词
+//! "-->"   inheritance
+//! "<->"   similarity
+//! ""      empty  # 空前缀
+//! ```
+
+use super::{
+    BiFixMatchDictPair, PrefixMatch, PrefixMatchDictPair, SuffixMatchDictPair, XFixMatchDict,
+};
+use std::{
+    borrow::Cow,
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// 文本格式的解析错误
+/// * 📌总是携带行号（从`1`开始）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DictTextError {
+    /// 出错的行号 | 从`1`开始
+    pub line: usize,
+    /// 错误的种类
+    pub kind: DictTextErrorKind,
+}
+
+/// 文本格式的解析错误种类
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DictTextErrorKind {
+    /// 双引号字符串未闭合
+    UnclosedQuote,
+    /// 未知的转义序列
+    InvalidEscape(char),
+    /// 字段后紧跟非空白字符，如`"a"b`
+    MissingSeparator,
+    /// 字段个数不对
+    WrongFieldCount {
+        /// 期望的字段个数
+        expected: usize,
+        /// 实际的字段个数
+        found: usize,
+    },
+    /// 重复的词缀
+    Duplicate(String),
+}
+
+impl Display for DictTextError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "第{}行：", self.line)?;
+        match &self.kind {
+            DictTextErrorKind::UnclosedQuote => write!(f, "双引号字符串未闭合"),
+            DictTextErrorKind::InvalidEscape(c) => write!(f, "未知的转义序列\\{c}"),
+            DictTextErrorKind::MissingSeparator => write!(f, "字段之间缺少空白"),
+            DictTextErrorKind::WrongFieldCount { expected, found } => {
+                write!(f, "期望{expected}个字段，实际有{found}个")
+            }
+            DictTextErrorKind::Duplicate(x_fix) => write!(f, "重复的词缀{x_fix:?}"),
+        }
+    }
+}
+
+impl Error for DictTextError {}
+
+/// 将一行拆分为字段
+/// * 📌空行、纯注释行⇒空数组
+fn parse_line(line: &str, line_no: usize) -> Result<Vec<String>, DictTextError> {
+    let error = |kind| DictTextError {
+        line: line_no,
+        kind,
+    };
+    let mut fields = vec![];
+    let mut chars = line.chars().peekable();
+    loop {
+        // 跳过空白
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        match chars.peek() {
+            // 行尾或注释⇒结束
+            None | Some('#') => break,
+            // 双引号字符串
+            Some('"') => {
+                chars.next();
+                let mut field = String::new();
+                loop {
+                    match chars.next() {
+                        None => return Err(error(DictTextErrorKind::UnclosedQuote)),
+                        Some('"') => break,
+                        Some('\\') => field.push(match chars.next() {
+                            Some('"') => '"',
+                            Some('\\') => '\\',
+                            Some('n') => '\n',
+                            Some('t') => '\t',
+                            Some('r') => '\r',
+                            Some(c) => return Err(error(DictTextErrorKind::InvalidEscape(c))),
+                            None => return Err(error(DictTextErrorKind::UnclosedQuote)),
+                        }),
+                        Some(c) => field.push(c),
+                    }
+                }
+                // 字段之后须为空白、注释或行尾
+                if chars
+                    .peek()
+                    .is_some_and(|c| !c.is_whitespace() && *c != '#')
+                {
+                    return Err(error(DictTextErrorKind::MissingSeparator));
+                }
+                fields.push(field);
+            }
+            // 裸词
+            Some(..) => {
+                let mut field = String::new();
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != '#') {
+                    field.push(c);
+                }
+                fields.push(field);
+            }
+        }
+    }
+    Ok(fields)
+}
+
+/// 将文本拆分为条目
+/// * 🚩检查每个条目的字段个数
+/// * 📌格式：`(行号, 字段)`
+fn parse_entries(text: &str, n_fields: usize) -> Result<Vec<(usize, Vec<String>)>, DictTextError> {
+    let mut entries = vec![];
+    for (i, line) in text.lines().enumerate() {
+        let line_no = i + 1;
+        let fields = parse_line(line, line_no)?;
+        match fields.len() {
+            0 => {}
+            n if n == n_fields => entries.push((line_no, fields)),
+            found => {
+                return Err(DictTextError {
+                    line: line_no,
+                    kind: DictTextErrorKind::WrongFieldCount {
+                        expected: n_fields,
+                        found,
+                    },
+                })
+            }
+        }
+    }
+    Ok(entries)
+}
+
+/// 格式化字段
+/// * 🚩仅在必要时加上双引号
+fn format_field(field: &str) -> Cow<'_, str> {
+    let is_bare = !field.is_empty()
+        && !field.starts_with('"')
+        && !field.chars().any(|c| c.is_whitespace() || c == '#');
+    if is_bare {
+        return Cow::Borrowed(field);
+    }
+    let mut quoted = String::with_capacity(field.len() + 2);
+    quoted.push('"');
+    for c in field.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            '\r' => quoted.push_str("\\r"),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    Cow::Owned(quoted)
+}
+
+/// 格式化条目
+fn format_entries<'a, const N: usize>(entries: impl Iterator<Item = [&'a str; N]>) -> String {
+    entries
+        .map(|fields| {
+            let fields = fields.map(format_field);
+            format!("{}\n", fields.join(" "))
+        })
+        .collect()
+}
+
+/// 构造「重复词缀」错误
+fn duplicate(line: usize, x_fix: String) -> DictTextError {
+    DictTextError {
+        line,
+        kind: DictTextErrorKind::Duplicate(x_fix),
+    }
+}
+
+impl XFixMatchDict {
+    /// 从文本格式加载
+    /// * 📌每行一个词缀
+    pub fn from_text(text: &str) -> Result<Self, DictTextError> {
+        let mut dict = Self::default();
+        for (line, mut fields) in parse_entries(text, 1)? {
+            let x_fix = fields.pop().unwrap();
+            if dict.has(&x_fix) {
+                return Err(duplicate(line, x_fix));
+            }
+            dict.insert(x_fix);
+        }
+        Ok(dict)
+    }
+
+    /// 保存为文本格式
+    pub fn to_text(&self) -> String {
+        self.iter_x_fixes()
+            .map(|x_fix| format!("{}\n", format_field(x_fix)))
+            .collect()
+    }
+}
+
+impl PrefixMatchDictPair<String> {
+    /// 从文本格式加载
+    /// * 📌每行`前缀 关联名称`
+    pub fn from_text(text: &str) -> Result<Self, DictTextError> {
+        let mut dict = Self::default();
+        for (line, fields) in parse_entries(text, 2)? {
+            let [prefix, name]: [String; 2] = fields.try_into().unwrap();
+            if dict.has(&prefix) {
+                return Err(duplicate(line, prefix));
+            }
+            dict.insert((prefix, name));
+        }
+        Ok(dict)
+    }
+
+    /// 保存为文本格式
+    pub fn to_text(&self) -> String {
+        format_entries(
+            self.iter_terms()
+                .map(|(prefix, name)| [prefix.as_str(), name.as_str()]),
+        )
+    }
+}
+
+impl SuffixMatchDictPair<String> {
+    /// 从文本格式加载
+    /// * 📌每行`关联名称 后缀`
+    pub fn from_text(text: &str) -> Result<Self, DictTextError> {
+        let mut dict = Self::default();
+        for (line, fields) in parse_entries(text, 2)? {
+            let [name, suffix]: [String; 2] = fields.try_into().unwrap();
+            if dict.has(&suffix) {
+                return Err(duplicate(line, suffix));
+            }
+            dict.insert((name, suffix));
+        }
+        Ok(dict)
+    }
+
+    /// 保存为文本格式
+    pub fn to_text(&self) -> String {
+        format_entries(
+            self.iter_terms()
+                .map(|(name, suffix)| [name.as_str(), suffix.as_str()]),
+        )
+    }
+}
+
+/// 从文本格式加载「双向配对字典」
+/// * 🚩每个条目的前两个字段为`前缀 后缀`，其余字段交由`associated`转换为关联内容
+/// * 📌前后缀有一个重复就插入不了⇒报错
+fn bi_fix_from_text<T>(
+    text: &str,
+    n_fields: usize,
+    associated: impl Fn(std::vec::IntoIter<String>) -> T,
+) -> Result<BiFixMatchDictPair<T>, DictTextError> {
+    let mut dict = BiFixMatchDictPair::default();
+    for (line, fields) in parse_entries(text, n_fields)? {
+        let mut fields = fields.into_iter();
+        let (prefix, suffix) = (fields.next().unwrap(), fields.next().unwrap());
+        let duplicated = match dict.search_prefix(&prefix).is_ok() {
+            true => Some(prefix.clone()),
+            false => dict.search_suffix(&suffix).ok().map(|_| suffix.clone()),
+        };
+        if let Some(x_fix) = duplicated {
+            return Err(duplicate(line, x_fix));
+        }
+        dict.insert((prefix, suffix, associated(fields)));
+    }
+    Ok(dict)
+}
+
+impl BiFixMatchDictPair {
+    /// 从文本格式加载
+    /// * 📌每行`前缀 后缀`
+    /// * 📌带「关联名称」的字典，参见[`BiFixMatchDictPair::from_text_with_names`]
+    /// * ⚠️与[`Self::new`]不同，不会自动加入「空括弧」`("", "")`
+    ///   * 📌需要的话，在文本中写上`"" ""`
+    pub fn from_text(text: &str) -> Result<Self, DictTextError> {
+        bi_fix_from_text(text, 2, |_| ())
+    }
+
+    /// 保存为文本格式
+    pub fn to_text(&self) -> String {
+        format_entries(
+            self.prefix_terms()
+                .map(|(prefix, suffix, _)| [prefix.as_str(), suffix.as_str()]),
+        )
+    }
+}
+
+impl BiFixMatchDictPair<String> {
+    /// 从文本格式加载带「关联名称」的字典
+    /// * 📌每行`前缀 后缀 关联名称`
+    /// * ⚠️不与[`BiFixMatchDictPair::from_text`]同名：否则`BiFixMatchDictPair::from_text`无法推断类型
+    ///   * 📌亦可使用`text.parse::<BiFixMatchDictPair<String>>()`
+    pub fn from_text_with_names(text: &str) -> Result<Self, DictTextError> {
+        bi_fix_from_text(text, 3, |mut fields| fields.next().unwrap())
+    }
+
+    /// 保存为带「关联名称」的文本格式
+    pub fn to_text_with_names(&self) -> String {
+        format_entries(
+            self.prefix_terms()
+                .map(|(prefix, suffix, name)| [prefix.as_str(), suffix.as_str(), name.as_str()]),
+        )
+    }
+}
+
+impl FromStr for BiFixMatchDictPair<String> {
+    type Err = DictTextError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_text_with_names(s)
+    }
+}
+
+/// 批量实现[`FromStr`]
+/// * 🎯支持`text.parse::<XFixMatchDict>()`
+macro_rules! impl_from_str {
+    ($($t:ty)*) => {
+        $(
+            impl FromStr for $t {
+                type Err = DictTextError;
+                fn from_str(s: &str) -> Result<Self, Self::Err> {
+                    Self::from_text(s)
+                }
+            }
+        )*
+    };
+}
+impl_from_str! {
+    XFixMatchDict
+    PrefixMatchDictPair<String>
+    SuffixMatchDictPair<String>
+    BiFixMatchDictPair
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{asserts, SuffixMatch};
+
+    /// 测试/字段解析
+    #[test]
+    fn test_parse_line() {
+        asserts! {
+            parse_line("", 1) => Ok(vec![]),
+            parse_line("  # 注释", 1) => Ok(vec![]),
+            parse_line(r#"--> inheritance # 继承"#, 1) => Ok(vec!["-->".into(), "inheritance".into()]),
+            parse_line(r#""" "a b" "\"\\\n""#, 1) => Ok(vec!["".into(), "a b".into(), "\"\\\n".into()]),
+            parse_line(r##""#""##, 1) => Ok(vec!["#".into()]),
+            parse_line(r#""abc"#, 3) => Err(DictTextError { line: 3, kind: DictTextErrorKind::UnclosedQuote }),
+            parse_line(r#""\q""#, 4) => Err(DictTextError { line: 4, kind: DictTextErrorKind::InvalidEscape('q') }),
+            parse_line(r#""a"b"#, 5) => Err(DictTextError { line: 5, kind: DictTextErrorKind::MissingSeparator }),
+        }
+    }
+
+    /// 测试/加载与保存
+    #[test]
+    fn test_round_trip() {
+        let text = r##"
+# 陈述系词
+"-->"   inheritance
+"<->"   similarity
+""      "空 前缀"  # 空前缀
+"\"#"   quoted
+"##;
+        let dict: PrefixMatchDictPair<String> = text.parse().unwrap();
+        asserts! {
+            dict.match_prefix("-->A").map(|(_, name)| name.as_str()) => Some("inheritance"),
+            dict.match_prefix("A").map(|(_, name)| name.as_str()) => Some("空 前缀"),
+            dict.match_prefix("\"#A").map(|(_, name)| name.as_str()) => Some("quoted"),
+        }
+        // 保存再加载
+        let saved = dict.to_text();
+        let reloaded = PrefixMatchDictPair::<String>::from_text(&saved).unwrap();
+        asserts! {
+            reloaded.iter_terms().collect::<Vec<_>>() => dict.iter_terms().collect::<Vec<_>>(),
+            reloaded.to_text() => saved,
+        }
+        // 其它字典
+        let x_fixes = XFixMatchDict::from_text("--> \n <-> \n \"\"").unwrap();
+        let suffixes =
+            SuffixMatchDictPair::<String>::from_text("present \":|:\"\npast :\\:").unwrap();
+        let brackets = BiFixMatchDictPair::from_text("( )\n\"\\\\left(\" \"\\\\right)\"").unwrap();
+        asserts! {
+            XFixMatchDict::from_text(&x_fixes.to_text()).unwrap().to_text() => x_fixes.to_text(),
+            x_fixes.match_prefix("A") => Some(&String::new()),
+            suffixes.match_suffix("A. :|:").map(|(name, _)| name.as_str()) => Some("present"),
+            SuffixMatchDictPair::<String>::from_text(&suffixes.to_text()).unwrap().to_text() => suffixes.to_text(),
+            brackets.match_prefix(r"\left(A\right)").map(|(_, r, _)| r.as_str()) => Some(r"\right)"),
+            brackets.match_prefix("A") => None, // 不会自动加入空括弧
+            BiFixMatchDictPair::from_text(&brackets.to_text()).unwrap().to_text() => brackets.to_text(),
+        }
+    }
+
+    /// 测试/带关联名称的双向配对字典
+    #[test]
+    fn test_bi_fix_with_names() {
+        let text = r#"
+# 复合词项
+"("      ")"       compound
+"{"      "}"       ext_set
+"\\left(" "\\right)" "latex compound"
+"#;
+        let dict: BiFixMatchDictPair<String> = text.parse().unwrap();
+        asserts! {
+            dict.match_prefix("{A}").map(|(_, _, name)| name.as_str()) => Some("ext_set"),
+            dict.match_suffix(r"A\right)").map(|(_, _, name)| name.as_str()) => Some("latex compound"),
+            dict.match_prefix("A") => None,
+        }
+        // 保存再加载
+        let saved = dict.to_text_with_names();
+        let reloaded = BiFixMatchDictPair::from_text_with_names(&saved).unwrap();
+        asserts! {
+            reloaded.prefix_terms().collect::<Vec<_>>() => dict.prefix_terms().collect::<Vec<_>>(),
+            reloaded.to_text_with_names() => saved,
+        }
+        // 错误
+        asserts! {
+            BiFixMatchDictPair::from_text_with_names("( ) compound\n[ ]") => @ Err(DictTextError {
+                line: 2,
+                kind: DictTextErrorKind::WrongFieldCount { expected: 3, found: 2 },
+            }),
+            BiFixMatchDictPair::from_text_with_names("( ) a\n[ ) b") => @ Err(DictTextError {
+                line: 2,
+                kind: DictTextErrorKind::Duplicate(..),
+            }),
+        }
+    }
+
+    /// 测试/错误与行号
+    #[test]
+    fn test_error() {
+        asserts! {
+            XFixMatchDict::from_text("a\nb c") => @ Err(DictTextError {
+                line: 2,
+                kind: DictTextErrorKind::WrongFieldCount { expected: 1, found: 2 },
+            }),
+            PrefixMatchDictPair::<String>::from_text("# 注释\n( a\n( b") => @ Err(DictTextError {
+                line: 3,
+                kind: DictTextErrorKind::Duplicate(..),
+            }),
+            BiFixMatchDictPair::from_text("( )\n[ )") => @ Err(DictTextError {
+                line: 2,
+                kind: DictTextErrorKind::Duplicate(..),
+            }),
+        }
+        asserts! {
+            XFixMatchDict::from_text("\n\n\"a").unwrap_err().to_string() => "第3行：双引号字符串未闭合",
+        }
+    }
+}
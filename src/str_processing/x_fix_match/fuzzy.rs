@@ -0,0 +1,183 @@
+//! 基于编辑距离的模糊查找
+//! * 🎯为「未识别的词缀」（如输错的系词`-=>`、`==>>`）提供「您是不是想输入……」的建议
+//! * 🚩在字典的所有词缀中，找出与输入编辑距离最近、且不超过上限的若干个
+//! * 📌编辑距离均按**字符**计算
+
+/// 编辑距离的度量方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum EditMetric {
+    /// Levenshtein距离：插入、删除、替换
+    Levenshtein,
+    /// Damerau-Levenshtein距离（受限版本，即「最优字符串对齐」）：在前者基础上允许「交换相邻字符」
+    /// * 📄`<->`与`<-->`距离为1，`-->`与`->-`距离为1
+    /// * 📌默认度量：更贴近常见的手误
+    #[default]
+    Damerau,
+}
+
+/// 计算两个字串间的编辑距离，超过上限时提前终止
+/// * 🚩逐行动态规划；某行的最小值超过上限时，后续只会更大⇒直接返回[`None`]
+/// * ⚡两者长度之差超过上限时无需计算
+pub fn edit_distance(a: &str, b: &str, metric: EditMetric, max_distance: usize) -> Option<usize> {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    if a.len().abs_diff(b.len()) > max_distance {
+        return None;
+    }
+    // 三行滚动：上上行、上一行、当前行
+    let mut before_previous = vec![0; b.len() + 1];
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    let mut current = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        current[0] = i;
+        let mut row_min = current[0];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            // 删除、插入、替换
+            let mut distance = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+            // 交换相邻字符
+            if metric == EditMetric::Damerau
+                && i > 1
+                && j > 1
+                && a[i - 1] == b[j - 2]
+                && a[i - 2] == b[j - 1]
+            {
+                distance = distance.min(before_previous[j - 2] + 1);
+            }
+            current[j] = distance;
+            row_min = row_min.min(distance);
+        }
+        if row_min > max_distance {
+            return None;
+        }
+        std::mem::swap(&mut before_previous, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+    Some(previous[b.len()]).filter(|&distance| distance <= max_distance)
+}
+
+/// 计算Levenshtein距离
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    // * 🚩无上限⇒必定有结果
+    edit_distance(a, b, EditMetric::Levenshtein, usize::MAX).unwrap()
+}
+
+/// 计算（受限的）Damerau-Levenshtein距离
+pub fn damerau_levenshtein(a: &str, b: &str) -> usize {
+    // * 🚩无上限⇒必定有结果
+    edit_distance(a, b, EditMetric::Damerau, usize::MAX).unwrap()
+}
+
+/// 模糊查找的一条建议
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Suggestion<'a, Term> {
+    /// 建议的条目
+    pub term: &'a Term,
+    /// 条目的词缀
+    pub x_fix: &'a str,
+    /// 与输入的编辑距离
+    pub distance: usize,
+}
+
+/// 在一系列条目中查找与输入最接近的至多`k`个
+/// * 📌仅保留编辑距离不超过`max_distance`的条目
+/// * 📌按「距离升序、词缀字典序」排列，结果稳定
+/// * ⚠️空词缀不参与建议：其「距离」只是输入的长度，没有参考价值
+/// * 🎯供[`PrefixMatch::suggest_prefix`](super::PrefixMatch::suggest_prefix)等方法统一调用
+pub fn suggest<'a, Term: 'a>(
+    terms: impl Iterator<Item = &'a Term>,
+    get_x_fix: impl Fn(&'a Term) -> &'a str,
+    input: &str,
+    metric: EditMetric,
+    max_distance: usize,
+    k: usize,
+) -> Vec<Suggestion<'a, Term>> {
+    let mut suggestions = terms
+        .filter_map(|term| {
+            let x_fix = get_x_fix(term);
+            if x_fix.is_empty() {
+                return None;
+            }
+            let distance = edit_distance(input, x_fix, metric, max_distance)?;
+            Some(Suggestion {
+                term,
+                x_fix,
+                distance,
+            })
+        })
+        .collect::<Vec<_>>();
+    suggestions.sort_by(|a, b| a.distance.cmp(&b.distance).then(a.x_fix.cmp(b.x_fix)));
+    suggestions.truncate(k);
+    suggestions
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        asserts, bi_fix_match_dict_pair, x_fix_match_dict, PrefixMatch, PrefixMatchDict,
+        PrefixMatchDictPair, SuffixMatch, XFixMatchDict,
+    };
+
+    #[test]
+    fn test_edit_distance() {
+        use EditMetric::*;
+        asserts! {
+            levenshtein("", "") => 0,
+            levenshtein("kitten", "sitting") => 3,
+            levenshtein("-->", "->-") => 2,
+            damerau_levenshtein("-->", "->-") => 1,
+            damerau_levenshtein("ca", "abc") => 3, // 受限版本：交换后不可再编辑
+            levenshtein("词项", "词条") => 1, // 按字符计算
+            // 上限
+            edit_distance("-=>", "==>", Levenshtein, 1) => Some(1),
+            edit_distance("-=>", "<=>", Levenshtein, 0) => None,
+            edit_distance("a", "abcd", Damerau, 2) => None, // 长度差超限
+            edit_distance("abcd", "dcba", Levenshtein, 3) => None, // 中途超限
+            edit_distance("abcd", "dcba", Levenshtein, 4) => Some(4),
+        }
+    }
+
+    #[test]
+    fn test_suggest() {
+        let copulas: XFixMatchDict = x_fix_match_dict!("-->" "<->" "==>" "<=>" "{--" "--]" "{-]");
+        asserts! {
+            // 一步之遥
+            copulas
+                .suggest_prefix("-=>", 1, 3)
+                .iter()
+                .map(|s| (s.x_fix, s.distance))
+                .collect::<Vec<_>>()
+                => [("-->", 1), ("<=>", 1), ("==>", 1)],
+            // 多余的字符
+            copulas.suggest_prefix("==>>", 1, 3)[0].x_fix => "==>",
+            // 相邻字符交换
+            copulas.suggest_suffix("->-", 1, 3)[0].x_fix => "-->",
+            // 限制数量
+            copulas.suggest_prefix("-=>", 2, 1).len() => 1,
+            // 完全不像
+            copulas.suggest_prefix("????", 2, 3).is_empty(),
+        }
+        // 配对字典：条目中带有关联内容；空词缀不参与建议
+        let brackets = bi_fix_match_dict_pair!(
+            "(" => ")"
+            "[" => "]"
+            "<<" => ">>"
+        );
+        asserts! {
+            brackets.suggest_prefix("<<<", 1, 5).iter().map(|s| s.term.1.as_str()).collect::<Vec<_>>()
+                => [">>"],
+            brackets.suggest_suffix("}", 1, 5).iter().map(|s| s.x_fix).collect::<Vec<_>>()
+                => [")", "]"],
+        }
+        // 前缀配对字典
+        let prefix_dict = PrefixMatchDictPair::new([("(", ")"), ("{", "}")]);
+        asserts! {
+            prefix_dict.suggest_prefix("[", 1, 5).iter().map(|s| s.term.1).collect::<Vec<_>>()
+                => [")", "}"],
+        }
+    }
+}
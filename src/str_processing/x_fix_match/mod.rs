@@ -7,6 +7,13 @@
 //!   * 📌前缀匹配左括弧，映射到「右括弧」 ⇒ [`PrefixMatchDict`]
 //!   * 📌后缀匹配右括弧，映射到「左括弧」 ⇒ [`SuffixMatchDict`]
 //!   * 📌前缀匹配左括弧⇄后缀匹配右括弧 ⇒ [`BiFixMatchDict`]
+//!   * 📌词缀较多、需要更快的匹配 ⇒ [`XFixTrieDict`]、[`PrefixTrieDictPair`]、[`SuffixTrieDictPair`]
+//!   * 📌在字符串中找出配对的括弧、在顶层按分隔符拆分 ⇒ [`BracketScanner`]
+//!   * 📌从用户可编辑的文本文件加载字典 ⇒ [`XFixMatchDict::from_text`]等
+//!   * 📌忽略大小写、全半角、空白差异的匹配 ⇒ [`NormalizedMatchDict`]
+//!   * 📌为未识别的词缀给出「您是不是想输入……」的建议 ⇒ [`PrefixMatch::suggest_prefix`]、[`edit_distance`]
+//!   * 📌在不同Narsese方言间逐词符转换 ⇒ [`DialectTranslator`]
+//!   * 📌词缀表固定、可在编译期构造 ⇒ [`StaticXFixMatchDict`]、[`StaticBiFixMatchDictPair`]等
 //!
 //! ! ⚠️此处无法使用[`crate::mod_and_reexport`]宏
 //! * 📌原因：内部导出了宏
@@ -15,6 +22,10 @@
 mod std_boost;
 pub use std_boost::*;
 
+// 编辑距离与模糊查找
+mod fuzzy;
+pub use fuzzy::*;
+
 // 抽象特征
 mod traits;
 pub use traits::*;
@@ -38,3 +49,27 @@ pub use suffix_match::*;
 // 双向匹配
 mod bi_fix_dict;
 pub use bi_fix_dict::*;
+
+// 字典树版本的前后缀匹配
+mod trie_dict;
+pub use trie_dict::*;
+
+// 括弧扫描
+mod bracket_scan;
+pub use bracket_scan::*;
+
+// 字典的文本格式
+mod dict_text;
+pub use dict_text::*;
+
+// 归一化的词缀匹配
+mod normalize;
+pub use normalize::*;
+
+// 静态（编译期构造）的词缀匹配字典
+mod static_dict;
+pub use static_dict::*;
+
+// 基于词缀字典的方言转换
+mod dialect;
+pub use dialect::*;
@@ -0,0 +1,525 @@
+//! 归一化的词缀匹配
+//! * 🎯兼容不同NARS实现在输出格式上的细微差异
+//!   * 📄大小写：ONA的`Answer:`与OpenNARS的`ANSWER:`
+//!   * 📄全角/半角标点：`：`与`:`
+//!   * 📄空白：`<A  -->  B>`与`<A --> B>`
+//! * 📌可插拔的「归一化器」[`Normalizer`]
+//!   * 🚩依次进行：字符映射 ⇒ 大小写折叠 ⇒ 空白合并
+//! * 📌归一化的匹配字典[`NormalizedMatchDict`]
+//!   * 📌插入排序与查找都经过归一化：`search`的结果与匹配保持一致
+//!   * 📌匹配长度按**原字符串**计算，可直接用于切分原字符串
+//!   * 📌字符序列版本的匹配（如`BufferIterator`的字典匹配）同样经过归一化
+
+use super::{traits::*, CharSeq, CharSeqRev, XFixMatch};
+use crate::search_by;
+use std::{collections::HashMap, iter::Peekable};
+
+/// 归一化器
+/// * 🚩逐字符归一化
+///   * 1. 字符映射：用户提供的映射表，如`'：' => ':'`
+///   * 2. 大小写折叠：转为小写（仅处理「一对一」的情况）
+///   * 3. 空白合并：连续的空白字符合并为一个空格
+/// * 📌默认不做任何处理
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Normalizer {
+    /// 是否折叠大小写
+    case_fold: bool,
+    /// 字符映射表
+    char_map: HashMap<char, char>,
+    /// 是否合并空白
+    collapse_whitespace: bool,
+}
+
+impl Normalizer {
+    /// 构造函数
+    /// * 📌不做任何处理
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 启用大小写折叠
+    pub fn with_case_fold(mut self) -> Self {
+        self.case_fold = true;
+        self
+    }
+
+    /// 启用空白合并
+    pub fn with_collapse_whitespace(mut self) -> Self {
+        self.collapse_whitespace = true;
+        self
+    }
+
+    /// 添加字符映射
+    /// * 📌格式：`(原字符, 映射到的字符)`
+    pub fn with_char_map(mut self, map: impl IntoIterator<Item = (char, char)>) -> Self {
+        self.char_map.extend(map);
+        self
+    }
+
+    /// 归一化单个字符
+    /// * ⚠️不含「空白合并」：这需要上下文
+    pub fn normalize_char(&self, c: char) -> char {
+        let c = self.char_map.get(&c).copied().unwrap_or(c);
+        match self.case_fold {
+            true => {
+                // 只处理「一对一」的情况，如`'İ'`这样会变成多个字符的保持原样
+                let mut lower = c.to_lowercase();
+                match (lower.next(), lower.next()) {
+                    (Some(lower), None) => lower,
+                    _ => c,
+                }
+            }
+            false => c,
+        }
+    }
+
+    /// 归一化字符串
+    pub fn normalize(&self, s: &str) -> String {
+        self.normalize_chars(s.chars()).map(|(c, ..)| c).collect()
+    }
+
+    /// 归一化字符迭代器
+    /// * 📌格式：`(归一化后的字符, 已消耗的字节数, 已消耗的字符数)`
+    ///   * 🎯将「归一化后的匹配」对应回原字符串
+    ///   * 📌合并后的空白，其「已消耗」包括整段空白
+    fn normalize_chars<I: Iterator<Item = char>>(&self, chars: I) -> NormalizedChars<'_, I> {
+        NormalizedChars {
+            normalizer: self,
+            chars: chars.peekable(),
+            n_bytes: 0,
+            n_chars: 0,
+        }
+    }
+
+    /// 【内部】让归一化后的字符依次与「已归一化的词缀」比对
+    /// * 🚩全部比对成功⇒返回原字符串中消耗的`(字节数, 字符数)`
+    fn match_normalized(
+        &self,
+        normalized_x_fix: impl Iterator<Item = char>,
+        chars: impl Iterator<Item = char>,
+    ) -> Option<(usize, usize)> {
+        let mut chars = self.normalize_chars(chars);
+        let mut consumed = (0, 0);
+        for expected in normalized_x_fix {
+            match chars.next() {
+                Some((c, n_bytes, n_chars)) if c == expected => consumed = (n_bytes, n_chars),
+                _ => return None,
+            }
+        }
+        Some(consumed)
+    }
+
+    /// 归一化的前缀匹配
+    /// * 📌参数`normalized_prefix`须为已归一化的前缀
+    /// * 🚩匹配成功⇒返回原字符串中被匹配部分的`(字节数, 字符数)`
+    pub fn match_prefix_len(
+        &self,
+        normalized_prefix: &str,
+        to_match: &str,
+    ) -> Option<(usize, usize)> {
+        self.match_normalized(normalized_prefix.chars(), to_match.chars())
+    }
+
+    /// 归一化的后缀匹配
+    /// * 📌参数`normalized_suffix`须为已归一化的后缀
+    /// * 🚩匹配成功⇒返回原字符串中被匹配部分的`(字节数, 字符数)`
+    pub fn match_suffix_len(
+        &self,
+        normalized_suffix: &str,
+        to_match: &str,
+    ) -> Option<(usize, usize)> {
+        self.match_normalized(normalized_suffix.chars().rev(), to_match.chars().rev())
+    }
+}
+
+/// 归一化后的字符迭代器
+/// * 📄参见[`Normalizer::normalize_chars`]
+struct NormalizedChars<'n, I: Iterator<Item = char>> {
+    normalizer: &'n Normalizer,
+    chars: Peekable<I>,
+    /// 已消耗的字节数
+    n_bytes: usize,
+    /// 已消耗的字符数
+    n_chars: usize,
+}
+
+impl<I: Iterator<Item = char>> NormalizedChars<'_, I> {
+    /// 取出一个原字符，并计入「已消耗」
+    fn take(&mut self, c: char) {
+        self.n_bytes += c.len_utf8();
+        self.n_chars += 1;
+    }
+}
+
+impl<I: Iterator<Item = char>> Iterator for NormalizedChars<'_, I> {
+    type Item = (char, usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let c = self.chars.next()?;
+        self.take(c);
+        let c = self.normalizer.normalize_char(c);
+        // 合并空白：连同后续的空白一并消耗
+        if self.normalizer.collapse_whitespace && c.is_whitespace() {
+            while let Some(next) = self
+                .chars
+                .next_if(|&next| self.normalizer.normalize_char(next).is_whitespace())
+            {
+                self.take(next);
+            }
+            return Some((' ', self.n_bytes, self.n_chars));
+        }
+        Some((c, self.n_bytes, self.n_chars))
+    }
+}
+
+/// 「归一化词缀条目」
+/// * 📌格式：`(原词缀, 关联内容)`
+type NormalizedTerm<T> = (String, T);
+
+/// 归一化的词缀匹配字典
+/// * 🎯作为[`XFixMatchDict`](super::XFixMatchDict)、[`PrefixMatchDictPair`](super::PrefixMatchDictPair)的替代
+///   * 📌条目格式：`(原词缀, 关联内容)`；无需关联内容时使用`()`
+/// * 📌同时实现「前缀匹配」与「后缀匹配」
+/// * 🚩存储时以「归一化后的词缀」排序、去重
+///   * 📄启用大小写折叠时，`ANSWER:`与`Answer:`视作同一词缀
+/// * 📌匹配时总是取「原字符串中最长」的匹配
+#[derive(Debug, Clone)]
+pub struct NormalizedMatchDict<T = ()> {
+    /// 归一化器
+    normalizer: Normalizer,
+    /// 条目 | `(归一化后的词缀, 条目)`
+    /// * 📌与[`PrefixMatchDictPair`](super::PrefixMatchDictPair)相同，按「归一化后的词缀」倒序排列
+    entries: Vec<(String, NormalizedTerm<T>)>,
+}
+
+impl<T> NormalizedMatchDict<T> {
+    /// 构造函数
+    pub fn new(normalizer: Normalizer) -> Self {
+        Self {
+            normalizer,
+            entries: vec![],
+        }
+    }
+
+    /// 构造函数（带条目）
+    /// * 📌格式：`(词缀, 关联内容)`
+    pub fn with_terms(
+        normalizer: Normalizer,
+        terms: impl IntoIterator<Item = (impl Into<String>, T)>,
+    ) -> Self {
+        let mut dict = Self::new(normalizer);
+        for (x_fix, associated) in terms {
+            dict.insert(x_fix.into(), associated);
+        }
+        dict
+    }
+
+    /// 获取归一化器
+    pub fn normalizer(&self) -> &Normalizer {
+        &self.normalizer
+    }
+
+    /// 获取条目个数
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 判断是否没有条目
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 搜索
+    /// * 🚩先归一化，再查找
+    /// * 🚩找到⇒位置，没找到⇒应该插入的位置
+    pub fn search(&self, x_fix: &str) -> Result<usize, usize> {
+        let key = self.normalizer.normalize(x_fix);
+        search_by(&self.entries, &key.as_str(), |key, (existed, _)| {
+            // * 📌与「前缀配对字典」一致：插入后从大到小排列
+            existed.as_str().cmp(key)
+        })
+    }
+
+    /// 判断「是否已有一个（归一化后相同的）词缀」
+    pub fn has(&self, x_fix: &str) -> bool {
+        self.search(x_fix).is_ok()
+    }
+
+    /// 根据词缀精确查找条目
+    /// * 📌查找经过归一化
+    pub fn get(&self, x_fix: &str) -> Option<&NormalizedTerm<T>> {
+        self.search(x_fix).ok().map(|i| &self.entries[i].1)
+    }
+
+    /// 插入一个条目
+    /// * 🚩归一化后已有⇒不插入，返回`false`
+    pub fn insert(&mut self, x_fix: String, associated: T) -> bool {
+        match self.search(&x_fix) {
+            Ok(..) => false,
+            Err(index) => {
+                let key = self.normalizer.normalize(&x_fix);
+                self.entries.insert(index, (key, (x_fix, associated)));
+                true
+            }
+        }
+    }
+
+    /// 迭代所有条目
+    pub fn iter_terms(&self) -> impl Iterator<Item = &NormalizedTerm<T>> + '_ {
+        self.entries.iter().map(|(_, term)| term)
+    }
+
+    /// 【内部】迭代所有匹配
+    /// * 📌匹配长度按原字符串计算
+    fn matches<'a>(
+        &'a self,
+        match_len: impl Fn(&str) -> Option<(usize, usize)>,
+    ) -> impl Iterator<Item = XFixMatch<'a, NormalizedTerm<T>>> {
+        self.entries.iter().filter_map(move |(key, term)| {
+            match_len(key).map(|(len, n_chars)| XFixMatch { term, len, n_chars })
+        })
+    }
+}
+
+/// 实现「前缀匹配」
+/// * 🚩所有匹配都经过归一化
+impl<T> PrefixMatch<NormalizedTerm<T>> for NormalizedMatchDict<T> {
+    fn get_prefix_from_term(term: &NormalizedTerm<T>) -> &PrefixStr {
+        &term.0
+    }
+
+    fn prefix_terms<'a>(&'a self) -> impl Iterator<Item = &'a NormalizedTerm<T>> + 'a
+    where
+        NormalizedTerm<T>: 'a,
+    {
+        self.iter_terms()
+    }
+
+    fn match_prefix(&self, to_match: &str) -> Option<&NormalizedTerm<T>> {
+        self.match_prefix_longest(to_match).map(|m| m.term)
+    }
+
+    fn match_prefix_chars<S: CharSeq + ?Sized>(&self, to_match: &S) -> Option<&NormalizedTerm<T>> {
+        self.match_prefix_chars_longest(to_match).map(|m| m.term)
+    }
+
+    fn match_prefix_chars_longest<S: CharSeq + ?Sized>(
+        &self,
+        to_match: &S,
+    ) -> Option<XFixMatch<'_, NormalizedTerm<T>>> {
+        let normalizer = &self.normalizer;
+        self.matches(|key| normalizer.match_normalized(key.chars(), to_match.iter_chars()))
+            .max_by_key(|m| m.n_chars)
+    }
+
+    /// * 🚩比对归一化后的字符序列与「归一化后的词缀」
+    /// * 📌合并空白时，以空白结尾⇒之后的空白也要一并匹配，与[`Self::match_prefix`]一致
+    fn prefix_needs_more_chars<S: CharSeq + ?Sized>(&self, to_match: &S) -> bool {
+        let normalizer = &self.normalizer;
+        let trailing_whitespace = normalizer.collapse_whitespace
+            && to_match
+                .iter_chars()
+                .last()
+                .is_some_and(|c| normalizer.normalize_char(c).is_whitespace());
+        self.entries.iter().any(|(key, _)| {
+            let mut key = key.chars();
+            normalizer
+                .normalize_chars(to_match.iter_chars())
+                .all(|(c, ..)| key.next() == Some(c))
+                && (trailing_whitespace || key.next().is_some())
+        })
+    }
+
+    fn match_prefix_all<'a, 's>(
+        &'a self,
+        to_match: &'s str,
+    ) -> impl Iterator<Item = XFixMatch<'a, NormalizedTerm<T>>>
+    where
+        NormalizedTerm<T>: 'a,
+    {
+        let normalizer = &self.normalizer;
+        self.matches(|key| normalizer.match_prefix_len(key, to_match))
+    }
+
+    fn match_prefix_longest(&self, to_match: &str) -> Option<XFixMatch<'_, NormalizedTerm<T>>> {
+        let normalizer = &self.normalizer;
+        self.matches(|key| normalizer.match_prefix_len(key, to_match))
+            .max_by_key(|m| m.len)
+    }
+
+    fn match_prefix_shortest(&self, to_match: &str) -> Option<XFixMatch<'_, NormalizedTerm<T>>> {
+        let normalizer = &self.normalizer;
+        self.matches(|key| normalizer.match_prefix_len(key, to_match))
+            .min_by_key(|m| m.len)
+    }
+}
+
+/// 实现「后缀匹配」
+/// * 🚩所有匹配都经过归一化
+impl<T> SuffixMatch<NormalizedTerm<T>> for NormalizedMatchDict<T> {
+    fn get_suffix_from_term(term: &NormalizedTerm<T>) -> &SuffixStr {
+        &term.0
+    }
+
+    fn suffix_terms<'a>(&'a self) -> impl Iterator<Item = &'a NormalizedTerm<T>> + 'a
+    where
+        NormalizedTerm<T>: 'a,
+    {
+        self.iter_terms()
+    }
+
+    fn match_suffix(&self, to_match: &str) -> Option<&NormalizedTerm<T>> {
+        self.match_suffix_longest(to_match).map(|m| m.term)
+    }
+
+    fn match_suffix_chars<S: CharSeqRev + ?Sized>(
+        &self,
+        to_match: &S,
+    ) -> Option<&NormalizedTerm<T>> {
+        self.match_suffix_chars_longest(to_match).map(|m| m.term)
+    }
+
+    fn match_suffix_chars_longest<S: CharSeqRev + ?Sized>(
+        &self,
+        to_match: &S,
+    ) -> Option<XFixMatch<'_, NormalizedTerm<T>>> {
+        let normalizer = &self.normalizer;
+        self.matches(|key| {
+            normalizer.match_normalized(key.chars().rev(), to_match.iter_chars_rev())
+        })
+        .max_by_key(|m| m.n_chars)
+    }
+
+    fn match_suffix_all<'a, 's>(
+        &'a self,
+        to_match: &'s str,
+    ) -> impl Iterator<Item = XFixMatch<'a, NormalizedTerm<T>>>
+    where
+        NormalizedTerm<T>: 'a,
+    {
+        let normalizer = &self.normalizer;
+        self.matches(|key| normalizer.match_suffix_len(key, to_match))
+    }
+
+    fn match_suffix_longest(&self, to_match: &str) -> Option<XFixMatch<'_, NormalizedTerm<T>>> {
+        let normalizer = &self.normalizer;
+        self.matches(|key| normalizer.match_suffix_len(key, to_match))
+            .max_by_key(|m| m.len)
+    }
+
+    fn match_suffix_shortest(&self, to_match: &str) -> Option<XFixMatch<'_, NormalizedTerm<T>>> {
+        let normalizer = &self.normalizer;
+        self.matches(|key| normalizer.match_suffix_len(key, to_match))
+            .min_by_key(|m| m.len)
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asserts;
+
+    /// 测试/归一化器
+    #[test]
+    fn test_normalizer() {
+        let n = Normalizer::new()
+            .with_case_fold()
+            .with_char_map([('：', ':'), ('（', '('), ('）', ')')])
+            .with_collapse_whitespace();
+        asserts! {
+            Normalizer::new().normalize("ANSWER：  x") => "ANSWER：  x",
+            n.normalize("ANSWER：  <A -->\t\tB>") => "answer: <a --> b>",
+            n.normalize("（Ａ）") => "(ａ)",
+            // 原字符串中的匹配长度
+            n.match_prefix_len("answer:", "Answer：<A --> B>.") => Some(("Answer：".len(), 7)),
+            n.match_prefix_len("a b", "A  \t B") => Some((6, 6)),
+            n.match_prefix_len("a b", "AB") => None,
+            n.match_suffix_len(". :|:", "<A --> B>.   :|:") => Some((7, 7)),
+            n.match_prefix_len("", "x") => Some((0, 0)),
+        }
+    }
+
+    /// 测试/归一化的字典
+    #[test]
+    fn test_normalized_dict() {
+        let normalizer = Normalizer::new()
+            .with_case_fold()
+            .with_char_map([('：', ':')]);
+        let mut dict = NormalizedMatchDict::with_terms(
+            normalizer,
+            [("ANSWER:", "answer"), ("IN:", "in"), ("OUT:", "out")],
+        );
+        // 插入与查找都经过归一化
+        asserts! {
+            dict.len() => 3,
+            !dict.insert("Answer:".into(), "duplicated"),
+            dict.has("answer："),
+            dict.get("in:").map(|(x_fix, _)| x_fix.as_str()) => Some("IN:"),
+            dict.search("out:") => dict.search("OUT:"),
+        }
+        // 前缀匹配：不同NARS实现的输出
+        for output in [
+            "ANSWER: <A --> B>.",
+            "Answer: <A --> B>.",
+            "answer：<A --> B>.",
+        ] {
+            asserts! {
+                dict.match_prefix(output).map(|(_, name)| *name) => Some("answer"),
+            }
+        }
+        let m = dict.match_prefix_longest("Answer：<A --> B>.").unwrap();
+        asserts! {
+            m.term.1 => "answer",
+            m.len => "Answer：".len(), // 按原字符串计算
+            dict.match_prefix("EXE: x") => None,
+            dict.match_prefix_char_slice(&['o', 'U', 't', ':']).map(|(_, name)| *name) => Some("out"),
+        }
+        // 后缀匹配
+        let dict = NormalizedMatchDict::with_terms(
+            Normalizer::new().with_collapse_whitespace(),
+            [(". :|:", ()), (".", ())],
+        );
+        asserts! {
+            dict.match_suffix("<A --> B>.  :|:").map(|(x_fix, _)| x_fix.as_str()) => Some(". :|:"),
+            dict.match_suffix("<A --> B>.").map(|(x_fix, _)| x_fix.as_str()) => Some("."),
+            dict.match_suffix_all("<A --> B>. :|:").count() => 1,
+            dict.match_suffix_chars(&['.', ' ', '\t', ':', '|', ':'][..]).map(|(x_fix, _)| x_fix.as_str()) => Some(". :|:"),
+        }
+    }
+
+    /// 测试/在缓冲迭代器中使用
+    /// * 📌字典匹配经过归一化；跳过的字符数按原字符计算
+    #[test]
+    #[cfg(feature = "iterators")]
+    fn test_in_buffer_iterator() {
+        use crate::BufferIterator;
+        let dict = NormalizedMatchDict::with_terms(
+            Normalizer::new()
+                .with_case_fold()
+                .with_char_map([('：', ':')])
+                .with_collapse_whitespace(),
+            [("answer:", "answer"), ("out: ", "out")],
+        );
+        let mut iter = BufferIterator::new("ANSWER：<A --> B>.".chars());
+        asserts! {
+            iter.match_prefix_dict(&dict).map(|(_, name)| *name) => Some("answer"),
+            iter.skip_when_match_prefix_dict(&dict).map(|(_, name)| *name) => Some("answer"),
+            iter.collect::<String>() => "<A --> B>.",
+        }
+        // 合并空白：与字符串版本一致，连同整段空白一并跳过
+        let text = "Out:      <A --> B>.";
+        let mut iter = BufferIterator::new(text.chars());
+        asserts! {
+            iter.skip_when_match_prefix_dict(&dict).map(|(_, name)| *name) => Some("out"),
+            iter.n_consumed() => dict.match_prefix_longest(text).unwrap().n_chars,
+            iter.collect::<String>() => "<A --> B>.",
+        }
+        // 空白直到末尾
+        let mut iter = BufferIterator::new("OUT:\t\t".chars());
+        asserts! {
+            iter.skip_when_match_prefix_dict(&dict).map(|(_, name)| *name) => Some("out"),
+            iter.collect::<String>() => "",
+        }
+    }
+}
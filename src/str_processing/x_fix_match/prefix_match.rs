@@ -136,11 +136,73 @@ impl<T> PrefixMatchDictPair<T> {
         None
     }
 
+    /// 根据前缀获取「关联内容」
+    /// * 🚩找不到⇒[`None`]
+    pub fn get(&self, prefix: &PrefixStr) -> Option<&T> {
+        let index = self.search(prefix).ok()?;
+        Some(Self::get_associated_from_term(&self.prefixes[index]))
+    }
+
+    /// 根据前缀获取「关联内容」的可变引用
+    /// * 📌只开放「关联内容」的修改：前缀决定了条目顺序，不能在原地修改
+    pub fn get_mut(&mut self, prefix: &PrefixStr) -> Option<&mut T> {
+        let index = self.search(prefix).ok()?;
+        Some(&mut self.prefixes[index].1)
+    }
+
+    /// 替换前缀所对应的「关联内容」
+    /// * 🚩有此前缀⇒替换并返回旧的关联内容
+    /// * 🚩无此前缀⇒不插入，返回[`None`]
+    pub fn replace_associated(&mut self, prefix: &PrefixStr, associated: T) -> Option<T> {
+        self.get_mut(prefix)
+            .map(|old| std::mem::replace(old, associated))
+    }
+
+    /// 根据前缀移除条目
+    /// * 🚩返回被移除的条目
+    /// * 📌移除不影响其它条目的顺序
+    pub fn remove(&mut self, prefix: &PrefixStr) -> Option<PrefixTerm<T>> {
+        let index = self.search(prefix).ok()?;
+        Some(self.prefixes.remove(index))
+    }
+
+    /// 只保留满足条件的条目
+    /// * 📌类似[`Vec::retain`]，保持原有顺序
+    pub fn retain(&mut self, f: impl FnMut(&PrefixTerm<T>) -> bool) {
+        self.prefixes.retain(f)
+    }
+
+    /// 合并另一个前缀配对字典
+    /// * 📌「冲突」即「前缀相同」，按[`MergePolicy`]处理
+    /// * 🚩[`MergePolicy::Error`]策略下，先检查所有冲突再合并：报错时自身不变
+    pub fn merge(&mut self, other: Self, policy: MergePolicy) -> Result<(), MergeConflict> {
+        if policy == MergePolicy::Error {
+            if let Some(term) = other
+                .prefixes
+                .iter()
+                .find(|term| self.search(&term.0).is_ok())
+            {
+                return Err(MergeConflict {
+                    x_fix: term.0.clone(),
+                });
+            }
+        }
+        for term in other.prefixes {
+            match self.search(&term.0) {
+                // 冲突⇒按策略覆盖 | 「报错」策略已在上边排除
+                Ok(index) if policy == MergePolicy::Overwrite => self.prefixes[index] = term,
+                Ok(..) => {}
+                Err(index) => self.prefixes.insert(index, term),
+            }
+        }
+        Ok(())
+    }
+
     /// （前前缀无关）以特殊顺序迭代「词缀」
     /// * 🎯统一「前前缀匹配」的迭代逻辑
     /// * 🚩总是按照「字典顺序」倒序遍历：**长度从长到短**
     #[inline(always)]
-    pub fn iter_terms<'a>(&'a self) -> impl Iterator<Item = &PrefixTerm<T>> + 'a
+    pub fn iter_terms<'a>(&'a self) -> impl Iterator<Item = &'a PrefixTerm<T>> + 'a
     where
         String: 'a,
     {
@@ -166,6 +228,16 @@ impl<T> PrefixMatchDictPair<T> {
     }
 }
 
+/// 批量插入条目
+/// * 🚩逐个调用[`PrefixMatchDictPair::insert`]，前缀已有的条目会被跳过
+impl<T> Extend<PrefixTerm<T>> for PrefixMatchDictPair<T> {
+    fn extend<I: IntoIterator<Item = PrefixTerm<T>>>(&mut self, iter: I) {
+        for term in iter {
+            self.insert(term);
+        }
+    }
+}
+
 /// 实现「前缀匹配」逻辑
 impl<T> PrefixMatch<PrefixTerm<T>> for PrefixMatchDictPair<T> {
     // 下面的方法直接进行「特化重定向」处理 //
@@ -185,7 +257,7 @@ impl<T> PrefixMatch<PrefixTerm<T>> for PrefixMatchDictPair<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{show, test_match_prefix};
+    use crate::{asserts, show, test_match_prefix};
 
     /// 测试/边缘
     #[test]
@@ -260,4 +332,66 @@ mod tests {
             "word" => None
         }
     }
+
+    /// 测试/移除、修改、合并
+    #[test]
+    fn test_update_merge() {
+        // 基础方言
+        let base: PrefixMatchDictPair<String> = prefix_match_dict_pair!(
+            "(" => ")"
+            "[" => "]"
+            "{" => "}"
+        );
+        let mut d = base.clone();
+        // 修改 & 移除
+        asserts! {
+            d.replace_associated("(", "]".into()) => Some(")".into()),
+            d.get("(") => Some(&"]".into()),
+            d.replace_associated("<", ">".into()) => None,
+            d.get("<") => None,
+            d.remove("[") => Some(("[".into(), "]".into())),
+            d.remove("[") => None,
+        }
+        if let Some(r) = d.get_mut("{") {
+            r.push('}');
+        }
+        asserts! {
+            d.match_prefix("{A").map(|(_, r)| r.as_str()) => Some("}}"),
+        }
+        // 保留
+        d.retain(|(l, _)| l != "{");
+        asserts! {
+            d.iter_terms().count() => 1,
+        }
+
+        // 合并 | 派生方言变体，覆盖少数条目
+        let variant: PrefixMatchDictPair<String> = prefix_match_dict_pair!(
+            "(" => "）"
+            "<" => ">"
+        );
+        let mut keep = base.clone();
+        keep.merge(variant.clone(), MergePolicy::KeepExisting)
+            .unwrap();
+        let mut overwrite = base.clone();
+        overwrite
+            .merge(variant.clone(), MergePolicy::Overwrite)
+            .unwrap();
+        let mut error = base.clone();
+        asserts! {
+            keep.get("(") => Some(&")".into()),
+            keep.get("<") => Some(&">".into()),
+            overwrite.get("(") => Some(&"）".into()),
+            overwrite.get("<") => Some(&">".into()),
+            error.merge(variant, MergePolicy::Error) => Err(MergeConflict { x_fix: "(".into() }),
+            // 报错时不修改自身
+            error.get("<") => None,
+        }
+        // 批量插入
+        let mut d: PrefixMatchDictPair<String> = prefix_match_dict_pair!("-" => "");
+        d.extend([("-->".into(), "".into()), ("--".into(), "".into())]);
+        asserts! {
+            d.match_prefix("--A").map(|(l, _)| l.as_str()) => Some("--"),
+            d.match_prefix("-->A").map(|(l, _)| l.as_str()) => Some("-->"),
+        }
+    }
 }
@@ -0,0 +1,496 @@
+//! 静态（编译期构造）的前后缀匹配字典
+//! * 🎯让各方言的词缀表直接作为`static`项存在，无需在启动时逐个`insert`
+//!   * 📄`static COPULAS: StaticXFixMatchDict = StaticXFixMatchDict::new(&["-->", "<->", "==>"]);`
+//! * 🚩以`const fn`构造，要求输入**已按字典序严格升序排列**
+//!   * 📌乱序、重复的输入会在常量求值时`panic`
+//!     * 用在`static`/`const`项中⇒**编译失败**
+//!     * 在运行时调用⇒运行时`panic`
+//!   * 📌匹配时与对应的动态字典（[`XFixMatchDict`]等）顺序一致：倒序迭代，长的在先
+//! * 📌只读：需要修改时，转换为对应的动态字典
+
+use super::{
+    traits::*, BiFixMatchDictPair, PrefixMatchDictPair, SuffixMatchDictPair, XFixMatchDict,
+};
+use std::cmp::Ordering;
+
+/// 编译期的字符串比较
+/// * 🎯`str`的[`Ord`]实现不是`const`的
+/// * 📌逐字节比较：UTF-8编码下与字符（码点）顺序一致
+const fn const_cmp_str(a: &str, b: &str) -> Ordering {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    let mut i = 0;
+    while i < a.len() && i < b.len() {
+        if a[i] < b[i] {
+            return Ordering::Less;
+        }
+        if a[i] > b[i] {
+            return Ordering::Greater;
+        }
+        i += 1;
+    }
+    if a.len() < b.len() {
+        Ordering::Less
+    } else if a.len() > b.len() {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+/// 编译期检查「前一个词缀严格小于后一个词缀」
+/// * 🚩否则`panic`：常量求值中即为编译错误
+const fn assert_ascending(previous: &str, next: &str) {
+    match const_cmp_str(previous, next) {
+        Ordering::Less => {}
+        Ordering::Equal => panic!("静态词缀字典：词缀重复"),
+        Ordering::Greater => panic!("静态词缀字典：词缀必须按字典序升序排列"),
+    }
+}
+
+/// 在「按词缀升序排列」的切片中二分查找
+/// * 🎯各静态字典共用的「搜索」逻辑
+fn search_sorted<Term>(
+    terms: &[Term],
+    x_fix: &str,
+    x_fix_of: impl Fn(&Term) -> &str,
+) -> Option<usize> {
+    terms
+        .binary_search_by(|term| x_fix_of(term).cmp(x_fix))
+        .ok()
+}
+
+/// 静态词缀匹配字典
+/// * 📌对应[`XFixMatchDict`]，条目就是词缀本身
+/// * ⚠️构造时要求词缀**严格升序**排列
+///
+/// ```compile_fail
+/// use nar_dev_utils::StaticXFixMatchDict;
+/// // 乱序⇒编译失败
+/// static COPULAS: StaticXFixMatchDict = StaticXFixMatchDict::new(&["==>", "-->"]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct StaticXFixMatchDict {
+    /// 按字典序升序排列的词缀
+    x_fixes: &'static [&'static str],
+}
+
+impl StaticXFixMatchDict {
+    /// 构造函数
+    /// * 🚩检查词缀严格升序排列，否则`panic`（编译期即编译失败）
+    pub const fn new(x_fixes: &'static [&'static str]) -> Self {
+        let mut i = 1;
+        while i < x_fixes.len() {
+            assert_ascending(x_fixes[i - 1], x_fixes[i]);
+            i += 1;
+        }
+        Self { x_fixes }
+    }
+
+    /// 词缀数量
+    pub const fn len(&self) -> usize {
+        self.x_fixes.len()
+    }
+
+    /// 是否为空
+    pub const fn is_empty(&self) -> bool {
+        self.x_fixes.is_empty()
+    }
+
+    /// 判断「是否已有一个词缀」
+    /// * 🚩二分查找
+    pub fn has(&self, x_fix: &str) -> bool {
+        search_sorted(self.x_fixes, x_fix, |x_fix| x_fix).is_some()
+    }
+
+    /// 以特殊顺序迭代「词缀」
+    /// * 🚩与[`XFixMatchDict::iter_x_fixes`]一致：倒序遍历，**长度从长到短**
+    pub fn iter_x_fixes(&self) -> impl Iterator<Item = &&'static str> + '_ {
+        self.x_fixes.iter().rev()
+    }
+}
+
+/// 转换为动态字典
+/// * 🎯在静态字典的基础上派生方言变体
+impl From<&StaticXFixMatchDict> for XFixMatchDict {
+    fn from(dict: &StaticXFixMatchDict) -> Self {
+        XFixMatchDict::new(dict.x_fixes.iter().copied())
+    }
+}
+
+/// 实现「前缀匹配」
+impl PrefixMatch<&'static str> for StaticXFixMatchDict {
+    fn get_prefix_from_term<'t>(term: &'t &'static str) -> &'t PrefixStr {
+        term
+    }
+
+    fn prefix_terms<'a>(&'a self) -> impl Iterator<Item = &'a &'static str> + 'a
+    where
+        &'static str: 'a,
+    {
+        self.iter_x_fixes()
+    }
+}
+
+/// 实现「后缀匹配」
+impl SuffixMatch<&'static str> for StaticXFixMatchDict {
+    fn get_suffix_from_term<'t>(term: &'t &'static str) -> &'t SuffixStr {
+        term
+    }
+
+    fn suffix_terms<'a>(&'a self) -> impl Iterator<Item = &'a &'static str> + 'a
+    where
+        &'static str: 'a,
+    {
+        self.iter_x_fixes()
+    }
+}
+
+/// 静态前缀配对字典
+/// * 📌对应[`PrefixMatchDictPair`]，条目为`(前缀, 关联内容)`
+/// * ⚠️构造时要求前缀**严格升序**排列
+#[derive(Debug, Clone, Copy)]
+pub struct StaticPrefixMatchDictPair<T: 'static> {
+    /// 按前缀升序排列的条目
+    prefixes: &'static [(&'static str, T)],
+}
+
+impl<T> StaticPrefixMatchDictPair<T> {
+    /// 构造函数
+    /// * 🚩检查前缀严格升序排列，否则`panic`（编译期即编译失败）
+    pub const fn new(prefixes: &'static [(&'static str, T)]) -> Self {
+        let mut i = 1;
+        while i < prefixes.len() {
+            assert_ascending(prefixes[i - 1].0, prefixes[i].0);
+            i += 1;
+        }
+        Self { prefixes }
+    }
+
+    /// 条目数量
+    pub const fn len(&self) -> usize {
+        self.prefixes.len()
+    }
+
+    /// 是否为空
+    pub const fn is_empty(&self) -> bool {
+        self.prefixes.is_empty()
+    }
+
+    /// 根据前缀获取「关联内容」
+    pub fn get(&self, prefix: &str) -> Option<&T> {
+        search_sorted(self.prefixes, prefix, |(prefix, _)| prefix).map(|i| &self.prefixes[i].1)
+    }
+
+    /// 以特殊顺序迭代条目
+    /// * 🚩倒序遍历：**长度从长到短**
+    pub fn iter_terms(&self) -> impl Iterator<Item = &(&'static str, T)> + '_ {
+        self.prefixes.iter().rev()
+    }
+}
+
+/// 转换为动态字典
+impl<T: Clone> From<&StaticPrefixMatchDictPair<T>> for PrefixMatchDictPair<T> {
+    fn from(dict: &StaticPrefixMatchDictPair<T>) -> Self {
+        PrefixMatchDictPair::new(dict.prefixes.iter().cloned())
+    }
+}
+
+/// 实现「前缀匹配」
+impl<T> PrefixMatch<(&'static str, T)> for StaticPrefixMatchDictPair<T> {
+    fn get_prefix_from_term<'t>(term: &'t (&'static str, T)) -> &'t PrefixStr {
+        term.0
+    }
+
+    fn prefix_terms<'a>(&'a self) -> impl Iterator<Item = &'a (&'static str, T)> + 'a
+    where
+        (&'static str, T): 'a,
+    {
+        self.iter_terms()
+    }
+}
+
+/// 静态后缀配对字典
+/// * 📌对应[`SuffixMatchDictPair`]，条目为`(关联内容, 后缀)`
+/// * ⚠️构造时要求后缀**严格升序**排列
+#[derive(Debug, Clone, Copy)]
+pub struct StaticSuffixMatchDictPair<T: 'static> {
+    /// 按后缀升序排列的条目
+    suffixes: &'static [(T, &'static str)],
+}
+
+impl<T> StaticSuffixMatchDictPair<T> {
+    /// 构造函数
+    /// * 🚩检查后缀严格升序排列，否则`panic`（编译期即编译失败）
+    pub const fn new(suffixes: &'static [(T, &'static str)]) -> Self {
+        let mut i = 1;
+        while i < suffixes.len() {
+            assert_ascending(suffixes[i - 1].1, suffixes[i].1);
+            i += 1;
+        }
+        Self { suffixes }
+    }
+
+    /// 条目数量
+    pub const fn len(&self) -> usize {
+        self.suffixes.len()
+    }
+
+    /// 是否为空
+    pub const fn is_empty(&self) -> bool {
+        self.suffixes.is_empty()
+    }
+
+    /// 根据后缀获取「关联内容」
+    pub fn get(&self, suffix: &str) -> Option<&T> {
+        search_sorted(self.suffixes, suffix, |(_, suffix)| suffix).map(|i| &self.suffixes[i].0)
+    }
+
+    /// 以特殊顺序迭代条目
+    /// * 🚩倒序遍历，与[`SuffixMatchDictPair`]一致
+    pub fn iter_terms(&self) -> impl Iterator<Item = &(T, &'static str)> + '_ {
+        self.suffixes.iter().rev()
+    }
+}
+
+/// 转换为动态字典
+impl<T: Clone> From<&StaticSuffixMatchDictPair<T>> for SuffixMatchDictPair<T> {
+    fn from(dict: &StaticSuffixMatchDictPair<T>) -> Self {
+        SuffixMatchDictPair::new(dict.suffixes.iter().cloned())
+    }
+}
+
+/// 实现「后缀匹配」
+impl<T> SuffixMatch<(T, &'static str)> for StaticSuffixMatchDictPair<T> {
+    fn get_suffix_from_term<'t>(term: &'t (T, &'static str)) -> &'t SuffixStr {
+        term.1
+    }
+
+    fn suffix_terms<'a>(&'a self) -> impl Iterator<Item = &'a (T, &'static str)> + 'a
+    where
+        (T, &'static str): 'a,
+    {
+        self.iter_terms()
+    }
+}
+
+/// 静态双向配对字典
+/// * 📌对应[`BiFixMatchDictPair`]，条目为`(前缀, 后缀, 关联内容)`
+/// * ⚠️构造时要求前缀**严格升序**排列，且后缀不重复
+///   * 📌后缀的顺序在编译期计算，存于「后缀序索引数组」中
+///   * 📌因此类型上需要带有条目数量`N`
+#[derive(Debug, Clone, Copy)]
+pub struct StaticBiFixMatchDictPair<T: 'static, const N: usize> {
+    /// 按前缀升序排列的条目
+    terms: &'static [(&'static str, &'static str, T); N],
+    /// 后缀序索引数组 | 按后缀升序排列
+    suffix_order: [usize; N],
+}
+
+impl<T, const N: usize> StaticBiFixMatchDictPair<T, N> {
+    /// 构造函数
+    /// * 🚩检查前缀严格升序排列，否则`panic`（编译期即编译失败）
+    /// * 🚩对后缀做插入排序，得到「后缀序索引数组」；后缀重复⇒`panic`
+    pub const fn new(terms: &'static [(&'static str, &'static str, T); N]) -> Self {
+        let mut suffix_order = [0; N];
+        let mut i = 0;
+        while i < N {
+            if i > 0 {
+                assert_ascending(terms[i - 1].0, terms[i].0);
+            }
+            // 插入排序：将比自己大的后移
+            let mut j = i;
+            while j > 0
+                && matches!(
+                    const_cmp_str(terms[suffix_order[j - 1]].1, terms[i].1),
+                    Ordering::Greater
+                )
+            {
+                suffix_order[j] = suffix_order[j - 1];
+                j -= 1;
+            }
+            if j > 0 {
+                assert_ascending(terms[suffix_order[j - 1]].1, terms[i].1);
+            }
+            suffix_order[j] = i;
+            i += 1;
+        }
+        Self {
+            terms,
+            suffix_order,
+        }
+    }
+
+    /// 条目数量
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// 是否为空
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// 根据前缀获取条目
+    pub fn get_by_prefix(&self, prefix: &str) -> Option<&(&'static str, &'static str, T)> {
+        search_sorted(self.terms.as_slice(), prefix, |(prefix, ..)| prefix).map(|i| &self.terms[i])
+    }
+
+    /// 根据后缀获取条目
+    pub fn get_by_suffix(&self, suffix: &str) -> Option<&(&'static str, &'static str, T)> {
+        search_sorted(self.suffix_order.as_slice(), suffix, |&i| self.terms[i].1)
+            .map(|i| &self.terms[self.suffix_order[i]])
+    }
+
+    /// 根据前缀获取「关联内容」
+    pub fn get(&self, prefix: &str) -> Option<&T> {
+        self.get_by_prefix(prefix)
+            .map(|(.., associated)| associated)
+    }
+}
+
+/// 转换为动态字典
+impl<T: Clone, const N: usize> From<&StaticBiFixMatchDictPair<T, N>> for BiFixMatchDictPair<T> {
+    fn from(dict: &StaticBiFixMatchDictPair<T, N>) -> Self {
+        BiFixMatchDictPair::with_terms(dict.terms.iter().cloned())
+    }
+}
+
+/// 实现「前缀匹配」
+impl<T, const N: usize> PrefixMatch<(&'static str, &'static str, T)>
+    for StaticBiFixMatchDictPair<T, N>
+{
+    fn get_prefix_from_term<'t>(term: &'t (&'static str, &'static str, T)) -> &'t PrefixStr {
+        term.0
+    }
+
+    fn prefix_terms<'a>(&'a self) -> impl Iterator<Item = &'a (&'static str, &'static str, T)> + 'a
+    where
+        (&'static str, &'static str, T): 'a,
+    {
+        self.terms.iter().rev()
+    }
+}
+
+/// 实现「后缀匹配」
+impl<T, const N: usize> SuffixMatch<(&'static str, &'static str, T)>
+    for StaticBiFixMatchDictPair<T, N>
+{
+    fn get_suffix_from_term<'t>(term: &'t (&'static str, &'static str, T)) -> &'t SuffixStr {
+        term.1
+    }
+
+    fn suffix_terms<'a>(&'a self) -> impl Iterator<Item = &'a (&'static str, &'static str, T)> + 'a
+    where
+        (&'static str, &'static str, T): 'a,
+    {
+        self.suffix_order.iter().rev().map(|&i| &self.terms[i])
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{asserts, x_fix_match_dict, PrefixMatchDict};
+
+    /// 陈述系词 | 升序排列
+    static COPULAS: StaticXFixMatchDict =
+        StaticXFixMatchDict::new(&["-->", "--]", "<->", "<=>", "==>", "{--", "{-]"]);
+
+    /// 真值、时间戳等后缀
+    static PUNCTUATIONS: StaticSuffixMatchDictPair<char> =
+        StaticSuffixMatchDictPair::new(&[('!', "!"), ('.', "."), ('?', "?"), ('@', "@")]);
+
+    /// 括弧 ⇒ 所开启的结构
+    static BRACKETS: StaticBiFixMatchDictPair<&str, 4> = StaticBiFixMatchDictPair::new(&[
+        ("(", ")", "compound"),
+        ("<", ">", "statement"),
+        ("[", "]", "int_set"),
+        ("{", "}", "ext_set"),
+    ]);
+
+    /// 测试/与动态字典结果一致
+    #[test]
+    fn test_same_as_dynamic() {
+        let dynamic = x_fix_match_dict!("-->" "<->" "<=>" "==>" "{--" "--]" "{-]");
+        for s in [
+            "-->A", "<->", "<=>B", "==>", "{--C", "{-]", "--]", "--", "A",
+        ] {
+            assert_eq!(
+                COPULAS.match_prefix(s).copied(),
+                dynamic.match_prefix(s).map(String::as_str),
+            );
+            assert_eq!(
+                COPULAS.match_suffix(s).copied(),
+                dynamic.match_suffix(s).map(String::as_str),
+            );
+        }
+        asserts! {
+            COPULAS.len() => 7,
+            COPULAS.has("==>"),
+            !COPULAS.has("=/>"),
+            XFixMatchDict::from(&COPULAS).iter_x_fixes().count() => 7,
+        }
+    }
+
+    /// 测试/配对字典
+    #[test]
+    fn test_pairs() {
+        static ATOMS: StaticPrefixMatchDictPair<&str> = StaticPrefixMatchDictPair::new(&[
+            ("", "word"),
+            ("#", "dependent"),
+            ("$", "independent"),
+        ]);
+        asserts! {
+            ATOMS.match_prefix("$x").map(|(_, kind)| *kind) => Some("independent"),
+            ATOMS.match_prefix("x").map(|(_, kind)| *kind) => Some("word"),
+            ATOMS.get("#") => Some(&"dependent"),
+            PUNCTUATIONS.match_suffix("<A --> B>?").map(|(c, _)| *c) => Some('?'),
+            PUNCTUATIONS.match_suffix("<A --> B>") => None,
+            PUNCTUATIONS.get("@") => Some(&'@'),
+        }
+        // 双向
+        asserts! {
+            BRACKETS.match_prefix("{A}") => Some(&("{", "}", "ext_set")),
+            BRACKETS.match_suffix("<A --> B>") => Some(&("<", ">", "statement")),
+            BRACKETS.get_by_suffix("]").map(|(.., s)| *s) => Some("int_set"),
+            BRACKETS.get("(") => Some(&"compound"),
+            BRACKETS.get_by_prefix("》") => None,
+            BiFixMatchDictPair::from(&BRACKETS).match_suffix("(A)").map(|(.., s)| *s) => Some("compound"),
+        }
+    }
+
+    /// 测试/后缀的顺序在编译期计算
+    #[test]
+    fn test_suffix_order() {
+        static D: StaticBiFixMatchDictPair<(), 3> =
+            StaticBiFixMatchDictPair::new(&[("a", "", ()), ("b", "bb", ()), ("c", "b", ())]);
+        asserts! {
+            D.suffix_terms().map(|(_, suffix, _)| *suffix).collect::<Vec<_>>() => vec!["bb", "b", ""],
+            D.match_suffix("xbb").map(|(prefix, ..)| *prefix) => Some("b"),
+            D.match_suffix("xb").map(|(prefix, ..)| *prefix) => Some("c"),
+            D.match_suffix("x").map(|(prefix, ..)| *prefix) => Some("a"),
+        }
+    }
+
+    /// 测试/乱序（运行时构造⇒panic）
+    #[test]
+    #[should_panic(expected = "升序")]
+    fn test_out_of_order() {
+        StaticXFixMatchDict::new(&["==>", "-->"]);
+    }
+
+    /// 测试/重复（运行时构造⇒panic）
+    #[test]
+    #[should_panic(expected = "重复")]
+    fn test_duplicated() {
+        StaticPrefixMatchDictPair::new(&[("(", 0), ("(", 1)]);
+    }
+
+    /// 测试/后缀重复（运行时构造⇒panic）
+    #[test]
+    #[should_panic(expected = "重复")]
+    fn test_duplicated_suffix() {
+        StaticBiFixMatchDictPair::new(&[("(", ")", ()), ("[", ")", ())]);
+    }
+}
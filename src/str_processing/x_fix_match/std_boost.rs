@@ -2,36 +2,171 @@
 //! * 🎯最初由「`&[char]`要支持`&str`前后缀匹配」而来
 
 use crate::if_return;
+use std::collections::VecDeque;
 
 /// 用于为「字符数组切片」添加对「静态字串」的前缀匹配功能
+/// * ✨现已推广到各种「字符序列」：[`[char]`](slice)、[`VecDeque<char>`]、[`[u8]`](slice)（UTF-8字节）、[`str`]
+/// * 📌所有实现均**逐个字符比对**，不会分配新的[`String`]
 pub trait StartsWithStr {
     /// 检查自身是否以指定静态字串（`&str`）开头
     /// * 📌类似[`[T]::starts_with`]方法，但会**逐个字符比对字符串**
     fn starts_with_str(&self, needle: &str) -> bool;
 }
 
+/// 用于为「字符序列」添加对「静态字串」的后缀匹配功能
+/// * 🎯与[`StartsWithStr`]配对，供「后缀匹配」使用
+pub trait EndsWithStr {
+    /// 检查自身是否以指定静态字串（`&str`）结尾
+    /// * 📌类似[`[T]::ends_with`]方法，但会**从尾部逐个字符比对字符串**
+    fn ends_with_str(&self, needle: &str) -> bool;
+}
+
+/// 可按顺序迭代字符的「字符序列」
+/// * 🎯供字典树、归一化等「逐字符匹配」的实现使用
+///   * 📄[`crate::PrefixMatch::match_prefix_chars`]
+/// * 📌与[`StartsWithStr`]分开：只需「前缀比对」的类型不必实现字符迭代
+pub trait CharSeq: StartsWithStr {
+    /// 按顺序迭代自身的字符
+    fn iter_chars(&self) -> impl Iterator<Item = char> + '_;
+}
+
+/// 可逆序迭代字符的「字符序列」
+/// * 🎯供「后缀匹配」逐字符比对使用
+///   * 📄[`crate::SuffixMatch::match_suffix_chars`]
+pub trait CharSeqRev: CharSeq + EndsWithStr {
+    /// 逆序迭代自身的字符
+    fn iter_chars_rev(&self) -> impl Iterator<Item = char> + '_;
+}
+
+/// 逐个比对「字符迭代器」与「字符串字符」，判断前者是否以后者开头
+/// * 🚩以`needle`为准迭代：`needle`耗尽⇒true；自身先耗尽或字符不等⇒false
+/// * 📌后缀匹配时，传入两者的逆序迭代器即可
+fn chars_start_with(
+    mut chars: impl Iterator<Item = char>,
+    mut needle_chars: impl Iterator<Item = char>,
+) -> bool {
+    needle_chars.all(|c2| chars.next() == Some(c2))
+}
+
 impl StartsWithStr for [char] {
     fn starts_with_str(&self, needle: &str) -> bool {
-        // 空字串总是为true
-        if_return! { needle.is_empty() => true }
-        // 空自身总是为false
-        if_return! { self.is_empty() => false }
-        // 生成字符迭代器
-        let mut needle_chars = needle.chars();
-        // 逐个检查自身字符（不从字符串处检查，避免不必要的越界检查）
-        for c in self.iter() {
-            // 从 needle 中取下一个字符
-            match needle_chars.next() {
-                // 有且字符相等⇒继续
-                Some(c2) if *c == c2 => (),
-                // 没有字符⇒true | 比自身短
-                None => return true,
-                // 否则⇒返回 false
-                _ => return false,
+        // 逐个比对字符 | 自身比`needle`短时为false
+        chars_start_with(self.iter_chars(), needle.chars())
+    }
+}
+
+impl CharSeq for [char] {
+    fn iter_chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.iter().copied()
+    }
+}
+
+impl EndsWithStr for [char] {
+    fn ends_with_str(&self, needle: &str) -> bool {
+        chars_start_with(self.iter_chars_rev(), needle.chars().rev())
+    }
+}
+
+impl CharSeqRev for [char] {
+    fn iter_chars_rev(&self) -> impl Iterator<Item = char> + '_ {
+        self.iter().rev().copied()
+    }
+}
+
+impl StartsWithStr for VecDeque<char> {
+    fn starts_with_str(&self, needle: &str) -> bool {
+        chars_start_with(self.iter_chars(), needle.chars())
+    }
+}
+
+impl CharSeq for VecDeque<char> {
+    fn iter_chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.iter().copied()
+    }
+}
+
+impl EndsWithStr for VecDeque<char> {
+    fn ends_with_str(&self, needle: &str) -> bool {
+        chars_start_with(self.iter_chars_rev(), needle.chars().rev())
+    }
+}
+
+impl CharSeqRev for VecDeque<char> {
+    fn iter_chars_rev(&self) -> impl Iterator<Item = char> + '_ {
+        self.iter().rev().copied()
+    }
+}
+
+/// 视作UTF-8字节序列
+/// * 📌UTF-8编码下，「字符前缀」与「字节前缀」等价，直接比对字节即可
+impl StartsWithStr for [u8] {
+    fn starts_with_str(&self, needle: &str) -> bool {
+        self.starts_with(needle.as_bytes())
+    }
+}
+
+/// 按UTF-8解码字节序列
+/// * 📌**每个**无效的字节视作一个[`char::REPLACEMENT_CHARACTER`]
+///   * 🎯正序、逆序迭代的结果互为逆序：逆序解码时无法得知「无效序列」的分段
+///   * ⚠️与[`String::from_utf8_lossy`]不同：后者每个「无效序列」只替换一次
+impl CharSeq for [u8] {
+    fn iter_chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.utf8_chunks().flat_map(|chunk| {
+            chunk.valid().chars().chain(std::iter::repeat_n(
+                char::REPLACEMENT_CHARACTER,
+                chunk.invalid().len(),
+            ))
+        })
+    }
+}
+
+impl EndsWithStr for [u8] {
+    fn ends_with_str(&self, needle: &str) -> bool {
+        self.ends_with(needle.as_bytes())
+    }
+}
+
+impl CharSeqRev for [u8] {
+    fn iter_chars_rev(&self) -> impl Iterator<Item = char> + '_ {
+        let mut rest = self;
+        std::iter::from_fn(move || {
+            if_return! { rest.is_empty() => None }
+            // 尾部最短的有效UTF-8序列，即为最后一个字符
+            for n in 1..=rest.len().min(4) {
+                let (init, last) = rest.split_at(rest.len() - n);
+                if let Ok(last) = std::str::from_utf8(last) {
+                    rest = init;
+                    return last.chars().next();
+                }
             }
-        }
-        // 检查完成⇒返回 true
-        true
+            // 无效字节⇒逐个替换
+            rest = &rest[..rest.len() - 1];
+            Some(char::REPLACEMENT_CHARACTER)
+        })
+    }
+}
+
+impl StartsWithStr for str {
+    fn starts_with_str(&self, needle: &str) -> bool {
+        self.starts_with(needle)
+    }
+}
+
+impl CharSeq for str {
+    fn iter_chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.chars()
+    }
+}
+
+impl EndsWithStr for str {
+    fn ends_with_str(&self, needle: &str) -> bool {
+        self.ends_with(needle)
+    }
+}
+
+impl CharSeqRev for str {
+    fn iter_chars_rev(&self) -> impl Iterator<Item = char> + '_ {
+        self.chars().rev()
     }
 }
 
@@ -59,4 +194,71 @@ mod tests {
             ['a' 'b' 'c'] => "";
         }
     }
+
+    /// 测试各种「字符序列」的前后缀匹配
+    /// * 📄自身比`needle`短、非ASCII字符、空序列
+    #[test]
+    fn test_x_fix_with_str() {
+        let chars = ['中', '文', 'a'];
+        let deque = chars.iter().copied().collect::<VecDeque<_>>();
+        let bytes = "中文a".as_bytes();
+        asserts! {
+            // 自身过短⇒false
+            !['a'].starts_with_str("ab"),
+            !['b'].ends_with_str("ab"),
+            !<[char]>::starts_with_str(&[], "a"),
+            <[char]>::ends_with_str(&[], ""),
+            // 字符数组切片
+            chars.starts_with_str("中文"),
+            chars.ends_with_str("文a"),
+            !chars.ends_with_str("中"),
+            // 双端队列
+            deque.starts_with_str("中"),
+            deque.ends_with_str("中文a"),
+            !deque.starts_with_str("中文ab"),
+            // UTF-8字节
+            bytes.starts_with_str("中"),
+            bytes.ends_with_str("a"),
+            !bytes.ends_with_str("文"),
+            // 字符串
+            "中文a".starts_with_str("中文"),
+            "中文a".ends_with_str("文a"),
+        }
+        // 逐字符迭代
+        let invalid: &[u8] = b"a\xFF\xE4\xB8";
+        asserts! {
+            deque.iter_chars_rev().collect::<String>() => "a文中",
+            bytes.iter_chars().collect::<String>() => "中文a",
+            bytes.iter_chars_rev().collect::<String>() => "a文中",
+            // 每个无效字节替换一次
+            invalid.iter_chars().collect::<String>() => "a\u{FFFD}\u{FFFD}\u{FFFD}",
+            invalid.iter_chars_rev().collect::<String>() => "\u{FFFD}\u{FFFD}\u{FFFD}a",
+        }
+    }
+
+    /// 测试 UTF-8字节序列正序、逆序迭代字符的一致性
+    /// * 📄无效字节、不完整的多字节序列、多余的后续字节
+    #[test]
+    fn test_bytes_iter_chars_consistent() {
+        let cases: [&[u8]; 7] = [
+            b"",
+            b"\xFF",
+            b"\xE4\xB8",
+            b"\xE4\xE4\xB8\xAD",
+            b"\xB8\xAD\xE4\xB8\xAD",
+            b"a\xF0\x9F\x98b\xF0\x9F\x98\x80",
+            b"\xC0\x80\xED\xA0\x80\xF4\x90\x80\x80",
+        ];
+        for bytes in cases {
+            let mut reversed = bytes.iter_chars_rev().collect::<Vec<_>>();
+            reversed.reverse();
+            asserts! {
+                bytes.iter_chars().collect::<Vec<_>>() => reversed,
+            }
+        }
+        // 有效字符不受影响
+        asserts! {
+            b"\xE4\xE4\xB8\xAD".iter_chars().collect::<String>() => "\u{FFFD}中",
+        }
+    }
 }
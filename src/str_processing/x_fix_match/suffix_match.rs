@@ -131,11 +131,73 @@ impl<T> SuffixMatchDictPair<T> {
         None
     }
 
+    /// 根据后缀获取「关联内容」
+    /// * 🚩找不到⇒[`None`]
+    pub fn get(&self, suffix: &SuffixStr) -> Option<&T> {
+        let index = self.search(suffix).ok()?;
+        Some(Self::get_associated_from_term(&self.suffixes[index]))
+    }
+
+    /// 根据后缀获取「关联内容」的可变引用
+    /// * 📌只开放「关联内容」的修改：后缀决定了条目顺序，不能在原地修改
+    pub fn get_mut(&mut self, suffix: &SuffixStr) -> Option<&mut T> {
+        let index = self.search(suffix).ok()?;
+        Some(&mut self.suffixes[index].0)
+    }
+
+    /// 替换后缀所对应的「关联内容」
+    /// * 🚩有此后缀⇒替换并返回旧的关联内容
+    /// * 🚩无此后缀⇒不插入，返回[`None`]
+    pub fn replace_associated(&mut self, suffix: &SuffixStr, associated: T) -> Option<T> {
+        self.get_mut(suffix)
+            .map(|old| std::mem::replace(old, associated))
+    }
+
+    /// 根据后缀移除条目
+    /// * 🚩返回被移除的条目
+    /// * 📌移除不影响其它条目的顺序
+    pub fn remove(&mut self, suffix: &SuffixStr) -> Option<SuffixTerm<T>> {
+        let index = self.search(suffix).ok()?;
+        Some(self.suffixes.remove(index))
+    }
+
+    /// 只保留满足条件的条目
+    /// * 📌类似[`Vec::retain`]，保持原有顺序
+    pub fn retain(&mut self, f: impl FnMut(&SuffixTerm<T>) -> bool) {
+        self.suffixes.retain(f)
+    }
+
+    /// 合并另一个后缀配对字典
+    /// * 📌「冲突」即「后缀相同」，按[`MergePolicy`]处理
+    /// * 🚩[`MergePolicy::Error`]策略下，先检查所有冲突再合并：报错时自身不变
+    pub fn merge(&mut self, other: Self, policy: MergePolicy) -> Result<(), MergeConflict> {
+        if policy == MergePolicy::Error {
+            if let Some(term) = other
+                .suffixes
+                .iter()
+                .find(|term| self.search(&term.1).is_ok())
+            {
+                return Err(MergeConflict {
+                    x_fix: term.1.clone(),
+                });
+            }
+        }
+        for term in other.suffixes {
+            match self.search(&term.1) {
+                // 冲突⇒按策略覆盖 | 「报错」策略已在上边排除
+                Ok(index) if policy == MergePolicy::Overwrite => self.suffixes[index] = term,
+                Ok(..) => {}
+                Err(index) => self.suffixes.insert(index, term),
+            }
+        }
+        Ok(())
+    }
+
     /// （前后缀无关）以特殊顺序迭代「词缀」
     /// * 🎯统一「前后缀匹配」的迭代逻辑
     /// * 🚩总是按照「字典顺序」倒序遍历：**长度从长到短**
     #[inline(always)]
-    pub fn iter_terms<'a>(&'a self) -> impl Iterator<Item = &SuffixTerm<T>> + 'a
+    pub fn iter_terms<'a>(&'a self) -> impl Iterator<Item = &'a SuffixTerm<T>> + 'a
     where
         String: 'a,
     {
@@ -161,6 +223,16 @@ impl<T> SuffixMatchDictPair<T> {
     }
 }
 
+/// 批量插入条目
+/// * 🚩逐个调用[`SuffixMatchDictPair::insert`]，后缀已有的条目会被跳过
+impl<T> Extend<SuffixTerm<T>> for SuffixMatchDictPair<T> {
+    fn extend<I: IntoIterator<Item = SuffixTerm<T>>>(&mut self, iter: I) {
+        for term in iter {
+            self.insert(term);
+        }
+    }
+}
+
 /// 实现「后缀匹配」逻辑
 impl<T> SuffixMatch<SuffixTerm<T>> for SuffixMatchDictPair<T> {
     // 下面的方法直接进行「特化重定向」处理 //
@@ -180,7 +252,7 @@ impl<T> SuffixMatch<SuffixTerm<T>> for SuffixMatchDictPair<T> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{show, test_match_suffix};
+    use crate::{asserts, show, test_match_suffix};
 
     /// 测试/边缘
     #[test]
@@ -277,4 +349,51 @@ mod tests {
             "「A是B」。将来" => Some("")
         }
     }
+
+    /// 测试/移除、修改、合并
+    #[test]
+    fn test_update_merge() {
+        let base: SuffixMatchDictPair<String> = suffix_match_dict_pair!(
+            "" => r":|:"
+            "" => r":/:"
+            "%" => "%"
+        );
+        let mut d = base.clone();
+        asserts! {
+            d.replace_associated("%", "t=".into()) => Some("%".into()),
+            d.get("%") => Some(&"t=".into()),
+            d.remove(":/:") => Some(("".into(), ":/:".into())),
+            d.match_suffix("A. :/:") => None,
+            d.match_suffix("A. :|:").map(|(l, _)| l.as_str()) => Some(""),
+        }
+        if let Some(l) = d.get_mut(":|:") {
+            l.push('!');
+        }
+        d.retain(|(_, r)| r != "%");
+        asserts! {
+            d.get(":|:") => Some(&"!".into()),
+            d.iter_terms().count() => 1,
+        }
+        // 合并
+        let variant: SuffixMatchDictPair<String> = suffix_match_dict_pair!(
+            "!" => r":/:"
+            "" => r":\:"
+        );
+        let mut overwrite = base.clone();
+        overwrite
+            .merge(variant.clone(), MergePolicy::Overwrite)
+            .unwrap();
+        let mut error = base;
+        asserts! {
+            overwrite.get(":/:") => Some(&"!".into()),
+            overwrite.match_suffix(r"A. :\:").map(|(l, _)| l.as_str()) => Some(""),
+            error.merge(variant, MergePolicy::Error) => Err(MergeConflict { x_fix: ":/:".into() }),
+        }
+        // 批量插入
+        let mut d = SuffixMatchDictPair::default();
+        d.extend([(1, "a".into()), (2, "aa".into())]);
+        asserts! {
+            d.match_suffix("_aa").map(|(i, _)| *i) => Some(2),
+        }
+    }
 }
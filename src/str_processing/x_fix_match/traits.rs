@@ -1,7 +1,7 @@
 //! 前后缀匹配的抽象特征
 //! * 🎯用于后续可能「同时实现『前缀匹配』与『后缀匹配』两者」的情况
 
-use crate::str_processing::char_slices::*;
+use super::{suggest, CharSeq, CharSeqRev, EditMetric, Suggestion};
 use std::cmp::Ordering;
 
 /// 定义「前缀」
@@ -22,6 +22,61 @@ pub(super) type PrefixStr = str;
 pub(super) type Suffix = String;
 pub(super) type SuffixStr = str;
 
+/// 词缀匹配的结果
+/// * 🎯在返回「匹配到的条目」的同时，携带「匹配长度」
+///   * 📄便于调用者自行消歧：`-->`与`--`都能匹配`-->A`时，按长度取舍
+/// * 📌`len`为字节长度，可直接用于切分字符串；`n_chars`为字符个数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct XFixMatch<'a, Term> {
+    /// 匹配到的条目
+    pub term: &'a Term,
+    /// 匹配到的词缀之字节长度
+    pub len: usize,
+    /// 匹配到的词缀之字符个数
+    pub n_chars: usize,
+}
+
+impl<'a, Term> XFixMatch<'a, Term> {
+    /// 从「条目」与「词缀」构造
+    pub fn new(term: &'a Term, x_fix: &str) -> Self {
+        Self {
+            term,
+            len: x_fix.len(),
+            n_chars: x_fix.chars().count(),
+        }
+    }
+}
+
+/// 合并字典时的「冲突策略」
+/// * 🎯从「基础方言」派生「方言变体」时，覆盖其中的少数条目
+/// * 📌「冲突」指「两字典中有相同的词缀，但条目不同」
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// 保留自身已有的条目，忽略传入字典中的冲突条目
+    KeepExisting,
+    /// 以传入字典中的条目覆盖自身已有的条目
+    Overwrite,
+    /// 遇到冲突即报错
+    /// * 📌报错时**不修改**自身
+    Error,
+}
+
+/// 合并字典时的「冲突」错误
+/// * 🎯在[`MergePolicy::Error`]策略下返回
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeConflict {
+    /// 发生冲突的词缀
+    pub x_fix: String,
+}
+
+impl std::fmt::Display for MergeConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "合并冲突：词缀「{}」已存在", self.x_fix)
+    }
+}
+
+impl std::error::Error for MergeConflict {}
+
 /// 前缀匹配（抽象特征）
 /// * 🎯用于存储前缀，封装如下两个逻辑
 ///   * 前缀匹配→返回被匹配项：用于匹配如「原子词项前缀」的一次性匹配
@@ -84,17 +139,111 @@ pub trait PrefixMatch<PrefixTerm> {
             .find(|&term| to_match.starts_with(Self::get_prefix_from_term(term)))
     }
 
-    /// 开启前缀匹配（字符迭代器版本）
-    /// * 🎯封装「前缀匹配」逻辑，用于「字符迭代器」兼「字符数组切片」
-    ///   * ❌字符迭代器：暂时不需要 & 还是得转数组
+    /// 开启前缀匹配（字符序列版本）
+    /// * 🎯封装「前缀匹配」逻辑，通用于各种「字符序列」
+    ///   * 📄字符数组切片、`VecDeque<char>`（如`BufferIterator`的缓冲区）、UTF-8字节
+    /// * 📌逐个字符比对（[`crate::StartsWithStr`]），不会分配新字符串
     /// * 🚩迭代、扫描、匹配
-    ///   * 1. 从一个字符串开始
+    ///   * 1. 从一个字符序列开始
     ///   * 2. 然后扫描自身所有前缀（字串从长到短）
     ///   * 3. 最后（若成功）返回匹配到的前缀所对应的「前缀条目」
     #[inline(always)]
+    fn match_prefix_chars<S: CharSeq + ?Sized>(&self, to_match: &S) -> Option<&PrefixTerm> {
+        self.prefix_terms()
+            .find(|&term| to_match.starts_with_str(Self::get_prefix_from_term(term)))
+    }
+
+    /// 前缀匹配（字符序列版本，最长匹配）
+    /// * 🎯在返回条目的同时，返回字符序列中被匹配的长度
+    ///   * 📄跳过被匹配的字符时，以此处的[`XFixMatch::n_chars`]为准
+    /// * 📄参见[`Self::match_prefix_longest`]
+    fn match_prefix_chars_longest<S: CharSeq + ?Sized>(
+        &self,
+        to_match: &S,
+    ) -> Option<XFixMatch<'_, PrefixTerm>> {
+        self.prefix_terms()
+            .map(|term| (term, Self::get_prefix_from_term(term)))
+            .filter(|(_, prefix)| to_match.starts_with_str(prefix))
+            .max_by_key(|(_, prefix)| prefix.len())
+            .map(|(term, prefix)| XFixMatch::new(term, prefix))
+    }
+
+    /// 判断「在字符序列之后追加字符」是否可能改变前缀匹配的结果
+    /// * 🎯供「逐字符拉取」的匹配（如`BufferIterator`的字典匹配）判断何时停止预读
+    ///   * 📌返回`false`时，[`Self::match_prefix_chars`]的结果已经确定
+    /// * 🚩默认实现：是否有前缀「以字符序列开头，且比它更长」
+    fn prefix_needs_more_chars<S: CharSeq + ?Sized>(&self, to_match: &S) -> bool {
+        self.prefix_terms().any(|term| {
+            let mut prefix = Self::get_prefix_from_term(term).chars();
+            to_match.iter_chars().all(|c| prefix.next() == Some(c)) && prefix.next().is_some()
+        })
+    }
+
+    /// 开启前缀匹配（字符数组切片版本）
+    /// * 📌[`Self::match_prefix_chars`]的特例
+    #[inline(always)]
     fn match_prefix_char_slice(&self, to_match: &[char]) -> Option<&PrefixTerm> {
+        self.match_prefix_chars(to_match)
+    }
+
+    /// 前缀匹配（所有匹配）
+    /// * 🎯处理有歧义的方言：返回所有候选，交由调用者取舍
+    /// * 🚩按[`Self::prefix_terms`]的顺序（从长到短）迭代所有匹配
+    fn match_prefix_all<'a, 's>(
+        &'a self,
+        to_match: &'s str,
+    ) -> impl Iterator<Item = XFixMatch<'a, PrefixTerm>>
+    where
+        PrefixTerm: 'a,
+    {
+        self.prefix_terms().filter_map(move |term| {
+            let prefix = Self::get_prefix_from_term(term);
+            match to_match.starts_with(prefix) {
+                true => Some(XFixMatch::new(term, prefix)),
+                false => None,
+            }
+        })
+    }
+
+    /// 前缀匹配（最长匹配）
+    /// * 🎯不依赖[`Self::prefix_terms`]的迭代顺序，显式取最长的前缀
+    fn match_prefix_longest(&self, to_match: &str) -> Option<XFixMatch<'_, PrefixTerm>> {
+        // * 📌前缀不重复，故同一字符串的不同前缀长度必不相同
         self.prefix_terms()
-            .find(|&term| char_slice_has_prefix(to_match, Self::get_prefix_from_term(term)))
+            .map(|term| (term, Self::get_prefix_from_term(term)))
+            .filter(|(_, prefix)| to_match.starts_with(prefix))
+            .max_by_key(|(_, prefix)| prefix.len())
+            .map(|(term, prefix)| XFixMatch::new(term, prefix))
+    }
+
+    /// 前缀匹配（最短匹配）
+    /// * 🎯不依赖[`Self::prefix_terms`]的迭代顺序，显式取最短的前缀
+    fn match_prefix_shortest(&self, to_match: &str) -> Option<XFixMatch<'_, PrefixTerm>> {
+        self.prefix_terms()
+            .map(|term| (term, Self::get_prefix_from_term(term)))
+            .filter(|(_, prefix)| to_match.starts_with(prefix))
+            .min_by_key(|(_, prefix)| prefix.len())
+            .map(|(term, prefix)| XFixMatch::new(term, prefix))
+    }
+
+    /// 模糊查找前缀：找出与输入最接近的至多`k`个前缀
+    /// * 🎯为「未识别的前缀」生成「您是不是想输入……」的建议
+    /// * 📌使用默认度量[`EditMetric::Damerau`]；仅保留编辑距离不超过`max_distance`的条目
+    /// * 📄参见[`suggest`]
+    fn suggest_prefix(
+        &self,
+        input: &str,
+        max_distance: usize,
+        k: usize,
+    ) -> Vec<Suggestion<'_, PrefixTerm>> {
+        suggest(
+            self.prefix_terms(),
+            Self::get_prefix_from_term,
+            input,
+            EditMetric::default(),
+            max_distance,
+            k,
+        )
     }
 }
 
@@ -166,16 +315,95 @@ pub trait SuffixMatch<SuffixTerm> {
             .find(|&term| to_match.ends_with(Self::get_suffix_from_term(term)))
     }
 
-    /// 开启后缀匹配（字符迭代器版本）
-    /// * 🎯封装「后缀匹配」逻辑，用于「字符迭代器」兼「字符数组切片」
-    ///   * ❌字符迭代器：暂时不需要 & 还是得转数组
+    /// 开启后缀匹配（字符序列版本）
+    /// * 🎯封装「后缀匹配」逻辑，通用于各种「字符序列」
+    ///   * 📄字符数组切片、`VecDeque<char>`、UTF-8字节
+    /// * 📌逐个字符比对（[`crate::EndsWithStr`]），不会分配新字符串
     /// * 🚩迭代、扫描、匹配
-    ///   * 1. 从一个字符串开始
+    ///   * 1. 从一个字符序列开始
     ///   * 2. 然后扫描自身所有后缀（字串从长到短）
     ///   * 3. 最后（若成功）返回匹配到的后缀所对应的「后缀条目」
     #[inline(always)]
+    fn match_suffix_chars<S: CharSeqRev + ?Sized>(&self, to_match: &S) -> Option<&SuffixTerm> {
+        self.suffix_terms()
+            .find(|&term| to_match.ends_with_str(Self::get_suffix_from_term(term)))
+    }
+
+    /// 后缀匹配（字符序列版本，最长匹配）
+    /// * 📄参见[`PrefixMatch::match_prefix_chars_longest`]
+    fn match_suffix_chars_longest<S: CharSeqRev + ?Sized>(
+        &self,
+        to_match: &S,
+    ) -> Option<XFixMatch<'_, SuffixTerm>> {
+        self.suffix_terms()
+            .map(|term| (term, Self::get_suffix_from_term(term)))
+            .filter(|(_, suffix)| to_match.ends_with_str(suffix))
+            .max_by_key(|(_, suffix)| suffix.len())
+            .map(|(term, suffix)| XFixMatch::new(term, suffix))
+    }
+
+    /// 开启后缀匹配（字符数组切片版本）
+    /// * 📌[`Self::match_suffix_chars`]的特例
+    #[inline(always)]
     fn match_suffix_char_slice(&self, to_match: &[char]) -> Option<&SuffixTerm> {
+        self.match_suffix_chars(to_match)
+    }
+
+    /// 后缀匹配（所有匹配）
+    /// * 🎯处理有歧义的方言：返回所有候选，交由调用者取舍
+    /// * 🚩按[`Self::suffix_terms`]的顺序（从长到短）迭代所有匹配
+    fn match_suffix_all<'a, 's>(
+        &'a self,
+        to_match: &'s str,
+    ) -> impl Iterator<Item = XFixMatch<'a, SuffixTerm>>
+    where
+        SuffixTerm: 'a,
+    {
+        self.suffix_terms().filter_map(move |term| {
+            let suffix = Self::get_suffix_from_term(term);
+            match to_match.ends_with(suffix) {
+                true => Some(XFixMatch::new(term, suffix)),
+                false => None,
+            }
+        })
+    }
+
+    /// 后缀匹配（最长匹配）
+    /// * 🎯不依赖[`Self::suffix_terms`]的迭代顺序，显式取最长的后缀
+    fn match_suffix_longest(&self, to_match: &str) -> Option<XFixMatch<'_, SuffixTerm>> {
+        // * 📌后缀不重复，故同一字符串的不同后缀长度必不相同
+        self.suffix_terms()
+            .map(|term| (term, Self::get_suffix_from_term(term)))
+            .filter(|(_, suffix)| to_match.ends_with(suffix))
+            .max_by_key(|(_, suffix)| suffix.len())
+            .map(|(term, suffix)| XFixMatch::new(term, suffix))
+    }
+
+    /// 后缀匹配（最短匹配）
+    /// * 🎯不依赖[`Self::suffix_terms`]的迭代顺序，显式取最短的后缀
+    fn match_suffix_shortest(&self, to_match: &str) -> Option<XFixMatch<'_, SuffixTerm>> {
         self.suffix_terms()
-            .find(|&term| char_slice_has_suffix(to_match, Self::get_suffix_from_term(term)))
+            .map(|term| (term, Self::get_suffix_from_term(term)))
+            .filter(|(_, suffix)| to_match.ends_with(suffix))
+            .min_by_key(|(_, suffix)| suffix.len())
+            .map(|(term, suffix)| XFixMatch::new(term, suffix))
+    }
+
+    /// 模糊查找后缀：找出与输入最接近的至多`k`个后缀
+    /// * 📄参见[`PrefixMatch::suggest_prefix`]
+    fn suggest_suffix(
+        &self,
+        input: &str,
+        max_distance: usize,
+        k: usize,
+    ) -> Vec<Suggestion<'_, SuffixTerm>> {
+        suggest(
+            self.suffix_terms(),
+            Self::get_suffix_from_term,
+            input,
+            EditMetric::default(),
+            max_distance,
+            k,
+        )
     }
 }
@@ -0,0 +1,665 @@
+//! 基于「字典树」的前后缀匹配字典
+//! * 🎯加速「词缀较多」时的前后缀匹配
+//!   * 📄Narsese方言中动辄几十个的「陈述系词」「复合词项连接词」
+//!   * 📌线性扫描的复杂度为`O(词缀数×词缀长度)`，字典树只需`O(待匹配串长度)`
+//! * 📌可直接替代对应的「线性字典」
+//!   * [`XFixMatchDict`](super::XFixMatchDict) ⇒ [`XFixTrieDict`]
+//!   * [`PrefixMatchDictPair`](super::PrefixMatchDictPair) ⇒ [`PrefixTrieDictPair`]
+//!   * [`SuffixMatchDictPair`](super::SuffixMatchDictPair) ⇒ [`SuffixTrieDictPair`]
+//! * 🚩后缀字典树以「倒序字符」为键
+
+use super::{
+    traits::*, CharSeq, CharSeqRev, PrefixMatchDictPair, SuffixMatchDictPair, XFixMatchDict,
+};
+use std::{cmp::Ordering, collections::HashMap};
+
+/// 字符字典树的节点
+#[derive(Debug, Clone, Default)]
+struct CharTrieNode {
+    /// 子节点
+    children: HashMap<char, usize>,
+    /// 在此结束的条目（索引）
+    term: Option<usize>,
+}
+
+/// 字符字典树
+/// * 🚩所有节点存储在数组中，以索引互相引用
+/// * 📌只存储「条目索引」，条目本身由外部结构存储
+#[derive(Debug, Clone)]
+struct CharTrie {
+    /// 所有节点 | 第一个为根节点
+    nodes: Vec<CharTrieNode>,
+}
+
+impl Default for CharTrie {
+    fn default() -> Self {
+        Self {
+            nodes: vec![CharTrieNode::default()],
+        }
+    }
+}
+
+impl CharTrie {
+    /// 插入「键⇒条目索引」
+    /// * 🚩键已存在⇒不覆盖，返回已有的条目索引
+    fn insert(&mut self, key: impl Iterator<Item = char>, term: usize) -> Result<(), usize> {
+        let mut node = 0;
+        for c in key {
+            node = match self.nodes[node].children.get(&c) {
+                Some(&next) => next,
+                None => {
+                    let next = self.nodes.len();
+                    self.nodes.push(CharTrieNode::default());
+                    self.nodes[node].children.insert(c, next);
+                    next
+                }
+            };
+        }
+        match self.nodes[node].term {
+            Some(existed) => Err(existed),
+            None => {
+                self.nodes[node].term = Some(term);
+                Ok(())
+            }
+        }
+    }
+
+    /// 精确查找键
+    fn get(&self, key: impl Iterator<Item = char>) -> Option<usize> {
+        let mut node = 0;
+        for c in key {
+            node = *self.nodes[node].children.get(&c)?;
+        }
+        self.nodes[node].term
+    }
+
+    /// 查找「输入的最长前缀」所对应的条目
+    /// * 🚩沿输入走到无路可走，记录最后一个「有条目」的节点
+    /// * 📌复杂度：`O(输入长度)`
+    fn longest_match(&self, input: impl Iterator<Item = char>) -> Option<usize> {
+        let mut node = 0;
+        let mut matched = self.nodes[node].term;
+        for c in input {
+            match self.nodes[node].children.get(&c) {
+                Some(&next) => node = next,
+                None => break,
+            }
+            if let Some(term) = self.nodes[node].term {
+                matched = Some(term);
+            }
+        }
+        matched
+    }
+
+    /// 判断「输入之后追加字符」能否匹配到更长的键
+    /// * 🚩沿输入走到末尾，看是否还有子节点
+    fn has_longer(&self, input: impl Iterator<Item = char>) -> bool {
+        let mut node = 0;
+        for c in input {
+            match self.nodes[node].children.get(&c) {
+                Some(&next) => node = next,
+                None => return false,
+            }
+        }
+        !self.nodes[node].children.is_empty()
+    }
+}
+
+/// 「从长到短」的词缀顺序
+/// * 🎯使[`PrefixMatch::prefix_terms`]、[`SuffixMatch::suffix_terms`]保持「长的先来」
+/// * 🚩字符数从多到少，相同的再按字典顺序倒序
+fn cmp_longest_first(a: &str, b: &str) -> Ordering {
+    b.chars()
+        .count()
+        .cmp(&a.chars().count())
+        .then_with(|| b.cmp(a))
+}
+
+/// 将新条目的索引按「从长到短」插入顺序表
+fn insert_ordered<'a>(ordered: &mut Vec<usize>, x_fix_of: impl Fn(usize) -> &'a str, new: usize) {
+    let new_x_fix = x_fix_of(new);
+    let index = ordered
+        .binary_search_by(|&existed| cmp_longest_first(x_fix_of(existed), new_x_fix))
+        .unwrap_or_else(|index| index);
+    ordered.insert(index, new);
+}
+
+/// 前后缀匹配字典（字典树版本）
+/// * 🎯作为[`XFixMatchDict`]的替代
+/// * 🚩同时维护「前缀字典树」与「后缀字典树」
+#[derive(Debug, Clone, Default)]
+pub struct XFixTrieDict {
+    /// 所有词缀 | 插入顺序
+    x_fixes: Vec<String>,
+    /// 「从长到短」的词缀索引
+    ordered: Vec<usize>,
+    /// 前缀字典树
+    prefix_trie: CharTrie,
+    /// 后缀字典树 | 以倒序字符为键
+    suffix_trie: CharTrie,
+}
+
+impl XFixTrieDict {
+    /// 构造函数
+    /// * 支持从任何「元素为『可转换为字符串』的可迭代对象」中转换
+    pub fn new(x_fixes: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        let mut dict = Self::default();
+        for x_fix in x_fixes {
+            dict.insert(x_fix.into());
+        }
+        dict
+    }
+
+    /// 获取词缀个数
+    pub fn len(&self) -> usize {
+        self.x_fixes.len()
+    }
+
+    /// 判断是否没有词缀
+    pub fn is_empty(&self) -> bool {
+        self.x_fixes.is_empty()
+    }
+
+    /// 判断「是否已有一个词缀」
+    pub fn has(&self, x_fix: &str) -> bool {
+        self.prefix_trie.get(x_fix.chars()).is_some()
+    }
+
+    /// 插入一个词缀
+    /// * 🚩已有⇒跳过
+    pub fn insert(&mut self, x_fix: String) {
+        let index = self.x_fixes.len();
+        if self.prefix_trie.insert(x_fix.chars(), index).is_err() {
+            return;
+        }
+        // * 📌前缀能插入，后缀必定也能插入
+        let _ = self.suffix_trie.insert(x_fix.chars().rev(), index);
+        self.x_fixes.push(x_fix);
+        let x_fixes = &self.x_fixes;
+        insert_ordered(&mut self.ordered, |i| &x_fixes[i], index);
+    }
+
+    /// 以「从长到短」的顺序迭代「词缀」
+    pub fn iter_x_fixes(&self) -> impl Iterator<Item = &String> + '_ {
+        self.ordered.iter().map(|&i| &self.x_fixes[i])
+    }
+}
+
+/// 从「线性」的词缀匹配字典转换
+impl From<&XFixMatchDict> for XFixTrieDict {
+    fn from(dict: &XFixMatchDict) -> Self {
+        Self::new(dict.iter_x_fixes().cloned())
+    }
+}
+
+/// 实现「前缀匹配」
+/// * 🚩匹配逻辑交由字典树完成
+impl PrefixMatch<String> for XFixTrieDict {
+    fn get_prefix_from_term(term: &String) -> &PrefixStr {
+        term
+    }
+
+    fn prefix_terms<'a>(&'a self) -> impl Iterator<Item = &'a String> + 'a
+    where
+        String: 'a,
+    {
+        self.iter_x_fixes()
+    }
+
+    fn match_prefix(&self, to_match: &str) -> Option<&String> {
+        self.prefix_trie
+            .longest_match(to_match.chars())
+            .map(|i| &self.x_fixes[i])
+    }
+
+    fn match_prefix_chars<S: CharSeq + ?Sized>(&self, to_match: &S) -> Option<&String> {
+        self.prefix_trie
+            .longest_match(to_match.iter_chars())
+            .map(|i| &self.x_fixes[i])
+    }
+
+    fn prefix_needs_more_chars<S: CharSeq + ?Sized>(&self, to_match: &S) -> bool {
+        self.prefix_trie.has_longer(to_match.iter_chars())
+    }
+
+    fn match_prefix_longest(&self, to_match: &str) -> Option<XFixMatch<'_, String>> {
+        self.prefix_trie.longest_match(to_match.chars()).map(|i| {
+            let term = &self.x_fixes[i];
+            XFixMatch::new(term, term)
+        })
+    }
+
+    fn match_prefix_chars_longest<S: CharSeq + ?Sized>(
+        &self,
+        to_match: &S,
+    ) -> Option<XFixMatch<'_, String>> {
+        self.prefix_trie
+            .longest_match(to_match.iter_chars())
+            .map(|i| {
+                let term = &self.x_fixes[i];
+                XFixMatch::new(term, term)
+            })
+    }
+}
+
+/// 实现「后缀匹配」
+/// * 🚩匹配逻辑交由（倒序的）字典树完成
+impl SuffixMatch<String> for XFixTrieDict {
+    fn get_suffix_from_term(term: &String) -> &SuffixStr {
+        term
+    }
+
+    fn suffix_terms<'a>(&'a self) -> impl Iterator<Item = &'a String> + 'a
+    where
+        String: 'a,
+    {
+        self.iter_x_fixes()
+    }
+
+    fn match_suffix(&self, to_match: &str) -> Option<&String> {
+        self.suffix_trie
+            .longest_match(to_match.chars().rev())
+            .map(|i| &self.x_fixes[i])
+    }
+
+    fn match_suffix_chars<S: CharSeqRev + ?Sized>(&self, to_match: &S) -> Option<&String> {
+        self.suffix_trie
+            .longest_match(to_match.iter_chars_rev())
+            .map(|i| &self.x_fixes[i])
+    }
+
+    fn match_suffix_longest(&self, to_match: &str) -> Option<XFixMatch<'_, String>> {
+        self.suffix_trie
+            .longest_match(to_match.chars().rev())
+            .map(|i| {
+                let term = &self.x_fixes[i];
+                XFixMatch::new(term, term)
+            })
+    }
+
+    fn match_suffix_chars_longest<S: CharSeqRev + ?Sized>(
+        &self,
+        to_match: &S,
+    ) -> Option<XFixMatch<'_, String>> {
+        self.suffix_trie
+            .longest_match(to_match.iter_chars_rev())
+            .map(|i| {
+                let term = &self.x_fixes[i];
+                XFixMatch::new(term, term)
+            })
+    }
+}
+
+/// 前缀配对字典（字典树版本）
+/// * 🎯作为[`PrefixMatchDictPair`]的替代
+/// * 📌格式：`条目=(前缀, 关联内容)`
+#[derive(Debug, Clone)]
+pub struct PrefixTrieDictPair<T> {
+    /// 所有条目 | 插入顺序
+    terms: Vec<(String, T)>,
+    /// 「从长到短」的条目索引
+    ordered: Vec<usize>,
+    /// 前缀字典树
+    trie: CharTrie,
+}
+
+/// 实现「默认构造函数」
+impl<T> Default for PrefixTrieDictPair<T> {
+    fn default() -> Self {
+        Self {
+            terms: Vec::new(),
+            ordered: Vec::new(),
+            trie: CharTrie::default(),
+        }
+    }
+}
+
+impl<T> PrefixTrieDictPair<T> {
+    /// 构造函数
+    /// * 📌格式：`条目=(前缀, 关联内容)`
+    pub fn new(terms: impl IntoIterator<Item = (impl Into<String>, T)>) -> Self {
+        let mut dict = Self::default();
+        for (prefix, associated) in terms {
+            dict.insert((prefix.into(), associated));
+        }
+        dict
+    }
+
+    /// 获取条目个数
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// 判断是否没有条目
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// 判断「是否已有一个前缀」
+    pub fn has(&self, prefix: &str) -> bool {
+        self.trie.get(prefix.chars()).is_some()
+    }
+
+    /// 根据前缀精确查找条目
+    pub fn get(&self, prefix: &str) -> Option<&(String, T)> {
+        self.trie.get(prefix.chars()).map(|i| &self.terms[i])
+    }
+
+    /// 插入一个条目
+    /// * 🚩前缀已有⇒不插入，返回[`None`]
+    /// * 🚩返回「插入后条目的索引」
+    ///   * 📌索引为插入顺序，不随后续插入而变化
+    pub fn insert(&mut self, term: (String, T)) -> Option<usize> {
+        let index = self.terms.len();
+        self.trie.insert(term.0.chars(), index).ok()?;
+        self.terms.push(term);
+        let terms = &self.terms;
+        insert_ordered(&mut self.ordered, |i| &terms[i].0, index);
+        Some(index)
+    }
+
+    /// 以「从长到短」的顺序迭代条目
+    pub fn iter_terms(&self) -> impl Iterator<Item = &(String, T)> + '_ {
+        self.ordered.iter().map(|&i| &self.terms[i])
+    }
+}
+
+/// 从「线性」的前缀配对字典转换
+impl<T: Clone> From<&PrefixMatchDictPair<T>> for PrefixTrieDictPair<T> {
+    fn from(dict: &PrefixMatchDictPair<T>) -> Self {
+        Self::new(dict.iter_terms().cloned())
+    }
+}
+
+/// 实现「前缀匹配」
+/// * 🚩匹配逻辑交由字典树完成
+impl<T> PrefixMatch<(String, T)> for PrefixTrieDictPair<T> {
+    fn get_prefix_from_term(term: &(String, T)) -> &PrefixStr {
+        &term.0
+    }
+
+    fn prefix_terms<'a>(&'a self) -> impl Iterator<Item = &'a (String, T)> + 'a
+    where
+        (String, T): 'a,
+    {
+        self.iter_terms()
+    }
+
+    fn match_prefix(&self, to_match: &str) -> Option<&(String, T)> {
+        self.trie
+            .longest_match(to_match.chars())
+            .map(|i| &self.terms[i])
+    }
+
+    fn match_prefix_chars<S: CharSeq + ?Sized>(&self, to_match: &S) -> Option<&(String, T)> {
+        self.trie
+            .longest_match(to_match.iter_chars())
+            .map(|i| &self.terms[i])
+    }
+
+    fn prefix_needs_more_chars<S: CharSeq + ?Sized>(&self, to_match: &S) -> bool {
+        self.trie.has_longer(to_match.iter_chars())
+    }
+
+    fn match_prefix_longest(&self, to_match: &str) -> Option<XFixMatch<'_, (String, T)>> {
+        self.trie.longest_match(to_match.chars()).map(|i| {
+            let term = &self.terms[i];
+            XFixMatch::new(term, &term.0)
+        })
+    }
+
+    fn match_prefix_chars_longest<S: CharSeq + ?Sized>(
+        &self,
+        to_match: &S,
+    ) -> Option<XFixMatch<'_, (String, T)>> {
+        self.trie.longest_match(to_match.iter_chars()).map(|i| {
+            let term = &self.terms[i];
+            XFixMatch::new(term, &term.0)
+        })
+    }
+}
+
+/// 后缀配对字典（字典树版本）
+/// * 🎯作为[`SuffixMatchDictPair`]的替代
+/// * 📌格式：`条目=(关联内容, 后缀)`
+/// * 🚩字典树以「倒序字符」为键
+#[derive(Debug, Clone)]
+pub struct SuffixTrieDictPair<T> {
+    /// 所有条目 | 插入顺序
+    terms: Vec<(T, String)>,
+    /// 「从长到短」的条目索引
+    ordered: Vec<usize>,
+    /// 后缀字典树 | 以倒序字符为键
+    trie: CharTrie,
+}
+
+/// 实现「默认构造函数」
+impl<T> Default for SuffixTrieDictPair<T> {
+    fn default() -> Self {
+        Self {
+            terms: Vec::new(),
+            ordered: Vec::new(),
+            trie: CharTrie::default(),
+        }
+    }
+}
+
+impl<T> SuffixTrieDictPair<T> {
+    /// 构造函数
+    /// * 📌格式：`条目=(关联内容, 后缀)`
+    pub fn new(terms: impl IntoIterator<Item = (T, impl Into<String>)>) -> Self {
+        let mut dict = Self::default();
+        for (associated, suffix) in terms {
+            dict.insert((associated, suffix.into()));
+        }
+        dict
+    }
+
+    /// 获取条目个数
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// 判断是否没有条目
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// 判断「是否已有一个后缀」
+    pub fn has(&self, suffix: &str) -> bool {
+        self.trie.get(suffix.chars().rev()).is_some()
+    }
+
+    /// 根据后缀精确查找条目
+    pub fn get(&self, suffix: &str) -> Option<&(T, String)> {
+        self.trie.get(suffix.chars().rev()).map(|i| &self.terms[i])
+    }
+
+    /// 插入一个条目
+    /// * 🚩后缀已有⇒不插入，返回[`None`]
+    /// * 🚩返回「插入后条目的索引」
+    ///   * 📌索引为插入顺序，不随后续插入而变化
+    pub fn insert(&mut self, term: (T, String)) -> Option<usize> {
+        let index = self.terms.len();
+        self.trie.insert(term.1.chars().rev(), index).ok()?;
+        self.terms.push(term);
+        let terms = &self.terms;
+        insert_ordered(&mut self.ordered, |i| &terms[i].1, index);
+        Some(index)
+    }
+
+    /// 以「从长到短」的顺序迭代条目
+    pub fn iter_terms(&self) -> impl Iterator<Item = &(T, String)> + '_ {
+        self.ordered.iter().map(|&i| &self.terms[i])
+    }
+}
+
+/// 从「线性」的后缀配对字典转换
+impl<T: Clone> From<&SuffixMatchDictPair<T>> for SuffixTrieDictPair<T> {
+    fn from(dict: &SuffixMatchDictPair<T>) -> Self {
+        Self::new(dict.iter_terms().cloned())
+    }
+}
+
+/// 实现「后缀匹配」
+/// * 🚩匹配逻辑交由（倒序的）字典树完成
+impl<T> SuffixMatch<(T, String)> for SuffixTrieDictPair<T> {
+    fn get_suffix_from_term(term: &(T, String)) -> &SuffixStr {
+        &term.1
+    }
+
+    fn suffix_terms<'a>(&'a self) -> impl Iterator<Item = &'a (T, String)> + 'a
+    where
+        (T, String): 'a,
+    {
+        self.iter_terms()
+    }
+
+    fn match_suffix(&self, to_match: &str) -> Option<&(T, String)> {
+        self.trie
+            .longest_match(to_match.chars().rev())
+            .map(|i| &self.terms[i])
+    }
+
+    fn match_suffix_chars<S: CharSeqRev + ?Sized>(&self, to_match: &S) -> Option<&(T, String)> {
+        self.trie
+            .longest_match(to_match.iter_chars_rev())
+            .map(|i| &self.terms[i])
+    }
+
+    fn match_suffix_longest(&self, to_match: &str) -> Option<XFixMatch<'_, (T, String)>> {
+        self.trie.longest_match(to_match.chars().rev()).map(|i| {
+            let term = &self.terms[i];
+            XFixMatch::new(term, &term.1)
+        })
+    }
+
+    fn match_suffix_chars_longest<S: CharSeqRev + ?Sized>(
+        &self,
+        to_match: &S,
+    ) -> Option<XFixMatch<'_, (T, String)>> {
+        self.trie.longest_match(to_match.iter_chars_rev()).map(|i| {
+            let term = &self.terms[i];
+            XFixMatch::new(term, &term.1)
+        })
+    }
+}
+
+/// 单元测试
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{asserts, prefix_match_dict_pair, x_fix_match_dict, PrefixMatchDict};
+
+    /// 测试用的Narsese系词与连接词
+    const X_FIXES: [&str; 14] = [
+        "-->", "<->", "==>", "<=>", "{--", "--]", "{-]", "&&", "||", "--", "&", "|", "-", "",
+    ];
+
+    /// 测试/与线性字典的一致性
+    #[test]
+    fn test_x_fix_consistent() {
+        let linear: XFixMatchDict = x_fix_match_dict!(
+            "-->" "<->" "==>" "<=>" "{--" "--]" "{-]" "&&" "||" "--" "&" "|" "-" ""
+        );
+        let trie = XFixTrieDict::from(&linear);
+        asserts! {
+            trie.len() => X_FIXES.len(),
+            trie.has("-->"),
+            !trie.has("->"),
+        }
+        for to_match in [
+            "-->A", "--A", "-A", "&&B", "&B", "<=>", "A", "", "{-]", "{-",
+        ] {
+            let chars = to_match.chars().collect::<Vec<_>>();
+            asserts! {
+                trie.match_prefix(to_match) => linear.match_prefix(to_match),
+                trie.match_prefix_char_slice(&chars) => linear.match_prefix_char_slice(&chars),
+            }
+        }
+        // 迭代顺序：从长到短
+        let lengths = trie.iter_x_fixes().map(|s| s.len()).collect::<Vec<_>>();
+        assert!(lengths.windows(2).all(|w| w[0] >= w[1]));
+    }
+
+    /// 测试/后缀匹配
+    /// * 📌总是匹配最长的后缀
+    #[test]
+    fn test_suffix() {
+        let trie = XFixTrieDict::new(["b", "ab", "cab", ""]);
+        asserts! {
+            trie.match_suffix("xcab") => Some(&"cab".to_string()),
+            trie.match_suffix("xab") => Some(&"ab".to_string()),
+            trie.match_suffix("xb") => Some(&"b".to_string()),
+            trie.match_suffix("ba") => Some(&"".to_string()),
+            trie.match_suffix_char_slice(&['c', 'a', 'b']) => Some(&"cab".to_string()),
+            // 其它字符序列
+            trie.match_prefix_chars(&std::collections::VecDeque::from(['c', 'a', 'b'])) => Some(&"cab".to_string()),
+            trie.match_suffix_chars("xcab".as_bytes()) => Some(&"cab".to_string()),
+        }
+        let d =
+            SuffixTrieDictPair::new([("句号", "."), ("问号", "?"), ("目标", "!"), ("真值", "%")]);
+        asserts! {
+            d.match_suffix("<A --> B>.").map(|(name, _)| *name) => Some("句号"),
+            d.match_suffix("<A --> B>?").map(|(name, _)| *name) => Some("问号"),
+            d.match_suffix("<A --> B>") => None,
+            d.get("!").map(|(name, _)| *name) => Some("目标"),
+        }
+    }
+
+    /// 测试/前缀配对
+    #[test]
+    fn test_prefix_pair() {
+        let linear: PrefixMatchDictPair<String> = prefix_match_dict_pair!(
+            "(" => ")"
+            "[" => "]"
+            "{" => "}"
+            "<" => ">"
+            r"\left(" => r"\right)"
+        );
+        let trie = PrefixTrieDictPair::from(&linear);
+        for to_match in ["(A)", "[A]", "<A>", r"\left(A\right)", r"\leftA", "A"] {
+            asserts! {
+                trie.match_prefix(to_match) => linear.match_prefix(to_match),
+            }
+        }
+        // 重复插入
+        let mut trie = trie;
+        asserts! {
+            trie.insert(("(".into(), "]".into())) => None,
+            trie.insert(("⟨".into(), "⟩".into())) => Some(5),
+            trie.match_prefix("⟨A⟩").map(|(_, r)| r.as_str()) => Some("⟩"),
+            trie.get("(").map(|(_, r)| r.as_str()) => Some(")"),
+        }
+    }
+
+    /// 测试/最长匹配与线性字典一致
+    #[test]
+    fn test_match_longest() {
+        let trie = XFixTrieDict::new(X_FIXES);
+        for to_match in ["-->A", "--A", "&&B", "A", "A--", "A|", ""] {
+            asserts! {
+                trie.match_prefix_longest(to_match) => trie.match_prefix_all(to_match).max_by_key(|m| m.len),
+                trie.match_suffix_longest(to_match) => trie.match_suffix_all(to_match).max_by_key(|m| m.len),
+                // 字符序列版本
+                trie.match_prefix_chars_longest(to_match.as_bytes()) => trie.match_prefix_longest(to_match),
+                trie.match_suffix_chars_longest(to_match.as_bytes()) => trie.match_suffix_longest(to_match),
+            }
+        }
+        let d = SuffixTrieDictPair::new([("过去", ":\\:"), ("现在", ":|:"), ("固定", ":")]);
+        let m = d.match_suffix_longest("<A --> B>. :|:").unwrap();
+        asserts! {
+            m.term.0 => "现在",
+            m.len => 3,
+            d.match_suffix_all("<A --> B>. :|:").count() => 2,
+            d.match_suffix_chars_longest(&"<A --> B>. :|:".chars().collect::<Vec<_>>()[..]) => Some(m),
+        }
+        let d = PrefixTrieDictPair::new([(":\\:", "过去"), (":|:", "现在"), (":", "固定")]);
+        let chars = std::collections::VecDeque::from([':', '|', ':', ' ']);
+        let m = d.match_prefix_chars_longest(&chars).unwrap();
+        asserts! {
+            m.term.1 => "现在",
+            m.n_chars => 3,
+        }
+    }
+}
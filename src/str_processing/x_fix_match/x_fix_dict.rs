@@ -39,13 +39,11 @@ impl PrefixMatchDict {
     /// 构造函数
     /// * 支持从任何「元素为『可转换为字符串』的可迭代对象」中转换
     pub fn new(prefixes: impl IntoIterator<Item = impl Into<XFix>>) -> Self {
-        // ? 或许也可以「先新建空值，然后逐个添加」来实现，复杂度 ∑ 1 log 1 ~ n log n
-        Self {
-            x_fixes: prefixes
-                .into_iter()
-                .map(|into_s| into_s.into())
-                .collect::<Vec<_>>(),
-        }
+        // * 🚩现在「先新建空值，然后逐个添加」，复杂度 ∑ 1 log 1 ~ n log n
+        // * 📌直接收集成数组无法保证有序，后续的「查找」「移除」会出错
+        let mut dict = Self::default();
+        dict.extend(prefixes);
+        dict
     }
 
     /// （前后缀无关）判断「是否已有一个词缀」
@@ -69,6 +67,32 @@ impl PrefixMatchDict {
         }
     }
 
+    /// （前后缀无关）移除一个词缀
+    /// * 🚩返回「是否成功移除」
+    /// * 📌移除不影响其它词缀的顺序
+    pub fn remove(&mut self, x_fix: &XFix) -> bool {
+        match self.search(x_fix) {
+            Ok(index) => {
+                self.x_fixes.remove(index);
+                true
+            }
+            Err(..) => false,
+        }
+    }
+
+    /// （前后缀无关）只保留满足条件的词缀
+    /// * 📌类似[`Vec::retain`]，保持原有顺序
+    pub fn retain(&mut self, f: impl FnMut(&XFix) -> bool) {
+        self.x_fixes.retain(f)
+    }
+
+    /// （前后缀无关）合并另一个词缀匹配字典
+    /// * 📌词缀本身就是条目：相同词缀即相同条目，不存在冲突
+    ///   * 故无需[`MergePolicy`]
+    pub fn merge(&mut self, other: Self) {
+        self.extend(other.x_fixes)
+    }
+
     /// （前后缀无关）以特殊顺序迭代「词缀」
     /// * 🎯统一「前缀匹配」与「后缀匹配」的迭代逻辑
     /// * 🚩总是按照「字典顺序」倒序遍历：**长度从长到短**
@@ -113,6 +137,16 @@ impl PrefixMatchDict {
     }
 }
 
+/// 批量插入词缀
+/// * 🚩逐个调用[`XFixMatchDict::insert`]，已有的词缀会被跳过
+impl<S: Into<XFix>> Extend<S> for XFixMatchDict {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        for x_fix in iter {
+            self.insert(x_fix.into());
+        }
+    }
+}
+
 /// 快速生成「词缀匹配字典」
 #[macro_export]
 macro_rules! x_fix_match_dict {
@@ -426,4 +460,66 @@ mod tests {
             r"<A --> B>🚩" => None
         }
     }
+
+    /// 测试/所有匹配与最长、最短匹配
+    #[test]
+    fn test_match_all() {
+        let d = x_fix_match_dict!("-->" "--" "-" "==>" "." ":.");
+        // 有歧义的系词：所有候选都能拿到
+        let all = d
+            .match_prefix_all("-->A")
+            .map(|m| (m.term.as_str(), m.len))
+            .collect::<Vec<_>>();
+        asserts! {
+            all => vec![("-->", 3), ("--", 2), ("-", 1)],
+            d.match_prefix_longest("-->A").map(|m| m.term.as_str()) => Some("-->"),
+            d.match_prefix_shortest("-->A").map(|m| m.term.as_str()) => Some("-"),
+            d.match_prefix_longest("A") => None,
+            d.match_prefix_all("").count() => 0,
+        }
+        // 后缀
+        asserts! {
+            d.match_suffix_all("A:.").count() => 2,
+            d.match_suffix_longest("A:.").map(|m| m.term.as_str()) => Some(":."),
+            d.match_suffix_shortest("A:.").map(|m| m.term.as_str()) => Some("."),
+        }
+        // 长度：字节与字符
+        let d = x_fix_match_dict!("「" "。");
+        let m = d.match_prefix_longest("「A是B」。").unwrap();
+        asserts! {
+            m.len => "「".len(),
+            m.n_chars => 1,
+        }
+    }
+
+    /// 测试/移除、保留、合并
+    #[test]
+    fn test_remove_merge() {
+        let mut d = x_fix_match_dict!("-->" "--" "-" "==>");
+        asserts! {
+            d.remove(&"--".into()),
+            !d.remove(&"--".into()),
+            !d.has(&"--".into()),
+            d.match_prefix("--A") => Some(&"-".into()),
+            d.match_prefix("-->A") => Some(&"-->".into()),
+        }
+        // 保留
+        d.retain(|x_fix| x_fix.starts_with('-'));
+        asserts! {
+            d.iter_x_fixes().collect::<Vec<_>>() => vec!["-->", "-"],
+        }
+        // 合并 | 仍然保持「从长到短」
+        d.merge(x_fix_match_dict!("--" "==>" "-->"));
+        d.extend(["<->"]);
+        asserts! {
+            d.iter_x_fixes().collect::<Vec<_>>() => vec!["==>", "<->", "-->", "--", "-"],
+            d.match_prefix("--A") => Some(&"--".into()),
+        }
+        // 构造函数也保证有序
+        let d = XFixMatchDict::new(["-", "-->", "--"]);
+        asserts! {
+            d.match_prefix("-->A") => Some(&"-->".into()),
+            d.has(&"--".into()),
+        }
+    }
 }